@@ -0,0 +1,173 @@
+use crate::{direct_angle, Angle, List, Quantity, Radian, Vector, TAU};
+use na::RealField;
+use std::f64::consts::PI;
+use std::fmt;
+
+/// Largest number of decimals of the seconds formatted by [`Angle::to_dms`] and
+/// [`Angle::to_hms`], beyond the precision of a double precision angle.
+pub const MAX_SEXAGESIMAL_DECIMALS: usize = 9;
+
+/// Error raised while parsing a sexagesimal angle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AngleError {
+    /// Malformed string.
+    Format(String),
+    /// Minutes or seconds not in $\left[0,60\right)$.
+    OutOfRange(String),
+}
+
+impl fmt::Display for AngleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AngleError::Format(message) => write!(f, "invalid angle format: {}", message),
+            AngleError::OutOfRange(message) => write!(f, "angle out of range: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for AngleError {}
+
+impl<T: RealField> Quantity<T, Radian> {
+    /// Direct angle from 0 to $\tau$ between two vectors and an upward vector. See
+    /// [`direct_angle`].
+    pub fn direct(v1: &Vector<T>, v2: &Vector<T>, up: &Vector<T>) -> Self {
+        Self::new(direct_angle(v1, v2, up))
+    }
+}
+
+impl Angle {
+    /// Angle normalised to $\left[0,\tau\right)$.
+    pub fn normalized(&self) -> Self {
+        let angle = self.radians().rem_euclid(TAU);
+        // The remainder of a tiny negative angle rounds to the full turn.
+        Self::new(if angle < TAU { angle } else { 0.0 })
+    }
+
+    /// Angle normalised to $\left(-\pi,\pi\right]$.
+    pub fn normalized_signed(&self) -> Self {
+        let angle = self.normalized().radians();
+        Self::new(if angle > PI { angle - TAU } else { angle })
+    }
+
+    /// Signed shortest difference from another angle to this angle, in $\left(-\pi,\pi\right]$.
+    pub fn difference(&self, other: &Self) -> Self {
+        (*self - *other).normalized_signed()
+    }
+
+    /// Create the angle from a string of degrees, arcminutes and arcseconds.
+    ///
+    /// The fields are separated by spaces, colons or the symbols `°'"` (or `d`, `m` and `s`). The
+    /// minutes and seconds are optional, and a sign can start the string.
+    ///
+    /// ```
+    /// use tool::Angle;
+    ///
+    /// let angle = Angle::from_dms("-12°30'36\"").unwrap();
+    /// assert!((angle.degrees() + 12.51).abs() < 1e-12);
+    /// assert_eq!(Angle::from_dms("-12 30 36").unwrap(), angle);
+    /// ```
+    pub fn from_dms(string: &str) -> Result<Self, AngleError> {
+        parse_sexagesimal(string, &['°', 'd', '\'', '′', 'm', '"', '″', 's'])
+            .map(Self::from_degrees)
+    }
+
+    /// Create the angle from a string of hours, minutes and seconds of time, with one hour equal
+    /// to 15 degrees.
+    ///
+    /// The fields are separated by spaces, colons or the letters `h`, `m` and `s`. The minutes and
+    /// seconds are optional, and a sign can start the string.
+    pub fn from_hms(string: &str) -> Result<Self, AngleError> {
+        parse_sexagesimal(string, &['h', 'm', 's']).map(|hours| Self::from_degrees(hours * 15.0))
+    }
+
+    /// Format the angle as degrees, arcminutes and arcseconds, with a number of decimals for
+    /// the arcseconds, such as `-12°30'36.00"`. The number of decimals is capped to
+    /// [`MAX_SEXAGESIMAL_DECIMALS`].
+    pub fn to_dms(&self, decimals: usize) -> String {
+        let (negative, degrees, minutes, seconds) = sexagesimal(self.degrees(), decimals);
+        format!(
+            "{}{}°{:02}'{}\"",
+            if negative { "-" } else { "" },
+            degrees,
+            minutes,
+            seconds
+        )
+    }
+
+    /// Format the angle normalised to $\left[0,\tau\right)$ as hours, minutes and seconds of time,
+    /// with a number of decimals for the seconds, such as `12h30m36.00s`. The number of decimals
+    /// is capped to [`MAX_SEXAGESIMAL_DECIMALS`].
+    pub fn to_hms(&self, decimals: usize) -> String {
+        let (_, hours, minutes, seconds) =
+            sexagesimal(self.normalized().degrees() / 15.0, decimals);
+        format!("{}h{:02}m{}s", hours % 24, minutes, seconds)
+    }
+}
+
+impl Quantity<List<f64>, Radian> {
+    /// Unwrap the phase of a list of angles: the jumps between consecutive angles larger than
+    /// $\pi$ are removed by adding multiples of $\tau$, so that the angles are continuous.
+    pub fn unwrapped(&self) -> Self {
+        let angles = self.value();
+        let mut unwrapped = angles.clone();
+        for index in 1..angles.len() {
+            let step = Angle::new(angles[index]).difference(&Angle::new(angles[index - 1]));
+            unwrapped[index] = unwrapped[index - 1] + step.radians();
+        }
+        Self::new(unwrapped)
+    }
+}
+
+/// Parse a sexagesimal string into the value of its first unit.
+fn parse_sexagesimal(string: &str, separators: &[char]) -> Result<f64, AngleError> {
+    let error = || AngleError::Format(string.to_string());
+    let trimmed = string.trim();
+    let (sign, unsigned) = match trimmed.strip_prefix('-') {
+        Some(unsigned) => (-1.0, unsigned),
+        None => (1.0, trimmed.strip_prefix('+').unwrap_or(trimmed)),
+    };
+    let fields = unsigned
+        .split(|c: char| c.is_whitespace() || c == ':' || separators.contains(&c))
+        .filter(|field| !field.is_empty())
+        .map(|field| {
+            if field.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+                field.parse::<f64>().map_err(|_| error())
+            } else {
+                Err(error())
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if fields.is_empty() || fields.len() > 3 {
+        return Err(error());
+    }
+    if fields[1..].iter().any(|&field| field >= 60.0) {
+        return Err(AngleError::OutOfRange(string.to_string()));
+    }
+    let value = fields
+        .iter()
+        .rev()
+        .fold(0.0, |value, &field| field + value / 60.0);
+    Ok(sign * value)
+}
+
+/// Split a value into its sign, integer part, minutes and formatted seconds, rounded to a number of
+/// decimals.
+fn sexagesimal(value: f64, decimals: usize) -> (bool, u64, u64, String) {
+    let decimals = decimals.min(MAX_SEXAGESIMAL_DECIMALS);
+    let scale = 10_u64.pow(decimals as u32);
+    let total = (value.abs() * 3600.0 * scale as f64).round() as u64;
+    let seconds = total % (60 * scale);
+    let minutes = total / (60 * scale) % 60;
+    let units = total / (3600 * scale);
+    let seconds = if decimals == 0 {
+        format!("{:02}", seconds)
+    } else {
+        format!(
+            "{:02}.{:0width$}",
+            seconds / scale,
+            seconds % scale,
+            width = decimals
+        )
+    };
+    (value < 0.0 && total > 0, units, minutes, seconds)
+}
@@ -0,0 +1,211 @@
+use crate::{
+    RotationModel, Vector, DAY, DEG2RAD, GM_SUN, GRAVITATIONAL_CONSTANT, HOUR, SOLAR_RADIUS,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Error raised while loading bodies.
+#[derive(Debug)]
+pub enum BodyError {
+    /// Error from the file system.
+    Io(std::io::Error),
+    /// Malformed JSON content.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for BodyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BodyError::Io(error) => write!(f, "{}", error),
+            BodyError::Json(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for BodyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BodyError::Io(error) => Some(error),
+            BodyError::Json(error) => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for BodyError {
+    fn from(error: std::io::Error) -> Self {
+        BodyError::Io(error)
+    }
+}
+
+impl From<serde_json::Error> for BodyError {
+    fn from(error: serde_json::Error) -> Self {
+        BodyError::Json(error)
+    }
+}
+
+/// Physical and rotational properties of a body of the Solar System.
+///
+/// All the values are in SI units and radians, also in JSON. The orientation follows the IAU
+/// convention of the [`RotationModel`], at the epoch J2000.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Body {
+    /// Name of the body.
+    pub name: String,
+    /// Gravitational parameter $GM$, in m³/s².
+    pub gm: f64,
+    /// Radii along the axes of the body-fixed frame, the last one being the polar radius, in m.
+    pub radii: Vector<f64>,
+    /// Mean radius, in m.
+    pub mean_radius: f64,
+    /// Sidereal rotation period, negative for a retrograde rotation, in s.
+    pub rotation_period: f64,
+    /// Right ascension of the pole at J2000.
+    pub pole_right_ascension: f64,
+    /// Declination of the pole at J2000.
+    pub pole_declination: f64,
+    /// Angle of the prime meridian at J2000.
+    pub prime_meridian: f64,
+}
+
+impl Body {
+    /// Mass of the body from its gravitational parameter, in kg.
+    pub fn mass(&self) -> f64 {
+        self.gm / GRAVITATIONAL_CONSTANT
+    }
+
+    /// Rotation model of the body, with times in seconds past J2000.
+    pub fn rotation_model(&self) -> RotationModel<f64> {
+        RotationModel {
+            right_ascension: self.pole_right_ascension,
+            declination: self.pole_declination,
+            prime_meridian: self.prime_meridian,
+            period: self.rotation_period,
+            epoch: 0.0,
+        }
+    }
+}
+
+/// Built-in bodies: name, GM in km³/s², equatorial and polar radii in km, mean radius in km,
+/// rotation period in days, right ascension and declination of the pole and prime meridian in
+/// degrees.
+#[allow(clippy::type_complexity)]
+#[rustfmt::skip]
+const BUILTIN_BODIES: [(&str, f64, f64, f64, f64, f64, f64, f64, f64); 11] = [
+    ("Sun", GM_SUN * 1e-9, SOLAR_RADIUS * 1e-3, SOLAR_RADIUS * 1e-3, SOLAR_RADIUS * 1e-3, 25.38, 286.13, 63.87, 84.176),
+    ("Mercury", 22_031.868_551, 2440.53, 2438.26, 2439.4, 58.6462, 281.0103, 61.4155, 329.5988),
+    ("Venus", 324_858.592, 6051.8, 6051.8, 6051.8, -243.0226, 272.76, 67.16, 160.20),
+    ("Earth", 398_600.435_507, 6378.1366, 6356.7519, 6371.0084, 0.997_269_566, 0.0, 90.0, 190.147),
+    ("Moon", 4_902.800_118, 1737.4, 1737.4, 1737.4, 27.321_661, 269.9949, 66.5392, 38.3213),
+    ("Mars", 42_828.375_816, 3396.19, 3376.20, 3389.50, 1.025_956_76, 317.269_202, 54.432_516, 176.049_863),
+    ("Jupiter", 126_712_764.1, 71_492.0, 66_854.0, 69_911.0, 9.9250 * HOUR / DAY, 268.056_595, 64.495_303, 284.95),
+    ("Saturn", 37_940_584.841_8, 60_268.0, 54_364.0, 58_232.0, 10.656_36 * HOUR / DAY, 40.589, 83.537, 38.90),
+    ("Uranus", 5_794_556.4, 25_559.0, 24_973.0, 25_362.0, -17.24 * HOUR / DAY, 257.311, -15.175, 203.81),
+    ("Neptune", 6_836_527.100_58, 24_764.0, 24_341.0, 24_622.0, 16.11 * HOUR / DAY, 299.36, 43.46, 253.18),
+    ("Pluto", 975.5, 1188.3, 1188.3, 1188.3, 6.3872, 132.993, -6.163, 302.695),
+];
+
+/// Collection of [`Body`]s looked up by name.
+///
+/// The built-in bodies are the Sun, the planets, the Moon and Pluto. Their gravitational
+/// parameters are from the ephemeris DE440, with the system values for the planets with moons,
+/// and their radii and orientations from the IAU WGCCRE reports. More bodies can be loaded
+/// from a JSON array of bodies, such as Bennu with the values of Lauretta et al. (2019).
+///
+/// ```
+/// use tool::BodyDatabase;
+///
+/// let mut database = BodyDatabase::builtin();
+/// database
+///     .load_json_str(
+///         r#"[{"name": "Bennu", "gm": 4.892, "radii": [282.5, 267.5, 254.0],
+///              "mean_radius": 244.89, "rotation_period": 15465.8052,
+///              "pole_right_ascension": 1.4913838, "pole_declination": -1.0534807,
+///              "prime_meridian": 2.6279423}]"#,
+///     )
+///     .unwrap();
+/// assert!(database.get("bennu").is_some());
+/// ```
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct BodyDatabase {
+    bodies: Vec<Body>,
+}
+
+impl BodyDatabase {
+    /// Empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Database of the built-in bodies.
+    pub fn builtin() -> Self {
+        let mut database = Self::new();
+        for &(name, gm, equatorial, polar, mean, period, right_ascension, declination, meridian) in
+            BUILTIN_BODIES.iter()
+        {
+            database.insert(Body {
+                name: name.to_string(),
+                gm: gm * 1e9,
+                radii: Vector::new(equatorial, equatorial, polar) * 1e3,
+                mean_radius: mean * 1e3,
+                rotation_period: period * DAY,
+                pole_right_ascension: right_ascension * DEG2RAD,
+                pole_declination: declination * DEG2RAD,
+                prime_meridian: meridian * DEG2RAD,
+            });
+        }
+        database
+    }
+
+    /// Database of the bodies of a JSON file.
+    pub fn from_json_file<P: AsRef<Path>>(path: P) -> Result<Self, BodyError> {
+        let mut database = Self::new();
+        database.load_json_file(path)?;
+        Ok(database)
+    }
+
+    /// Add the bodies of a JSON file, replacing the bodies with the same names.
+    pub fn load_json_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), BodyError> {
+        self.load_json_str(&fs::read_to_string(path)?)
+    }
+
+    /// Add the bodies of a JSON string, replacing the bodies with the same names.
+    pub fn load_json_str(&mut self, json: &str) -> Result<(), BodyError> {
+        let bodies: Vec<Body> = serde_json::from_str(json)?;
+        for body in bodies {
+            self.insert(body);
+        }
+        Ok(())
+    }
+
+    /// Write the bodies to a JSON file.
+    pub fn write_json_file<P: AsRef<Path>>(&self, path: P) -> Result<(), BodyError> {
+        fs::write(path, serde_json::to_string_pretty(&self.bodies)?)?;
+        Ok(())
+    }
+
+    /// Add a body, replacing the body with the same name, ignoring the case.
+    pub fn insert(&mut self, body: Body) {
+        match self
+            .bodies
+            .iter_mut()
+            .find(|other| other.name.eq_ignore_ascii_case(&body.name))
+        {
+            Some(other) => *other = body,
+            None => self.bodies.push(body),
+        }
+    }
+
+    /// Body with a name, ignoring the case.
+    pub fn get(&self, name: &str) -> Option<&Body> {
+        self.bodies
+            .iter()
+            .find(|body| body.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Bodies of the database, in insertion order.
+    pub fn bodies(&self) -> &[Body] {
+        &self.bodies
+    }
+}
@@ -13,6 +13,10 @@ pub const HOUR: f64 = MINUTE * 60.0;
 pub const DAY: f64 = HOUR * 24.0;
 /// Year alias for 365.25 days.
 pub const YEAR: f64 = DAY * 365.25;
+/// Julian date of the epoch J2000, 2000-01-01T12:00:00 TT.
+pub const JULIAN_DATE_J2000: f64 = 2_451_545.0;
+/// Difference between the Julian date and the modified Julian date.
+pub const MODIFIED_JULIAN_DATE_OFFSET: f64 = 2_400_000.5;
 
 // Distance related constants
 // These constants are defined in meters.
@@ -24,3 +28,32 @@ pub const ASTRONAUMICAL_UNIT: f64 = 1.495978707e11;
 pub const DEG2RAD: f64 = TAU / 360.0;
 /// Conversion from radians to degrees.
 pub const RAD2DEG: f64 = 360.0 / TAU;
+
+// Angle related constants
+// These constants are defined in radians.
+/// Obliquity of the ecliptic at the epoch J2000, 84381.448 arcseconds.
+pub const OBLIQUITY_J2000: f64 = 84381.448 / 3600.0 * DEG2RAD;
+
+// Physical constants
+// These constants are the CODATA 2018 recommended values, in SI units.
+/// Newtonian constant of gravitation $G$, in m³/kg/s².
+pub const GRAVITATIONAL_CONSTANT: f64 = 6.674_30e-11;
+/// Speed of light in vacuum $c$, in m/s.
+pub const SPEED_OF_LIGHT: f64 = 299_792_458.0;
+/// Stefan–Boltzmann constant $\sigma$, in W/m²/K⁴.
+pub const STEFAN_BOLTZMANN: f64 = 5.670_374_419e-8;
+/// Boltzmann constant $k_B$, in J/K.
+pub const BOLTZMANN: f64 = 1.380_649e-23;
+/// Planck constant $h$, in J·s.
+pub const PLANCK: f64 = 6.626_070_15e-34;
+
+// Solar constants
+// These constants are the IAU 2015 nominal values, in SI units.
+/// Total solar irradiance at one [astronaumical unit][ASTRONAUMICAL_UNIT], in W/m².
+pub const SOLAR_CONSTANT: f64 = 1361.0;
+/// Solar luminosity, in W.
+pub const SOLAR_LUMINOSITY: f64 = 3.828e26;
+/// Solar radius, in m.
+pub const SOLAR_RADIUS: f64 = 6.957e8;
+/// Gravitational parameter of the Sun $GM_\odot$ from the ephemeris DE440, in m³/s².
+pub const GM_SUN: f64 = 1.327_124_400_412_794e20;
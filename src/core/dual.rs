@@ -0,0 +1,766 @@
+use na::{ComplexField, Field, RealField, SimdValue};
+use num_traits::{Bounded, FromPrimitive, Num, One, Signed, Zero};
+use simba::scalar::SubsetOf;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{
+    Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
+};
+
+/// Dual number for forward-mode automatic differentiation.
+///
+/// ## Expression
+///
+/// A dual number $a+b\varepsilon$ extends the reals with $\varepsilon^2=0$. A function $f$
+/// evaluated on $x+\varepsilon$ gives its derivative along with its value,
+///
+/// $$f\left(x+\varepsilon\right)=f\left(x\right)+f'\left(x\right)\varepsilon$$
+///
+/// ## Usage
+///
+/// [`Dual`] implements [`RealField`], so that a function written for any [`RealField`] is
+/// differentiated by evaluating it on the [variable][Dual::variable] and reading the
+/// [dual part][Dual::dual]. The comparisons only involve the real parts.
+///
+/// ```
+/// use nalgebra as na;
+/// use tool::Dual;
+///
+/// fn function<T: na::RealField>(x: T) -> T {
+///     x.powi(3) - x.sin() * na::convert(2.0)
+/// }
+///
+/// let x = 0.5f64;
+/// let result = function(Dual::variable(x));
+/// assert_eq!(result.real, function(x));
+/// assert!((result.dual - (3.0 * x * x - 2.0 * x.cos())).abs() < 1e-15);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dual<T>
+where
+    T: RealField,
+{
+    /// Real part, the value.
+    pub real: T,
+    /// Dual part, the derivative.
+    pub dual: T,
+}
+
+impl<T> Dual<T>
+where
+    T: RealField,
+{
+    /// Create a dual number from its real and dual parts.
+    pub fn new(real: T, dual: T) -> Self {
+        Self { real, dual }
+    }
+
+    /// Constant, of null derivative.
+    pub fn constant(real: T) -> Self {
+        Self::new(real, T::zero())
+    }
+
+    /// Variable of the differentiation, of unit derivative.
+    pub fn variable(real: T) -> Self {
+        Self::new(real, T::one())
+    }
+
+    /// Dual number of the real part of a function and of its derivative by the chain rule.
+    fn chain(self, real: T, derivative: T) -> Self {
+        Self::new(real, self.dual * derivative)
+    }
+}
+
+impl<T> PartialEq for Dual<T>
+where
+    T: RealField,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.real == other.real
+    }
+}
+
+impl<T> PartialOrd for Dual<T>
+where
+    T: RealField,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.real.partial_cmp(&other.real)
+    }
+}
+
+impl<T> fmt::Display for Dual<T>
+where
+    T: RealField,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} + {}ε", self.real, self.dual)
+    }
+}
+
+impl<T> Neg for Dual<T>
+where
+    T: RealField,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.real, -self.dual)
+    }
+}
+
+impl<T> Add for Dual<T>
+where
+    T: RealField,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.real + other.real, self.dual + other.dual)
+    }
+}
+
+impl<T> Sub for Dual<T>
+where
+    T: RealField,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.real - other.real, self.dual - other.dual)
+    }
+}
+
+impl<T> Mul for Dual<T>
+where
+    T: RealField,
+{
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self::new(
+            self.real * other.real,
+            self.dual * other.real + self.real * other.dual,
+        )
+    }
+}
+
+impl<T> Div for Dual<T>
+where
+    T: RealField,
+{
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self {
+        Self::new(
+            self.real / other.real,
+            (self.dual * other.real - self.real * other.dual) / (other.real * other.real),
+        )
+    }
+}
+
+impl<T> Rem for Dual<T>
+where
+    T: RealField,
+{
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        let quotient = (self.real / other.real).trunc();
+        Self::new(self.real % other.real, self.dual - other.dual * quotient)
+    }
+}
+
+/// Implement the assignment operators from the binary operators.
+macro_rules! impl_assign {
+    ($($trait: ident, $method: ident, $operator: tt);*) => {$(
+        impl<T> $trait for Dual<T>
+        where
+            T: RealField,
+        {
+            fn $method(&mut self, other: Self) {
+                *self = *self $operator other;
+            }
+        }
+    )*};
+}
+
+impl_assign!(
+    AddAssign, add_assign, +;
+    SubAssign, sub_assign, -;
+    MulAssign, mul_assign, *;
+    DivAssign, div_assign, /;
+    RemAssign, rem_assign, %
+);
+
+impl<T> Zero for Dual<T>
+where
+    T: RealField,
+{
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+
+    fn is_zero(&self) -> bool {
+        self.real.is_zero()
+    }
+}
+
+impl<T> One for Dual<T>
+where
+    T: RealField,
+{
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+}
+
+impl<T> Num for Dual<T>
+where
+    T: RealField,
+{
+    type FromStrRadixErr = T::FromStrRadixErr;
+
+    fn from_str_radix(string: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(string, radix).map(Self::constant)
+    }
+}
+
+impl<T> Signed for Dual<T>
+where
+    T: RealField,
+{
+    fn abs(&self) -> Self {
+        ComplexField::abs(*self)
+    }
+
+    fn abs_sub(&self, other: &Self) -> Self {
+        if self.real <= other.real {
+            Self::zero()
+        } else {
+            *self - *other
+        }
+    }
+
+    fn signum(&self) -> Self {
+        Self::constant(Signed::signum(&self.real))
+    }
+
+    fn is_positive(&self) -> bool {
+        self.real.is_positive()
+    }
+
+    fn is_negative(&self) -> bool {
+        self.real.is_negative()
+    }
+}
+
+impl<T> Bounded for Dual<T>
+where
+    T: RealField,
+{
+    fn min_value() -> Self {
+        Self::constant(T::min_value())
+    }
+
+    fn max_value() -> Self {
+        Self::constant(T::max_value())
+    }
+}
+
+impl<T> FromPrimitive for Dual<T>
+where
+    T: RealField,
+{
+    fn from_i64(n: i64) -> Option<Self> {
+        T::from_i64(n).map(Self::constant)
+    }
+
+    fn from_u64(n: u64) -> Option<Self> {
+        T::from_u64(n).map(Self::constant)
+    }
+
+    fn from_f64(n: f64) -> Option<Self> {
+        T::from_f64(n).map(Self::constant)
+    }
+}
+
+impl<T> SimdValue for Dual<T>
+where
+    T: RealField,
+{
+    type Element = Self;
+    type SimdBool = bool;
+
+    fn lanes() -> usize {
+        1
+    }
+
+    fn splat(value: Self) -> Self {
+        value
+    }
+
+    fn extract(&self, _: usize) -> Self {
+        *self
+    }
+
+    unsafe fn extract_unchecked(&self, _: usize) -> Self {
+        *self
+    }
+
+    fn replace(&mut self, _: usize, value: Self) {
+        *self = value
+    }
+
+    unsafe fn replace_unchecked(&mut self, _: usize, value: Self) {
+        *self = value
+    }
+
+    fn select(self, condition: bool, other: Self) -> Self {
+        if condition {
+            self
+        } else {
+            other
+        }
+    }
+}
+
+impl<T> Field for Dual<T> where T: RealField {}
+
+impl<T> SubsetOf<Dual<T>> for Dual<T>
+where
+    T: RealField,
+{
+    fn to_superset(&self) -> Self {
+        *self
+    }
+
+    fn from_superset_unchecked(element: &Self) -> Self {
+        *element
+    }
+
+    fn is_in_subset(_: &Self) -> bool {
+        true
+    }
+}
+
+impl<T> SubsetOf<Dual<T>> for f64
+where
+    T: RealField,
+{
+    fn to_superset(&self) -> Dual<T> {
+        Dual::constant(T::from_subset(self))
+    }
+
+    fn from_superset_unchecked(element: &Dual<T>) -> Self {
+        element.real.to_subset_unchecked()
+    }
+
+    fn is_in_subset(element: &Dual<T>) -> bool {
+        element.dual.is_zero() && element.real.is_in_subset()
+    }
+}
+
+impl<T> approx::AbsDiffEq for Dual<T>
+where
+    T: RealField,
+{
+    type Epsilon = Self;
+
+    fn default_epsilon() -> Self {
+        Self::constant(T::default_epsilon())
+    }
+
+    fn abs_diff_eq(&self, other: &Self, epsilon: Self) -> bool {
+        self.real.abs_diff_eq(&other.real, epsilon.real)
+            && self.dual.abs_diff_eq(&other.dual, epsilon.real)
+    }
+}
+
+impl<T> approx::RelativeEq for Dual<T>
+where
+    T: RealField,
+{
+    fn default_max_relative() -> Self {
+        Self::constant(T::default_max_relative())
+    }
+
+    fn relative_eq(&self, other: &Self, epsilon: Self, max_relative: Self) -> bool {
+        self.real
+            .relative_eq(&other.real, epsilon.real, max_relative.real)
+            && self
+                .dual
+                .relative_eq(&other.dual, epsilon.real, max_relative.real)
+    }
+}
+
+impl<T> approx::UlpsEq for Dual<T>
+where
+    T: RealField,
+{
+    fn default_max_ulps() -> u32 {
+        T::default_max_ulps()
+    }
+
+    fn ulps_eq(&self, other: &Self, epsilon: Self, max_ulps: u32) -> bool {
+        self.real.ulps_eq(&other.real, epsilon.real, max_ulps)
+            && self.dual.ulps_eq(&other.dual, epsilon.real, max_ulps)
+    }
+}
+
+impl<T> ComplexField for Dual<T>
+where
+    T: RealField,
+{
+    type RealField = Self;
+
+    fn from_real(real: Self) -> Self {
+        real
+    }
+
+    fn real(self) -> Self {
+        self
+    }
+
+    fn imaginary(self) -> Self {
+        Self::zero()
+    }
+
+    fn modulus(self) -> Self {
+        ComplexField::abs(self)
+    }
+
+    fn modulus_squared(self) -> Self {
+        self * self
+    }
+
+    fn argument(self) -> Self {
+        if self.real >= T::zero() {
+            Self::zero()
+        } else {
+            Self::pi()
+        }
+    }
+
+    fn norm1(self) -> Self {
+        ComplexField::abs(self)
+    }
+
+    fn scale(self, factor: Self) -> Self {
+        self * factor
+    }
+
+    fn unscale(self, factor: Self) -> Self {
+        self / factor
+    }
+
+    fn floor(self) -> Self {
+        Self::constant(self.real.floor())
+    }
+
+    fn ceil(self) -> Self {
+        Self::constant(self.real.ceil())
+    }
+
+    fn round(self) -> Self {
+        Self::constant(self.real.round())
+    }
+
+    fn trunc(self) -> Self {
+        Self::constant(self.real.trunc())
+    }
+
+    fn fract(self) -> Self {
+        Self::new(self.real.fract(), self.dual)
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+
+    fn signum(self) -> Self {
+        Self::constant(ComplexField::signum(self.real))
+    }
+
+    fn abs(self) -> Self {
+        self.chain(self.real.abs(), ComplexField::signum(self.real))
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        let real = self.real.hypot(other.real);
+        Self::new(
+            real,
+            (self.real * self.dual + other.real * other.dual) / real,
+        )
+    }
+
+    fn recip(self) -> Self {
+        let real = self.real.recip();
+        self.chain(real, -real * real)
+    }
+
+    fn conjugate(self) -> Self {
+        self
+    }
+
+    fn sin(self) -> Self {
+        let (sin, cos) = self.real.sin_cos();
+        self.chain(sin, cos)
+    }
+
+    fn cos(self) -> Self {
+        let (sin, cos) = self.real.sin_cos();
+        self.chain(cos, -sin)
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        let (sin, cos) = self.real.sin_cos();
+        (self.chain(sin, cos), self.chain(cos, -sin))
+    }
+
+    fn tan(self) -> Self {
+        let real = self.real.tan();
+        self.chain(real, T::one() + real * real)
+    }
+
+    fn asin(self) -> Self {
+        self.chain(
+            self.real.asin(),
+            (T::one() - self.real * self.real).sqrt().recip(),
+        )
+    }
+
+    fn acos(self) -> Self {
+        self.chain(
+            self.real.acos(),
+            -(T::one() - self.real * self.real).sqrt().recip(),
+        )
+    }
+
+    fn atan(self) -> Self {
+        self.chain(self.real.atan(), (T::one() + self.real * self.real).recip())
+    }
+
+    fn sinh(self) -> Self {
+        self.chain(self.real.sinh(), self.real.cosh())
+    }
+
+    fn cosh(self) -> Self {
+        self.chain(self.real.cosh(), self.real.sinh())
+    }
+
+    fn tanh(self) -> Self {
+        let real = self.real.tanh();
+        self.chain(real, T::one() - real * real)
+    }
+
+    fn asinh(self) -> Self {
+        self.chain(
+            self.real.asinh(),
+            (self.real * self.real + T::one()).sqrt().recip(),
+        )
+    }
+
+    fn acosh(self) -> Self {
+        self.chain(
+            self.real.acosh(),
+            (self.real * self.real - T::one()).sqrt().recip(),
+        )
+    }
+
+    fn atanh(self) -> Self {
+        self.chain(
+            self.real.atanh(),
+            (T::one() - self.real * self.real).recip(),
+        )
+    }
+
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+
+    fn log2(self) -> Self {
+        self.chain(self.real.log2(), (self.real * T::ln_2()).recip())
+    }
+
+    fn log10(self) -> Self {
+        self.chain(self.real.log10(), (self.real * T::ln_10()).recip())
+    }
+
+    fn ln(self) -> Self {
+        self.chain(self.real.ln(), self.real.recip())
+    }
+
+    fn ln_1p(self) -> Self {
+        self.chain(self.real.ln_1p(), (T::one() + self.real).recip())
+    }
+
+    fn sqrt(self) -> Self {
+        let real = self.real.sqrt();
+        self.chain(real, (real + real).recip())
+    }
+
+    fn exp(self) -> Self {
+        let real = self.real.exp();
+        self.chain(real, real)
+    }
+
+    fn exp2(self) -> Self {
+        let real = self.real.exp2();
+        self.chain(real, real * T::ln_2())
+    }
+
+    fn exp_m1(self) -> Self {
+        self.chain(self.real.exp_m1(), self.real.exp())
+    }
+
+    fn powi(self, n: i32) -> Self {
+        if n == 0 {
+            return Self::one();
+        }
+        self.chain(
+            self.real.powi(n),
+            self.real.powi(n - 1) * T::from_i32(n).unwrap(),
+        )
+    }
+
+    fn powf(self, n: Self) -> Self {
+        let real = self.real.powf(n.real);
+        let mut power = self.chain(real, n.real * self.real.powf(n.real - T::one()));
+        // The logarithm is only involved when the exponent is differentiated.
+        if !n.dual.is_zero() {
+            power.dual += real * self.real.ln() * n.dual;
+        }
+        power
+    }
+
+    fn powc(self, n: Self) -> Self {
+        self.powf(n)
+    }
+
+    fn cbrt(self) -> Self {
+        let real = self.real.cbrt();
+        self.chain(real, (real * real * na::convert(3.0)).recip())
+    }
+
+    fn is_finite(&self) -> bool {
+        self.real.is_finite() && self.dual.is_finite()
+    }
+
+    fn try_sqrt(self) -> Option<Self> {
+        if self.real >= T::zero() {
+            Some(self.sqrt())
+        } else {
+            None
+        }
+    }
+}
+
+impl<T> RealField for Dual<T>
+where
+    T: RealField,
+{
+    fn is_sign_positive(self) -> bool {
+        self.real.is_sign_positive()
+    }
+
+    fn is_sign_negative(self) -> bool {
+        self.real.is_sign_negative()
+    }
+
+    fn copysign(self, to: Self) -> Self {
+        if self.is_sign_positive() {
+            ComplexField::abs(to)
+        } else {
+            -ComplexField::abs(to)
+        }
+    }
+
+    fn max(self, other: Self) -> Self {
+        if other.real > self.real {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn min(self, other: Self) -> Self {
+        if other.real < self.real {
+            other
+        } else {
+            self
+        }
+    }
+
+    fn clamp(self, min: Self, max: Self) -> Self {
+        RealField::min(RealField::max(self, min), max)
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        Self::new(
+            self.real.atan2(other.real),
+            (other.real * self.dual - self.real * other.dual)
+                / (self.real * self.real + other.real * other.real),
+        )
+    }
+
+    fn pi() -> Self {
+        Self::constant(T::pi())
+    }
+
+    fn two_pi() -> Self {
+        Self::constant(T::two_pi())
+    }
+
+    fn frac_pi_2() -> Self {
+        Self::constant(T::frac_pi_2())
+    }
+
+    fn frac_pi_3() -> Self {
+        Self::constant(T::frac_pi_3())
+    }
+
+    fn frac_pi_4() -> Self {
+        Self::constant(T::frac_pi_4())
+    }
+
+    fn frac_pi_6() -> Self {
+        Self::constant(T::frac_pi_6())
+    }
+
+    fn frac_pi_8() -> Self {
+        Self::constant(T::frac_pi_8())
+    }
+
+    fn frac_1_pi() -> Self {
+        Self::constant(T::frac_1_pi())
+    }
+
+    fn frac_2_pi() -> Self {
+        Self::constant(T::frac_2_pi())
+    }
+
+    fn frac_2_sqrt_pi() -> Self {
+        Self::constant(T::frac_2_sqrt_pi())
+    }
+
+    fn e() -> Self {
+        Self::constant(T::e())
+    }
+
+    fn log2_e() -> Self {
+        Self::constant(T::log2_e())
+    }
+
+    fn log10_e() -> Self {
+        Self::constant(T::log10_e())
+    }
+
+    fn ln_2() -> Self {
+        Self::constant(T::ln_2())
+    }
+
+    fn ln_10() -> Self {
+        Self::constant(T::ln_10())
+    }
+}
@@ -0,0 +1,618 @@
+use crate::spk::hermite;
+use crate::{Angle, Epoch, List, TimeScale, Vector, Vectors, ASTRONAUMICAL_UNIT, DAY};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Error raised while reading or interpolating an ephemeris table.
+#[derive(Debug)]
+pub enum EphemerisIoError {
+    /// Error from the file system.
+    Io(std::io::Error),
+    /// Malformed line of a text file, with the line number starting at 1.
+    Parse {
+        /// Number of the line.
+        line: usize,
+        /// Description of the error.
+        message: String,
+    },
+    /// Malformed structure of the file.
+    Format(String),
+    /// Julian date outside of the table.
+    OutOfRange {
+        /// Requested Julian date.
+        julian_date: f64,
+        /// First Julian date of the table.
+        first: f64,
+        /// Last Julian date of the table.
+        last: f64,
+    },
+}
+
+impl fmt::Display for EphemerisIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EphemerisIoError::Io(error) => write!(f, "{}", error),
+            EphemerisIoError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            EphemerisIoError::Format(message) => write!(f, "{}", message),
+            EphemerisIoError::OutOfRange {
+                julian_date,
+                first,
+                last,
+            } => write!(
+                f,
+                "Julian date {} outside of the table from {} to {}",
+                julian_date, first, last
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EphemerisIoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EphemerisIoError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for EphemerisIoError {
+    fn from(error: std::io::Error) -> Self {
+        EphemerisIoError::Io(error)
+    }
+}
+
+/// State of a vector table, in meters and meters per second.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorRecord {
+    /// Julian date in TDB.
+    pub julian_date: f64,
+    /// Position.
+    pub position: Vector<f64>,
+    /// Velocity, if the table has one.
+    pub velocity: Option<Vector<f64>>,
+}
+
+/// Table of states, with increasing Julian dates in TDB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorTable {
+    records: Vec<VectorRecord>,
+}
+
+impl VectorTable {
+    /// Records of the table.
+    pub fn records(&self) -> &[VectorRecord] {
+        &self.records
+    }
+
+    /// Julian dates of the records, in TDB.
+    pub fn julian_dates(&self) -> List<f64> {
+        List::from_iterator(
+            self.records.len(),
+            self.records.iter().map(|record| record.julian_date),
+        )
+    }
+
+    /// Positions of the records.
+    pub fn positions(&self) -> Vectors<f64> {
+        Vectors::from_fn(self.records.len(), |row, column| {
+            self.records[column].position[row]
+        })
+    }
+
+    /// Velocities of the records, if the table has them.
+    pub fn velocities(&self) -> Option<Vectors<f64>> {
+        let velocities: Option<Vec<Vector<f64>>> =
+            self.records.iter().map(|record| record.velocity).collect();
+        velocities.map(|velocities| Vectors::from_columns(&velocities))
+    }
+
+    /// Position and velocity at an epoch, interpolated between the surrounding records.
+    ///
+    /// With velocities, the components are cubic Hermite polynomials matching the positions and
+    /// the velocities of the two records, and the velocity is their derivative. Without
+    /// velocities, the position is interpolated linearly and no velocity is given.
+    pub fn interpolate(
+        &self,
+        epoch: &Epoch,
+    ) -> Result<(Vector<f64>, Option<Vector<f64>>), EphemerisIoError> {
+        let julian_date = epoch.julian_date(TimeScale::TDB);
+        let index = interval(&self.julian_dates(), julian_date)?;
+        let (first, second) = (&self.records[index], &self.records[index + 1]);
+        let times = [0.0, (second.julian_date - first.julian_date) * DAY];
+        let time = (julian_date - first.julian_date) * DAY;
+        match (first.velocity, second.velocity) {
+            (Some(first_velocity), Some(second_velocity)) => {
+                let mut position = Vector::zeros();
+                let mut velocity = Vector::zeros();
+                for component in 0..3 {
+                    let (value, derivative) = hermite(
+                        &times,
+                        &[first.position[component], second.position[component]],
+                        &[first_velocity[component], second_velocity[component]],
+                        time,
+                    );
+                    position[component] = value;
+                    velocity[component] = derivative;
+                }
+                Ok((position, Some(velocity)))
+            }
+            _ => {
+                let fraction = time / times[1];
+                Ok((first.position.lerp(&second.position, fraction), None))
+            }
+        }
+    }
+}
+
+/// Astrometric observation of an observer table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObserverRecord {
+    /// Julian date, in the time scale of the table.
+    pub julian_date: f64,
+    /// Right ascension.
+    pub right_ascension: Angle,
+    /// Declination.
+    pub declination: Angle,
+    /// Distance from the observer, in meters, if the table has it.
+    pub distance: Option<f64>,
+    /// Rate of the distance, in meters per second, if the table has it.
+    pub range_rate: Option<f64>,
+}
+
+/// Table of astrometric observations, with increasing Julian dates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObserverTable {
+    time_scale: TimeScale,
+    records: Vec<ObserverRecord>,
+}
+
+impl ObserverTable {
+    /// Time scale of the Julian dates.
+    pub fn time_scale(&self) -> TimeScale {
+        self.time_scale
+    }
+
+    /// Records of the table.
+    pub fn records(&self) -> &[ObserverRecord] {
+        &self.records
+    }
+
+    /// Julian dates of the records.
+    pub fn julian_dates(&self) -> List<f64> {
+        List::from_iterator(
+            self.records.len(),
+            self.records.iter().map(|record| record.julian_date),
+        )
+    }
+
+    /// Right ascension and declination at an epoch, interpolated linearly between the
+    /// surrounding records, across the full turn of the right ascension.
+    pub fn interpolate(&self, epoch: &Epoch) -> Result<(Angle, Angle), EphemerisIoError> {
+        let julian_date = epoch.julian_date(self.time_scale);
+        let index = interval(&self.julian_dates(), julian_date)?;
+        let (first, second) = (&self.records[index], &self.records[index + 1]);
+        let fraction = (julian_date - first.julian_date) / (second.julian_date - first.julian_date);
+        let right_ascension = first.right_ascension
+            + second.right_ascension.difference(&first.right_ascension) * fraction;
+        let declination = first.declination + (second.declination - first.declination) * fraction;
+        Ok((right_ascension.normalized(), declination))
+    }
+}
+
+/// Observation of the 80-column format of the Minor Planet Center.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MpcObservation {
+    /// Packed number of the object, or its packed provisional designation if it has no number.
+    pub designation: String,
+    /// Whether the observation is a discovery.
+    pub discovery: bool,
+    /// Note of the observation, a space if none.
+    pub note: char,
+    /// Type of the observation, such as `C` for CCD, a space for photographic.
+    pub observation_type: char,
+    /// Julian date in UTC.
+    pub julian_date: f64,
+    /// Right ascension.
+    pub right_ascension: Angle,
+    /// Declination.
+    pub declination: Angle,
+    /// Observed magnitude, if any.
+    pub magnitude: Option<f64>,
+    /// Band of the magnitude, if any.
+    pub band: Option<char>,
+    /// Code of the observatory.
+    pub observatory: String,
+}
+
+/// Line, Julian date and values by name with their lines of a record of a vector table.
+type RawRecord = (usize, f64, Vec<(usize, String, String)>);
+
+/// Build a parse error.
+fn parse_error<S: Into<String>>(line: usize, message: S) -> EphemerisIoError {
+    EphemerisIoError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Parse a number of a field.
+fn parse_number(token: &str, name: &str, line: usize) -> Result<f64, EphemerisIoError> {
+    token
+        .trim()
+        .parse()
+        .map_err(|_| parse_error(line, format!("invalid {} `{}`", name, token.trim())))
+}
+
+/// Index of the interval of increasing Julian dates containing a Julian date.
+fn interval(julian_dates: &List<f64>, julian_date: f64) -> Result<usize, EphemerisIoError> {
+    let size = julian_dates.len();
+    if size < 2 || julian_date < julian_dates[0] || julian_date > julian_dates[size - 1] {
+        return Err(EphemerisIoError::OutOfRange {
+            julian_date,
+            first: julian_dates.iter().next().copied().unwrap_or(f64::NAN),
+            last: julian_dates.iter().next_back().copied().unwrap_or(f64::NAN),
+        });
+    }
+    let index = julian_dates
+        .iter()
+        .skip(1)
+        .position(|&other| julian_date <= other)
+        .unwrap();
+    Ok(index)
+}
+
+/// Lines of the data between `$$SOE` and `$$EOE` with their numbers, the header line of the
+/// columns and the lines before the data.
+#[allow(clippy::type_complexity)]
+fn horizons_sections(
+    content: &str,
+) -> Result<(Vec<&str>, Option<&str>, Vec<(usize, &str)>), EphemerisIoError> {
+    let lines: Vec<&str> = content.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "$$SOE")
+        .ok_or_else(|| EphemerisIoError::Format("missing $$SOE marker".to_string()))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "$$EOE")
+            .ok_or_else(|| EphemerisIoError::Format("missing $$EOE marker".to_string()))?;
+    let header = lines[..start]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .find(|line| !line.is_empty() && !line.chars().all(|c| c == '*'));
+    let data = (start + 1..end)
+        .map(|index| (index + 1, lines[index]))
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+    Ok((lines[..start].to_vec(), header, data))
+}
+
+/// Fields of a CSV line, without the empty field after the final comma.
+fn csv_fields(line: &str) -> Vec<&str> {
+    let line = line.trim();
+    line.strip_suffix(',')
+        .unwrap_or(line)
+        .split(',')
+        .map(str::trim)
+        .collect()
+}
+
+/// Index of the first column whose name satisfies a predicate.
+fn column(names: &[&str], predicate: impl Fn(&str) -> bool) -> Option<usize> {
+    names.iter().position(|&name| predicate(name))
+}
+
+/// Check that the Julian dates of the records increase.
+fn check_increasing(julian_dates: &[(usize, f64)]) -> Result<(), EphemerisIoError> {
+    for pair in julian_dates.windows(2) {
+        if pair[1].1 <= pair[0].1 {
+            return Err(parse_error(pair[1].0, "Julian dates must increase"));
+        }
+    }
+    Ok(())
+}
+
+/// Read a vector table of [JPL Horizons](https://ssd.jpl.nasa.gov/horizons/), in the default text
+/// format or in CSV format.
+///
+/// The units of the `Output units` line of the header (`KM-S`, `KM-D` or `AU-D`, by default
+/// `KM-S`) are converted to meters and meters per second. The velocities are read when the table
+/// has them, and the coordinates are kept in the frame of the table.
+pub fn read_horizons_vectors<P: AsRef<Path>>(path: P) -> Result<VectorTable, EphemerisIoError> {
+    parse_horizons_vectors(&fs::read_to_string(path)?)
+}
+
+/// Parse the content of a vector table of JPL Horizons. See [`read_horizons_vectors`].
+pub fn parse_horizons_vectors(content: &str) -> Result<VectorTable, EphemerisIoError> {
+    let (preamble, header, data) = horizons_sections(content)?;
+    let (length, time) = match preamble
+        .iter()
+        .find(|line| line.trim_start().starts_with("Output units"))
+        .and_then(|line| line.split(':').nth(1))
+        .map(|units| units.trim())
+    {
+        None | Some("KM-S") => (1e3, 1.0),
+        Some("KM-D") => (1e3, DAY),
+        Some("AU-D") => (ASTRONAUMICAL_UNIT, DAY),
+        Some(units) => {
+            return Err(EphemerisIoError::Format(format!(
+                "unsupported output units {}",
+                units
+            )))
+        }
+    };
+
+    let mut raw: Vec<RawRecord> = vec![];
+    let csv = data.first().is_some_and(|(_, line)| line.contains(','));
+    if csv {
+        let names = csv_fields(header.unwrap_or(""));
+        let date = column(&names, |name| name.starts_with("JD")).ok_or_else(|| {
+            EphemerisIoError::Format("missing column of Julian dates".to_string())
+        })?;
+        for &(line, text) in &data {
+            let fields = csv_fields(text);
+            if fields.len() != names.len() {
+                return Err(parse_error(
+                    line,
+                    format!("expected {} fields, found {}", names.len(), fields.len()),
+                ));
+            }
+            let julian_date = parse_number(fields[date], "Julian date", line)?;
+            let values = names
+                .iter()
+                .zip(fields)
+                .map(|(name, field)| (line, name.to_string(), field.to_string()))
+                .collect();
+            raw.push((line, julian_date, values));
+        }
+    } else {
+        for &(line, text) in &data {
+            if text.starts_with(|c: char| c.is_ascii_digit()) {
+                let token = text.split_whitespace().next().unwrap();
+                raw.push((line, parse_number(token, "Julian date", line)?, vec![]));
+                continue;
+            }
+            let record = raw
+                .last_mut()
+                .ok_or_else(|| parse_error(line, "values before the first Julian date"))?;
+            let spaced = text.replace('=', " = ");
+            let tokens: Vec<&str> = spaced.split_whitespace().collect();
+            if !tokens.len().is_multiple_of(3) || tokens.chunks(3).any(|chunk| chunk[1] != "=") {
+                return Err(parse_error(line, "expected `NAME = value` pairs"));
+            }
+            for chunk in tokens.chunks(3) {
+                record
+                    .2
+                    .push((line, chunk[0].to_string(), chunk[2].to_string()));
+            }
+        }
+    }
+
+    let mut records = vec![];
+    for (line, julian_date, values) in raw {
+        let value = |name: &str| -> Result<Option<f64>, EphemerisIoError> {
+            values
+                .iter()
+                .find(|(_, other, _)| other == name)
+                .map(|(line, _, value)| parse_number(value, name, *line))
+                .transpose()
+        };
+        let mut position = Vector::zeros();
+        for (component, name) in ["X", "Y", "Z"].iter().enumerate() {
+            position[component] = value(name)?
+                .ok_or_else(|| parse_error(line, format!("missing value {}", name)))?
+                * length;
+        }
+        let velocity = match (value("VX")?, value("VY")?, value("VZ")?) {
+            (Some(x), Some(y), Some(z)) => Some(Vector::new(x, y, z) * length / time),
+            (None, None, None) => None,
+            _ => return Err(parse_error(line, "incomplete velocity")),
+        };
+        records.push((
+            line,
+            VectorRecord {
+                julian_date,
+                position,
+                velocity,
+            },
+        ));
+    }
+    let dates: Vec<(usize, f64)> = records
+        .iter()
+        .map(|(line, record)| (*line, record.julian_date))
+        .collect();
+    check_increasing(&dates)?;
+    Ok(VectorTable {
+        records: records.into_iter().map(|(_, record)| record).collect(),
+    })
+}
+
+/// Read an observer table of [JPL Horizons](https://ssd.jpl.nasa.gov/horizons/) in CSV format.
+///
+/// The columns are found by their names in the header: the date (calendar date such as
+/// `2021-Jan-01 00:00` or Julian date), whose time scale is the one of the name (UT, TT or TDB),
+/// the right ascension `R.A.` and the declination `DEC` (sexagesimal or in degrees), and
+/// optionally the distance `delta` in au and its rate `deldot` in km/s.
+pub fn read_horizons_observer<P: AsRef<Path>>(path: P) -> Result<ObserverTable, EphemerisIoError> {
+    parse_horizons_observer(&fs::read_to_string(path)?)
+}
+
+/// Parse the content of an observer table of JPL Horizons. See [`read_horizons_observer`].
+pub fn parse_horizons_observer(content: &str) -> Result<ObserverTable, EphemerisIoError> {
+    let (_, header, data) = horizons_sections(content)?;
+    let names = csv_fields(header.unwrap_or(""));
+    let missing = |name: &str| EphemerisIoError::Format(format!("missing column {}", name));
+    let date = column(&names, |name| name.starts_with("Date")).ok_or_else(|| missing("Date"))?;
+    let right_ascension =
+        column(&names, |name| name.starts_with("R.A.")).ok_or_else(|| missing("R.A."))?;
+    let declination =
+        column(&names, |name| name.starts_with("DEC")).ok_or_else(|| missing("DEC"))?;
+    let distance = column(&names, |name| name == "delta");
+    let range_rate = column(&names, |name| name == "deldot");
+    let time_scale = if names[date].contains("TDB") {
+        TimeScale::TDB
+    } else if names[date].contains("TT") {
+        TimeScale::TT
+    } else {
+        TimeScale::UTC
+    };
+    let julian = names[date].contains("JD");
+
+    let mut records = vec![];
+    let mut dates = vec![];
+    for &(line, text) in &data {
+        let fields = csv_fields(text);
+        if fields.len() != names.len() {
+            return Err(parse_error(
+                line,
+                format!("expected {} fields, found {}", names.len(), fields.len()),
+            ));
+        }
+        let julian_date = if julian {
+            parse_number(fields[date], "Julian date", line)?
+        } else {
+            parse_calendar(fields[date], time_scale, line)?
+        };
+        let angle = |index: usize, hours: bool| {
+            let field = fields[index];
+            if field.contains(' ') {
+                if hours {
+                    Angle::from_hms(field)
+                } else {
+                    Angle::from_dms(field)
+                }
+                .map_err(|error| parse_error(line, error.to_string()))
+            } else {
+                parse_number(field, "angle", line).map(Angle::from_degrees)
+            }
+        };
+        let optional = |index: Option<usize>, name: &str| {
+            index
+                .map(|index| parse_number(fields[index], name, line))
+                .transpose()
+        };
+        records.push(ObserverRecord {
+            julian_date,
+            right_ascension: angle(right_ascension, true)?,
+            declination: angle(declination, false)?,
+            distance: optional(distance, "distance")?.map(|value| value * ASTRONAUMICAL_UNIT),
+            range_rate: optional(range_rate, "range rate")?.map(|value| value * 1e3),
+        });
+        dates.push((line, julian_date));
+    }
+    check_increasing(&dates)?;
+    Ok(ObserverTable {
+        time_scale,
+        records,
+    })
+}
+
+/// Julian date of a calendar date of Horizons, such as `2021-Jan-01 00:00:00.000`.
+fn parse_calendar(field: &str, scale: TimeScale, line: usize) -> Result<f64, EphemerisIoError> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let error = || parse_error(line, format!("invalid date `{}`", field));
+    let mut parts = field.split_whitespace();
+    let date: Vec<&str> = parts.next().ok_or_else(error)?.split('-').collect();
+    let time: Vec<&str> = parts.next().unwrap_or("00:00").split(':').collect();
+    if date.len() != 3 || time.len() < 2 || time.len() > 3 || parts.next().is_some() {
+        return Err(error());
+    }
+    let year = date[0].parse().map_err(|_| error())?;
+    let month = MONTHS
+        .iter()
+        .position(|&month| month == date[1])
+        .ok_or_else(error)? as u32
+        + 1;
+    let day = date[2].parse().map_err(|_| error())?;
+    let hour = time[0].parse().map_err(|_| error())?;
+    let minute = time[1].parse().map_err(|_| error())?;
+    let second = match time.get(2) {
+        Some(second) => second.parse().map_err(|_| error())?,
+        None => 0.0,
+    };
+    Epoch::from_calendar(year, month, day, hour, minute, second, scale)
+        .map(|epoch| epoch.julian_date(scale))
+        .map_err(|error| parse_error(line, error.to_string()))
+}
+
+/// Read observations in the 80-column format of the [Minor Planet
+/// Center](https://minorplanetcenter.net/iau/info/OpticalObs.html).
+///
+/// The second lines of the satellite, roving and radar observations, of types `s`, `r` and
+/// `v`, are skipped, as well as the empty lines.
+pub fn read_mpc_observations<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<MpcObservation>, EphemerisIoError> {
+    parse_mpc_observations(&fs::read_to_string(path)?)
+}
+
+/// Parse observations in the 80-column format of the Minor Planet Center. See
+/// [`read_mpc_observations`].
+pub fn parse_mpc_observations(content: &str) -> Result<Vec<MpcObservation>, EphemerisIoError> {
+    let mut observations = vec![];
+    for (index, text) in content.lines().enumerate() {
+        let line = index + 1;
+        let text = text.trim_end();
+        if text.is_empty() {
+            continue;
+        }
+        let columns: Vec<char> = text.chars().collect();
+        if columns.len() != 80 {
+            return Err(parse_error(
+                line,
+                format!("expected 80 columns, found {}", columns.len()),
+            ));
+        }
+        let field = |start: usize, end: usize| columns[start..end].iter().collect::<String>();
+        if matches!(columns[14], 's' | 'r' | 'v') {
+            continue;
+        }
+        let number = field(0, 5);
+        let designation = if number.trim().is_empty() {
+            field(5, 12)
+        } else {
+            number
+        };
+
+        let date = field(15, 32);
+        let date: Vec<&str> = date.split_whitespace().collect();
+        let error = || parse_error(line, format!("invalid date `{}`", field(15, 32)));
+        if date.len() != 3 {
+            return Err(error());
+        }
+        let year = date[0].parse().map_err(|_| error())?;
+        let month = date[1].parse().map_err(|_| error())?;
+        let day: f64 = date[2].parse().map_err(|_| error())?;
+        let julian_date =
+            Epoch::from_calendar(year, month, day.floor() as u32, 0, 0, 0.0, TimeScale::UTC)
+                .map_err(|error| parse_error(line, error.to_string()))?
+                .julian_date(TimeScale::UTC)
+                + day.fract();
+
+        let angle_error = |error: crate::AngleError| parse_error(line, error.to_string());
+        let magnitude = field(65, 70);
+        observations.push(MpcObservation {
+            designation: designation.trim().to_string(),
+            discovery: columns[12] == '*',
+            note: columns[13],
+            observation_type: columns[14],
+            julian_date,
+            right_ascension: Angle::from_hms(&field(32, 44)).map_err(angle_error)?,
+            declination: Angle::from_dms(&field(44, 56)).map_err(angle_error)?,
+            magnitude: if magnitude.trim().is_empty() {
+                None
+            } else {
+                Some(parse_number(&magnitude, "magnitude", line)?)
+            },
+            band: Some(columns[70]).filter(|band| *band != ' '),
+            observatory: field(77, 80),
+        });
+    }
+    Ok(observations)
+}
@@ -0,0 +1,500 @@
+use crate::{DAY, DEG2RAD, JULIAN_DATE_J2000, MODIFIED_JULIAN_DATE_OFFSET};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Difference TT - TAI in seconds.
+pub const TT_MINUS_TAI: f64 = 32.184;
+
+/// Dates at which the difference TAI - UTC changes, with the new difference in seconds. The
+/// table starts at the introduction of the leap seconds in 1972 and ends with the leap second of
+/// 2016-12-31.
+const LEAP_SECONDS: [(i64, u32, f64); 28] = [
+    (1972, 1, 10.0),
+    (1972, 7, 11.0),
+    (1973, 1, 12.0),
+    (1974, 1, 13.0),
+    (1975, 1, 14.0),
+    (1976, 1, 15.0),
+    (1977, 1, 16.0),
+    (1978, 1, 17.0),
+    (1979, 1, 18.0),
+    (1980, 1, 19.0),
+    (1981, 7, 20.0),
+    (1982, 7, 21.0),
+    (1983, 7, 22.0),
+    (1985, 7, 23.0),
+    (1988, 1, 24.0),
+    (1990, 1, 25.0),
+    (1991, 1, 26.0),
+    (1992, 7, 27.0),
+    (1993, 7, 28.0),
+    (1994, 7, 29.0),
+    (1996, 1, 30.0),
+    (1997, 7, 31.0),
+    (1999, 1, 32.0),
+    (2006, 1, 33.0),
+    (2009, 1, 34.0),
+    (2012, 7, 35.0),
+    (2015, 7, 36.0),
+    (2017, 1, 37.0),
+];
+
+/// Number of days from 1970-01-01 to 2000-01-01.
+const DAYS_1970_TO_2000: i64 = 10_957;
+
+/// Time scales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TimeScale {
+    /// Coordinated Universal Time, following the International Atomic Time with leap seconds.
+    UTC,
+    /// International Atomic Time.
+    TAI,
+    /// Terrestrial Time, TAI + 32.184 s.
+    TT,
+    /// Barycentric Dynamical Time.
+    TDB,
+}
+
+impl fmt::Display for TimeScale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+impl FromStr for TimeScale {
+    type Err = EpochError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "UTC" => Ok(TimeScale::UTC),
+            "TAI" => Ok(TimeScale::TAI),
+            "TT" => Ok(TimeScale::TT),
+            "TDB" => Ok(TimeScale::TDB),
+            _ => Err(EpochError::Format(format!("unknown time scale {}", string))),
+        }
+    }
+}
+
+/// Error raised while creating an epoch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpochError {
+    /// Malformed string.
+    Format(String),
+    /// Component of a calendar date out of its range.
+    OutOfRange(String),
+}
+
+impl fmt::Display for EpochError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EpochError::Format(message) => write!(f, "invalid epoch format: {}", message),
+            EpochError::OutOfRange(message) => write!(f, "epoch out of range: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for EpochError {}
+
+/// Instant in time, independent of the time scale used to express it.
+///
+/// The epoch is stored as the number of seconds of TT past J2000, 2000-01-01T12:00:00 TT, which
+/// gives a precision better than a microsecond over centuries around J2000. The conversions take
+/// a [`TimeScale`] to express the epoch in.
+///
+/// The durations added to or subtracted from an epoch, and the differences of epochs, are in SI
+/// seconds.
+///
+/// ## UTC
+///
+/// The UTC count of seconds past J2000 ignores the leap seconds, like the UTC Julian dates: it is
+/// the number of days past 2000-01-01T12:00:00 UTC times 86400 plus the seconds of the day. During
+/// a leap second, the count is held at the start of the next day, while the ISO 8601 format shows
+/// the second 60. Before 1972, UTC is approximated with the TAI - UTC difference of 1972.
+///
+/// ## TDB
+///
+/// The difference TDB - TT is approximated by the two periodic terms of the Explanatory Supplement
+/// to the Astronomical Almanac, accurate to about 30 microseconds,
+///
+/// $$TDB-TT=0.001657\sin g+0.000014\sin 2g$$
+///
+/// where $g=357.53°+0.98560028°\left(JD_{TT}-2451545\right)$ is the mean anomaly of the Earth.
+///
+/// ## Serialization
+///
+/// The epoch is serialized as the string of its [`Display`][fmt::Display], the ISO 8601 string
+/// in UTC followed by the time scale, and deserialized with its [`FromStr`], so that the JSON
+/// outputs are readable and do not depend on the internal representation. The string is rounded
+/// to the millisecond.
+///
+/// ```
+/// use tool::{Epoch, TimeScale};
+///
+/// let epoch: Epoch = "2000-01-01T11:58:55.816Z".parse().unwrap();
+/// assert_eq!(epoch.julian_date(TimeScale::TT), 2_451_545.0);
+/// assert_eq!(epoch.to_string(), "2000-01-01T11:58:55.816 UTC");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Epoch {
+    tt_seconds_past_j2000: f64,
+}
+
+impl Epoch {
+    /// Create an epoch from the number of seconds past J2000 in a time scale.
+    pub fn from_seconds_past_j2000(seconds: f64, scale: TimeScale) -> Self {
+        let tt_seconds_past_j2000 = match scale {
+            TimeScale::UTC => seconds + tai_minus_utc(seconds) + TT_MINUS_TAI,
+            TimeScale::TAI => seconds + TT_MINUS_TAI,
+            TimeScale::TT => seconds,
+            TimeScale::TDB => {
+                let tt = seconds - tdb_minus_tt(seconds);
+                seconds - tdb_minus_tt(tt)
+            }
+        };
+        Self {
+            tt_seconds_past_j2000,
+        }
+    }
+
+    /// Number of seconds past J2000 in a time scale.
+    pub fn seconds_past_j2000(&self, scale: TimeScale) -> f64 {
+        let tt = self.tt_seconds_past_j2000;
+        match scale {
+            TimeScale::UTC => {
+                let tai = tt - TT_MINUS_TAI;
+                match leap_second_interval(tai) {
+                    (_, Some(next_day)) => next_day,
+                    (offset, None) => tai - offset,
+                }
+            }
+            TimeScale::TAI => tt - TT_MINUS_TAI,
+            TimeScale::TT => tt,
+            TimeScale::TDB => tt + tdb_minus_tt(tt),
+        }
+    }
+
+    /// Create an epoch from a Julian date in a time scale.
+    pub fn from_julian_date(julian_date: f64, scale: TimeScale) -> Self {
+        Self::from_seconds_past_j2000((julian_date - JULIAN_DATE_J2000) * DAY, scale)
+    }
+
+    /// Julian date in a time scale.
+    pub fn julian_date(&self, scale: TimeScale) -> f64 {
+        self.seconds_past_j2000(scale) / DAY + JULIAN_DATE_J2000
+    }
+
+    /// Create an epoch from a modified Julian date in a time scale.
+    pub fn from_modified_julian_date(modified_julian_date: f64, scale: TimeScale) -> Self {
+        Self::from_julian_date(modified_julian_date + MODIFIED_JULIAN_DATE_OFFSET, scale)
+    }
+
+    /// Modified Julian date in a time scale.
+    pub fn modified_julian_date(&self, scale: TimeScale) -> f64 {
+        self.seconds_past_j2000(scale) / DAY + JULIAN_DATE_J2000 - MODIFIED_JULIAN_DATE_OFFSET
+    }
+
+    /// Create an epoch from a date of the proleptic Gregorian calendar in a time scale.
+    ///
+    /// The second can reach 61 in UTC at the end of a day followed by a leap second.
+    pub fn from_calendar(
+        year: i64,
+        month: u32,
+        day: u32,
+        hour: u32,
+        minute: u32,
+        second: f64,
+        scale: TimeScale,
+    ) -> Result<Self, EpochError> {
+        if !(1..=12).contains(&month) {
+            return Err(EpochError::OutOfRange(format!("month {}", month)));
+        }
+        if day < 1 || day > days_in_month(year, month) {
+            return Err(EpochError::OutOfRange(format!("day {}", day)));
+        }
+        if hour > 23 {
+            return Err(EpochError::OutOfRange(format!("hour {}", hour)));
+        }
+        if minute > 59 {
+            return Err(EpochError::OutOfRange(format!("minute {}", minute)));
+        }
+        let days = days_from_civil(year, month, day);
+        let day_start = ((days - DAYS_1970_TO_2000) as f64 - 0.5) * DAY;
+        let leap_day = scale == TimeScale::UTC
+            && hour == 23
+            && minute == 59
+            && leap_second_at(day_start + DAY).is_some();
+        let max_second = if leap_day { 61.0 } else { 60.0 };
+        if !(0.0..max_second).contains(&second) {
+            return Err(EpochError::OutOfRange(format!("second {}", second)));
+        }
+        let seconds = day_start + f64::from(hour * 3600 + minute * 60) + second;
+        if scale == TimeScale::UTC {
+            // The difference at the start of the day keeps the leap second in the same day.
+            Ok(Self {
+                tt_seconds_past_j2000: seconds + tai_minus_utc(day_start) + TT_MINUS_TAI,
+            })
+        } else {
+            Ok(Self::from_seconds_past_j2000(seconds, scale))
+        }
+    }
+
+    /// Create an epoch from an ISO 8601 string in a time scale.
+    ///
+    /// The accepted formats are `YYYY-MM-DD`, `YYYY-MM-DDThh:mm` and `YYYY-MM-DDThh:mm:ss` with
+    /// an optional fraction of second. The separator `T` can be replaced by a space, and the
+    /// designator `Z` is accepted for UTC.
+    pub fn from_iso8601(string: &str, scale: TimeScale) -> Result<Self, EpochError> {
+        let error = || EpochError::Format(string.to_string());
+        let mut string = string.trim();
+        if let Some(stripped) = string.strip_suffix('Z') {
+            if scale != TimeScale::UTC {
+                return Err(EpochError::Format(format!(
+                    "designator Z with the time scale {}",
+                    scale
+                )));
+            }
+            string = stripped;
+        }
+        let (date, time) = match string.find(['T', ' ']) {
+            Some(index) => (&string[..index], Some(&string[index + 1..])),
+            None => (string, None),
+        };
+        let date: Vec<&str> = date.split('-').collect();
+        if date.len() != 3 || date[1].len() != 2 || date[2].len() != 2 {
+            return Err(error());
+        }
+        let year = date[0].parse().map_err(|_| error())?;
+        let month = date[1].parse().map_err(|_| error())?;
+        let day = date[2].parse().map_err(|_| error())?;
+        let (hour, minute, second) = match time {
+            None => (0, 0, 0.0),
+            Some(time) => {
+                let time: Vec<&str> = time.split(':').collect();
+                if !(2..=3).contains(&time.len()) || time[0].len() != 2 || time[1].len() != 2 {
+                    return Err(error());
+                }
+                let second = match time.get(2) {
+                    Some(second) if second.len() >= 2 && !second.starts_with(['+', '-']) => {
+                        second.parse().map_err(|_| error())?
+                    }
+                    Some(_) => return Err(error()),
+                    None => 0.0,
+                };
+                (
+                    time[0].parse().map_err(|_| error())?,
+                    time[1].parse().map_err(|_| error())?,
+                    second,
+                )
+            }
+        };
+        Self::from_calendar(year, month, day, hour, minute, second, scale)
+    }
+
+    /// Format the epoch as an ISO 8601 string with milliseconds in a time scale, without
+    /// designator of the time scale.
+    pub fn to_iso8601(&self, scale: TimeScale) -> String {
+        if scale == TimeScale::UTC {
+            let tai = self.tt_seconds_past_j2000 - TT_MINUS_TAI;
+            if let (_, Some(next_day)) = leap_second_interval(tai) {
+                let leap_start = next_day + tai_minus_utc(next_day) - 1.0;
+                let milliseconds = ((tai - leap_start) * 1000.0).round() as i64;
+                if milliseconds < 1000 {
+                    let (year, month, day, ..) = calendar(next_day - DAY);
+                    return format!(
+                        "{:04}-{:02}-{:02}T23:59:60.{:03}",
+                        year, month, day, milliseconds
+                    );
+                }
+            }
+        }
+        let (year, month, day, hour, minute, milliseconds) =
+            calendar(self.seconds_past_j2000(scale));
+        format!(
+            "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}",
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            milliseconds / 1000,
+            milliseconds % 1000
+        )
+    }
+}
+
+/// Parse an ISO 8601 string, optionally followed by a space and the name of a time scale. The
+/// designator `Z` and the absence of time scale mean UTC.
+impl FromStr for Epoch {
+    type Err = EpochError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let string = string.trim();
+        match string.rsplit_once(' ') {
+            Some((date, scale)) if scale.chars().all(|c| c.is_ascii_uppercase()) => {
+                Self::from_iso8601(date, scale.parse()?)
+            }
+            _ => Self::from_iso8601(string, TimeScale::UTC),
+        }
+    }
+}
+
+/// Format the epoch in UTC with the name of the time scale.
+impl fmt::Display for Epoch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} UTC", self.to_iso8601(TimeScale::UTC))
+    }
+}
+
+impl Serialize for Epoch {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Epoch {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl Add<f64> for Epoch {
+    type Output = Self;
+
+    fn add(self, seconds: f64) -> Self {
+        Self {
+            tt_seconds_past_j2000: self.tt_seconds_past_j2000 + seconds,
+        }
+    }
+}
+
+impl Sub<f64> for Epoch {
+    type Output = Self;
+
+    fn sub(self, seconds: f64) -> Self {
+        Self {
+            tt_seconds_past_j2000: self.tt_seconds_past_j2000 - seconds,
+        }
+    }
+}
+
+impl AddAssign<f64> for Epoch {
+    fn add_assign(&mut self, seconds: f64) {
+        self.tt_seconds_past_j2000 += seconds;
+    }
+}
+
+impl SubAssign<f64> for Epoch {
+    fn sub_assign(&mut self, seconds: f64) {
+        self.tt_seconds_past_j2000 -= seconds;
+    }
+}
+
+/// Duration in seconds between two epochs.
+impl Sub for Epoch {
+    type Output = f64;
+
+    fn sub(self, other: Self) -> f64 {
+        self.tt_seconds_past_j2000 - other.tt_seconds_past_j2000
+    }
+}
+
+/// Difference TDB - TT in seconds from the seconds of TT past J2000.
+fn tdb_minus_tt(tt: f64) -> f64 {
+    let g = (357.53 + 0.985_600_28 * tt / DAY) * DEG2RAD;
+    0.001_657 * g.sin() + 0.000_014 * (2.0 * g).sin()
+}
+
+/// Seconds of UTC past J2000 at the start of the entry of the leap second table.
+fn leap_second_start(index: usize) -> f64 {
+    let (year, month, _) = LEAP_SECONDS[index];
+    ((days_from_civil(year, month, 1) - DAYS_1970_TO_2000) as f64 - 0.5) * DAY
+}
+
+/// Index of the entry of the leap second table starting at the seconds of UTC past J2000.
+fn leap_second_at(utc: f64) -> Option<usize> {
+    (1..LEAP_SECONDS.len()).find(|&index| leap_second_start(index) == utc)
+}
+
+/// Difference TAI - UTC in seconds from the seconds of UTC past J2000.
+fn tai_minus_utc(utc: f64) -> f64 {
+    (0..LEAP_SECONDS.len())
+        .rev()
+        .find(|&index| leap_second_start(index) <= utc)
+        .map_or(LEAP_SECONDS[0].2, |index| LEAP_SECONDS[index].2)
+}
+
+/// Difference TAI - UTC in seconds from the seconds of TAI past J2000, with the seconds of UTC of
+/// the start of the next day if the epoch is during a leap second.
+fn leap_second_interval(tai: f64) -> (f64, Option<f64>) {
+    for index in (0..LEAP_SECONDS.len()).rev() {
+        let start = leap_second_start(index);
+        let offset = LEAP_SECONDS[index].2;
+        if start + offset <= tai {
+            return (offset, None);
+        }
+        if index > 0 && start + offset - 1.0 <= tai {
+            return (LEAP_SECONDS[index - 1].2, Some(start));
+        }
+    }
+    (LEAP_SECONDS[0].2, None)
+}
+
+/// Calendar date from the seconds past J2000, with the time of the day rounded to the millisecond.
+fn calendar(seconds: f64) -> (i64, u32, u32, u32, u32, i64) {
+    let milliseconds = ((seconds + DAY / 2.0) * 1000.0).round() as i64;
+    let days = milliseconds.div_euclid(86_400_000);
+    let milliseconds = milliseconds.rem_euclid(86_400_000);
+    let (year, month, day) = civil_from_days(days + DAYS_1970_TO_2000);
+    let hour = (milliseconds / 3_600_000) as u32;
+    let minute = (milliseconds / 60_000 % 60) as u32;
+    (year, month, day, hour, minute, milliseconds % 60_000)
+}
+
+/// Whether a year of the proleptic Gregorian calendar is a leap year.
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+/// Number of days in a month.
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Number of days from 1970-01-01 to a date of the proleptic Gregorian calendar.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = i64::from(month);
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(day) - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Date of the proleptic Gregorian calendar from the number of days from 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
@@ -0,0 +1,369 @@
+use crate::{elementary_rotation, Rotation, Vector, Vectors, OBLIQUITY_J2000, TAU};
+use na::RealField;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// Trait to be added to a marker type to be used as reference frame.
+///
+/// The built-in frames are [`Equatorial`], [`Ecliptic`] and [`BodyFixed`]. You can define your
+/// own frame, for example for each body of a system, with a unit struct.
+pub trait Frame: Debug + Clone + Copy + PartialEq {
+    /// Name of the frame.
+    const NAME: &'static str;
+}
+
+/// Equatorial frame of the epoch J2000, aligned with the Earth mean equator and equinox.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Equatorial;
+
+impl Frame for Equatorial {
+    const NAME: &'static str = "J2000 equatorial";
+}
+
+/// Ecliptic frame of the epoch J2000, aligned with the mean ecliptic and equinox.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ecliptic;
+
+impl Frame for Ecliptic {
+    const NAME: &'static str = "J2000 ecliptic";
+}
+
+/// Frame fixed to a rotating body, with the z axis along the pole and the x axis along the prime
+/// meridian. Define a marker for each body instead when the frames of several bodies are used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BodyFixed;
+
+impl Frame for BodyFixed {
+    const NAME: &'static str = "body-fixed";
+}
+
+/// [`Vector`] expressed in a reference frame.
+///
+/// Vectors from different frames cannot be mixed, the following does not compile:
+///
+/// ```compile_fail
+/// use tool::{Ecliptic, Equatorial, FramedVector, Vector};
+///
+/// let equatorial = FramedVector::<f64, Equatorial>::new(Vector::x());
+/// let ecliptic = FramedVector::<f64, Ecliptic>::new(Vector::y());
+/// let sum = equatorial + ecliptic;
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FramedVector<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    vector: Vector<T>,
+    frame: PhantomData<F>,
+}
+
+impl<T, F> FramedVector<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    /// Tag a [`Vector`] with the frame.
+    pub fn new(vector: Vector<T>) -> Self {
+        Self {
+            vector,
+            frame: PhantomData,
+        }
+    }
+
+    /// Access the [`Vector`].
+    pub fn vector(&self) -> &Vector<T> {
+        &self.vector
+    }
+
+    /// Remove the frame tag.
+    pub fn into_vector(self) -> Vector<T> {
+        self.vector
+    }
+
+    /// Name of the frame.
+    pub fn frame_name(&self) -> &'static str {
+        F::NAME
+    }
+}
+
+impl<T, F> Add for FramedVector<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.vector + other.vector)
+    }
+}
+
+impl<T, F> Sub for FramedVector<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.vector - other.vector)
+    }
+}
+
+impl<T, F> Neg for FramedVector<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.vector)
+    }
+}
+
+impl<T, F> Mul<T> for FramedVector<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self {
+        Self::new(self.vector * scalar)
+    }
+}
+
+/// [`Vectors`] expressed in a reference frame. See [`FramedVector`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct FramedVectors<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    vectors: Vectors<T>,
+    frame: PhantomData<F>,
+}
+
+impl<T, F> FramedVectors<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    /// Tag [`Vectors`] with the frame.
+    pub fn new(vectors: Vectors<T>) -> Self {
+        Self {
+            vectors,
+            frame: PhantomData,
+        }
+    }
+
+    /// Access the [`Vectors`].
+    pub fn vectors(&self) -> &Vectors<T> {
+        &self.vectors
+    }
+
+    /// Remove the frame tag.
+    pub fn into_vectors(self) -> Vectors<T> {
+        self.vectors
+    }
+
+    /// Get the [`FramedVector`] at the index.
+    pub fn vector(&self, index: usize) -> FramedVector<T, F> {
+        FramedVector::new(self.vectors.column(index).into_owned())
+    }
+
+    /// Name of the frame.
+    pub fn frame_name(&self) -> &'static str {
+        F::NAME
+    }
+}
+
+impl<T, F> Add for FramedVectors<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.vectors + other.vectors)
+    }
+}
+
+impl<T, F> Sub for FramedVectors<T, F>
+where
+    T: RealField,
+    F: Frame,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.vectors - other.vectors)
+    }
+}
+
+/// Rotation from a reference frame to another one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameTransform<T, From, To>
+where
+    T: RealField,
+    From: Frame,
+    To: Frame,
+{
+    rotation: Rotation<T>,
+    frames: PhantomData<(From, To)>,
+}
+
+impl<T, From, To> FrameTransform<T, From, To>
+where
+    T: RealField,
+    From: Frame,
+    To: Frame,
+{
+    /// Transform from the [`Rotation`] that gives the coordinates in the new frame from the
+    /// coordinates in the old frame.
+    pub fn new(rotation: Rotation<T>) -> Self {
+        Self {
+            rotation,
+            frames: PhantomData,
+        }
+    }
+
+    /// Access the [`Rotation`].
+    pub fn rotation(&self) -> &Rotation<T> {
+        &self.rotation
+    }
+
+    /// Transform from the new frame back to the old frame.
+    pub fn inverse(&self) -> FrameTransform<T, To, From> {
+        FrameTransform::new(self.rotation.inverse())
+    }
+
+    /// Transform obtained by applying this transform, then the next one.
+    pub fn then<Next>(&self, next: &FrameTransform<T, To, Next>) -> FrameTransform<T, From, Next>
+    where
+        Next: Frame,
+    {
+        FrameTransform::new(next.rotation * self.rotation)
+    }
+
+    /// Express a [`FramedVector`] in the new frame.
+    pub fn apply(&self, vector: &FramedVector<T, From>) -> FramedVector<T, To> {
+        FramedVector::new(self.rotation * vector.vector)
+    }
+
+    /// Express [`FramedVectors`] in the new frame.
+    pub fn apply_all(&self, vectors: &FramedVectors<T, From>) -> FramedVectors<T, To> {
+        FramedVectors::new(self.rotation.matrix() * &vectors.vectors)
+    }
+}
+
+/// Transform from the J2000 [`Equatorial`] frame to the J2000 [`Ecliptic`] frame, using the
+/// [obliquity][OBLIQUITY_J2000] of the ecliptic.
+pub fn equatorial_to_ecliptic<T>() -> FrameTransform<T, Equatorial, Ecliptic>
+where
+    T: RealField,
+{
+    FrameTransform::new(elementary_rotation(
+        0,
+        -na::convert::<f64, T>(OBLIQUITY_J2000),
+    ))
+}
+
+/// Transform from the J2000 [`Ecliptic`] frame to the J2000 [`Equatorial`] frame.
+pub fn ecliptic_to_equatorial<T>() -> FrameTransform<T, Ecliptic, Equatorial>
+where
+    T: RealField,
+{
+    equatorial_to_ecliptic().inverse()
+}
+
+/// Rotation of a body, described by the orientation of its pole and its rotation period.
+///
+/// The pole is given by its right ascension and declination in the J2000 [`Equatorial`] frame.
+/// The prime meridian angle is measured along the equator of the body, from the ascending node of
+/// the equator of the body on the J2000 equator, at the reference epoch. The epoch and the period
+/// are expressed in seconds with the same time origin as the times given to
+/// [`RotationModel::transform`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RotationModel<T>
+where
+    T: RealField,
+{
+    /// Right ascension of the pole.
+    pub right_ascension: T,
+    /// Declination of the pole.
+    pub declination: T,
+    /// Angle of the prime meridian at the reference epoch.
+    pub prime_meridian: T,
+    /// Sidereal rotation period, negative for a retrograde rotation.
+    pub period: T,
+    /// Reference epoch.
+    pub epoch: T,
+}
+
+impl<T> RotationModel<T>
+where
+    T: RealField,
+{
+    /// Angle of the prime meridian at the time.
+    pub fn prime_meridian_at(&self, time: T) -> T {
+        self.prime_meridian + na::convert::<f64, T>(TAU) * (time - self.epoch) / self.period
+    }
+
+    /// Transform from the J2000 [`Equatorial`] frame to the frame fixed to the body at the time.
+    ///
+    /// The body-fixed frame is a type parameter, [`BodyFixed`] or a marker defined for the body,
+    /// so that the frames of two bodies cannot be mixed.
+    ///
+    /// ## Expression
+    ///
+    /// $$R=R_z\left(-W\right)R_x\left(\delta-\frac{\pi}{2}\right)R_z\left(-\alpha-\frac{\pi}{2}\right)$$
+    ///
+    /// where $\alpha$ and $\delta$ are the right ascension and declination of the pole, $W$ the
+    /// angle of the prime meridian at the time, and $R_i$ the
+    /// [elementary rotations][elementary_rotation].
+    ///
+    /// ## Example
+    ///
+    /// ```compile_fail
+    /// use tool::{Frame, FramedVector, RotationModel, Vector};
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// struct Didymos;
+    /// impl Frame for Didymos {
+    ///     const NAME: &'static str = "Didymos body-fixed";
+    /// }
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// struct Dimorphos;
+    /// impl Frame for Dimorphos {
+    ///     const NAME: &'static str = "Dimorphos body-fixed";
+    /// }
+    ///
+    /// let model = RotationModel {
+    ///     right_ascension: 0.0,
+    ///     declination: 1.0,
+    ///     prime_meridian: 0.0,
+    ///     period: 8e3,
+    ///     epoch: 0.0,
+    /// };
+    /// let didymos = model.transform::<Didymos>(0.0).apply(&FramedVector::new(Vector::x()));
+    /// let dimorphos = model.transform::<Dimorphos>(0.0).apply(&FramedVector::new(Vector::y()));
+    /// // The vectors of two body-fixed frames cannot be added.
+    /// let _ = didymos + dimorphos;
+    /// ```
+    pub fn transform<F>(&self, time: T) -> FrameTransform<T, Equatorial, F>
+    where
+        F: Frame,
+    {
+        FrameTransform::new(
+            elementary_rotation(2, -self.prime_meridian_at(time))
+                * elementary_rotation(0, self.declination - T::frac_pi_2())
+                * elementary_rotation(2, -self.right_ascension - T::frac_pi_2()),
+        )
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{List, Vector, Vectors, VectorsGeneric};
+use crate::{
+    newton_method_with_options, Angle, List, NewtonMethodArguments, NewtonMethodError,
+    NewtonMethodOptions, Vector, Vectors, VectorsGeneric,
+};
 use itertools::multizip;
 use na::{storage::Storage, Dynamic, RealField, U3};
 use num_traits::{cast, NumCast};
@@ -71,7 +74,7 @@ where
 /// $$\rho=\left\Vert\bm{q}\right\Vert$$
 ///
 /// where $\theta$ is the azimuth, $\phi$ is the elevation, $\rho$ the radius, and $\bm{q}$ the
-/// cartesian vector.
+/// cartesian vector. See [`cart_to_sph_angle_lists`] and [`cart_to_sph_angles`] for typed angles.
 pub fn cart_to_sph<T, S>(vectors: &VectorsGeneric<T, S>) -> Vectors<T>
 where
     T: RealField + NumCast,
@@ -85,19 +88,429 @@ where
     for (mut spherical, cartesian) in
         multizip((sphericals.column_iter_mut(), vectors.column_iter()))
     {
-        if relative_eq!(cast::<T, f64>(cartesian.norm()).unwrap(), 0.0) {
-            spherical.copy_from(&Vector::<T>::zeros());
-        } else {
-            spherical.copy_from_slice(&[
-                cartesian[1].atan2(cartesian[0]),
-                (cartesian[2] / cartesian.norm()).asin(),
-                cartesian.norm(),
-            ]);
-        }
+        spherical.copy_from(&cart_to_sph_vector(&cartesian.into_owned()));
     }
     sphericals
 }
 
+/// Convert a [`Vector`] from cartesian to spherical coordinates. See [`cart_to_sph`] for the
+/// convention.
+pub fn cart_to_sph_vector<T>(vector: &Vector<T>) -> Vector<T>
+where
+    T: RealField + NumCast,
+{
+    if relative_eq!(cast::<T, f64>(vector.norm()).unwrap(), 0.0) {
+        Vector::<T>::zeros()
+    } else {
+        Vector::new(
+            vector[1].atan2(vector[0]),
+            (vector[2] / vector.norm()).asin(),
+            vector.norm(),
+        )
+    }
+}
+
+/// Convert a list of [`Vector`]s from spherical to cartesian coordinates. Inverse of
+/// [`cart_to_sph`].
+///
+/// ## Expression
+///
+/// $$q_x=\rho\cos\phi\cos\theta$$
+/// $$q_y=\rho\cos\phi\sin\theta$$
+/// $$q_z=\rho\sin\phi$$
+///
+/// where $\theta$ is the azimuth, $\phi$ is the elevation (not the colatitude), $\rho$ the radius,
+/// and $\bm{q}$ the cartesian vector.
+pub fn sph_to_cart<T, S>(vectors: &VectorsGeneric<T, S>) -> Vectors<T>
+where
+    T: RealField,
+    S: Storage<T, U3, Dynamic>,
+{
+    // Allocation.
+    let size = vectors.ncols();
+    let mut cartesians = Vectors::zeros(size);
+
+    // Computation.
+    for (mut cartesian, spherical) in
+        multizip((cartesians.column_iter_mut(), vectors.column_iter()))
+    {
+        cartesian.copy_from(&sph_to_cart_vector(&spherical.into_owned()));
+    }
+    cartesians
+}
+
+/// Convert a [`Vector`] from spherical to cartesian coordinates. See [`sph_to_cart`] for the
+/// convention.
+pub fn sph_to_cart_vector<T>(vector: &Vector<T>) -> Vector<T>
+where
+    T: RealField,
+{
+    let (sin_azimuth, cos_azimuth) = vector[0].sin_cos();
+    let (sin_elevation, cos_elevation) = vector[1].sin_cos();
+    Vector::new(
+        vector[2] * cos_elevation * cos_azimuth,
+        vector[2] * cos_elevation * sin_azimuth,
+        vector[2] * sin_elevation,
+    )
+}
+
+/// Convert a [`Vector`] from cartesian to spherical coordinates, with typed angles. See
+/// [`cart_to_sph`] for the convention.
+pub fn cart_to_sph_angles<T>(vector: &Vector<T>) -> (Angle<T>, Angle<T>, T)
+where
+    T: RealField + NumCast,
+{
+    let spherical = cart_to_sph_vector(vector);
+    (
+        Angle::new(spherical[0]),
+        Angle::new(spherical[1]),
+        spherical[2],
+    )
+}
+
+/// Convert spherical coordinates with typed angles to a cartesian [`Vector`]. Inverse of
+/// [`cart_to_sph_angles`].
+pub fn sph_angles_to_cart<T>(azimuth: Angle<T>, elevation: Angle<T>, radius: T) -> Vector<T>
+where
+    T: RealField,
+{
+    sph_to_cart_vector(&Vector::new(
+        azimuth.into_value(),
+        elevation.into_value(),
+        radius,
+    ))
+}
+
+/// Convert a list of [`Vector`]s from cartesian to spherical coordinates, with typed angles. See
+/// [`cart_to_sph`] for the convention.
+pub fn cart_to_sph_angle_lists<T, S>(
+    vectors: &VectorsGeneric<T, S>,
+) -> (Angle<List<T>>, Angle<List<T>>, List<T>)
+where
+    T: RealField + NumCast,
+    S: Storage<T, U3, Dynamic>,
+{
+    let sphericals = cart_to_sph(vectors);
+    (
+        Angle::new(sphericals.row(0).into_owned()),
+        Angle::new(sphericals.row(1).into_owned()),
+        sphericals.row(2).into_owned(),
+    )
+}
+
+/// Convert lists of spherical coordinates with typed angles to cartesian [`Vector`]s. Inverse of
+/// [`cart_to_sph_angle_lists`].
+///
+/// ## Panics
+///
+/// If the azimuths, elevations and radii do not have the same length.
+pub fn sph_angle_lists_to_cart<T>(
+    azimuths: &Angle<List<T>>,
+    elevations: &Angle<List<T>>,
+    radii: &List<T>,
+) -> Vectors<T>
+where
+    T: RealField,
+{
+    sph_to_cart(&Vectors::from_rows(&[
+        azimuths.value().clone(),
+        elevations.value().clone(),
+        radii.clone(),
+    ]))
+}
+
+/// Convert a list of [`Vector`]s from cartesian to cylindrical coordinates.
+///
+/// The column layout follows [`cart_to_sph`], with the angle first and the radial distance last.
+///
+/// ## Expression
+///
+/// $$\varphi={\rm arctan2}\left(q_y, q_x\right)$$
+/// $$z=q_z$$
+/// $$\rho=\sqrt{q_x^2+q_y^2}$$
+///
+/// where $\varphi$ is the azimuth, $z$ the height, $\rho$ the distance to the $z$ axis, and
+/// $\bm{q}$ the cartesian vector.
+pub fn cart_to_cyl<T, S>(vectors: &VectorsGeneric<T, S>) -> Vectors<T>
+where
+    T: RealField,
+    S: Storage<T, U3, Dynamic>,
+{
+    // Allocation.
+    let size = vectors.ncols();
+    let mut cylindricals = Vectors::zeros(size);
+
+    // Computation.
+    for (mut cylindrical, cartesian) in
+        multizip((cylindricals.column_iter_mut(), vectors.column_iter()))
+    {
+        cylindrical.copy_from(&cart_to_cyl_vector(&cartesian.into_owned()));
+    }
+    cylindricals
+}
+
+/// Convert a [`Vector`] from cartesian to cylindrical coordinates. See [`cart_to_cyl`] for the
+/// convention.
+pub fn cart_to_cyl_vector<T>(vector: &Vector<T>) -> Vector<T>
+where
+    T: RealField,
+{
+    Vector::new(
+        vector[1].atan2(vector[0]),
+        vector[2],
+        vector[0].hypot(vector[1]),
+    )
+}
+
+/// Convert a list of [`Vector`]s from cylindrical to cartesian coordinates. Inverse of
+/// [`cart_to_cyl`].
+///
+/// ## Expression
+///
+/// $$q_x=\rho\cos\varphi$$
+/// $$q_y=\rho\sin\varphi$$
+/// $$q_z=z$$
+pub fn cyl_to_cart<T, S>(vectors: &VectorsGeneric<T, S>) -> Vectors<T>
+where
+    T: RealField,
+    S: Storage<T, U3, Dynamic>,
+{
+    // Allocation.
+    let size = vectors.ncols();
+    let mut cartesians = Vectors::zeros(size);
+
+    // Computation.
+    for (mut cartesian, cylindrical) in
+        multizip((cartesians.column_iter_mut(), vectors.column_iter()))
+    {
+        cartesian.copy_from(&cyl_to_cart_vector(&cylindrical.into_owned()));
+    }
+    cartesians
+}
+
+/// Convert a [`Vector`] from cylindrical to cartesian coordinates. See [`cyl_to_cart`] for the
+/// convention.
+pub fn cyl_to_cart_vector<T>(vector: &Vector<T>) -> Vector<T>
+where
+    T: RealField,
+{
+    let (sin_azimuth, cos_azimuth) = vector[0].sin_cos();
+    Vector::new(vector[2] * cos_azimuth, vector[2] * sin_azimuth, vector[1])
+}
+
+/// Convert a list of [`Vector`]s from geodetic to cartesian coordinates on an ellipsoid.
+///
+/// The ellipsoid is described by its semi-axes `radii` along $x$, $y$ and $z$. An oblate spheroid
+/// has equal first two radii, a triaxial ellipsoid has three different radii. The column layout
+/// follows [`cart_to_sph`]: longitude, latitude and altitude.
+///
+/// ## Expression
+///
+/// The geodetic latitude $\phi$ and longitude $\lambda$ define the normal of the surface,
+///
+/// $$\bm{n}=\left(\cos\phi\cos\lambda, \cos\phi\sin\lambda, \sin\phi\right)$$
+///
+/// and the cartesian vector is the point of the surface with this normal, moved along the normal
+/// by the altitude $h$,
+///
+/// $$q_i=\frac{a_i^2n_i}{\sqrt{\sum_ja_j^2n_j^2}}+hn_i$$
+///
+/// where $a_i$ are the radii of the ellipsoid.
+pub fn geod_to_cart<T, S>(vectors: &VectorsGeneric<T, S>, radii: &Vector<T>) -> Vectors<T>
+where
+    T: RealField,
+    S: Storage<T, U3, Dynamic>,
+{
+    // Allocation.
+    let size = vectors.ncols();
+    let mut cartesians = Vectors::zeros(size);
+
+    // Computation.
+    for (mut cartesian, geodetic) in multizip((cartesians.column_iter_mut(), vectors.column_iter()))
+    {
+        cartesian.copy_from(&geod_to_cart_vector(&geodetic.into_owned(), radii));
+    }
+    cartesians
+}
+
+/// Convert a [`Vector`] from geodetic to cartesian coordinates on an ellipsoid. See
+/// [`geod_to_cart`] for the convention.
+pub fn geod_to_cart_vector<T>(vector: &Vector<T>, radii: &Vector<T>) -> Vector<T>
+where
+    T: RealField,
+{
+    let (sin_longitude, cos_longitude) = vector[0].sin_cos();
+    let (sin_latitude, cos_latitude) = vector[1].sin_cos();
+    let normal = Vector::new(
+        cos_latitude * cos_longitude,
+        cos_latitude * sin_longitude,
+        sin_latitude,
+    );
+    let squares = radii.component_mul(radii);
+    let surface = squares.component_mul(&normal) / radii.component_mul(&normal).norm();
+    surface + normal * vector[2]
+}
+
+/// Arguments of the Newton's method used to find the closest point of the ellipsoid.
+struct GeodeticArguments<T>
+where
+    T: RealField,
+{
+    /// Cartesian vectors scaled component-wise by the radii.
+    scaled: Vectors<T>,
+    /// Squares of the radii.
+    squares: Vector<T>,
+}
+
+impl<T> NewtonMethodArguments for GeodeticArguments<T>
+where
+    T: RealField,
+{
+    fn subset(&self, components: &[usize]) -> Option<Self> {
+        Some(Self {
+            scaled: self.scaled.select_columns(components),
+            squares: self.squares,
+        })
+    }
+}
+
+/// Function of the Newton's method for the closest point of the ellipsoid:
+///
+/// $$f(t)=\sum_i\left(\frac{a_iq_i}{a_i^2+t}\right)^2-1$$
+fn geodetic_function<T>(values: &List<T>, args: &GeodeticArguments<T>) -> List<T>
+where
+    T: RealField,
+{
+    List::from_iterator(
+        values.len(),
+        multizip((values.iter(), args.scaled.column_iter())).map(|(t, scaled)| {
+            let mut res = -T::one();
+            for (component, square) in multizip((scaled.iter(), args.squares.iter())) {
+                res += (*component / (*square + *t)).powi(2);
+            }
+            res
+        }),
+    )
+}
+
+/// Derivative of the Newton's method for the closest point of the ellipsoid.
+fn geodetic_derivative<T>(values: &List<T>, args: &GeodeticArguments<T>) -> List<T>
+where
+    T: RealField,
+{
+    List::from_iterator(
+        values.len(),
+        multizip((values.iter(), args.scaled.column_iter())).map(|(t, scaled)| {
+            let mut res = T::zero();
+            for (component, square) in multizip((scaled.iter(), args.squares.iter())) {
+                res -= (*component).powi(2) * (T::one() + T::one()) / (*square + *t).powi(3);
+            }
+            res
+        }),
+    )
+}
+
+/// Convert a list of [`Vector`]s from cartesian to geodetic coordinates on an ellipsoid. Inverse
+/// of [`geod_to_cart`].
+///
+/// ## Expression
+///
+/// The closest point of the ellipsoid to the cartesian vector $\bm{q}$ is
+/// $p_i=\frac{a_i^2q_i}{a_i^2+t}$, where $t$ is the largest root of,
+///
+/// $$f(t)=\sum_i\left(\frac{a_iq_i}{a_i^2+t}\right)^2-1$$
+///
+/// The root is found for all the vectors at once with the
+/// [Newton's method][newton_method_with_options], starting from the left of the root where $f$ is
+/// convex and decreasing. The normal of the surface at this point gives the latitude and the
+/// longitude, and the altitude is $h=t\left\Vert\bm{g}\right\Vert$ with $g_i=\frac{q_i}{a_i^2+t}$.
+///
+/// The origin, for which the closest point is not unique, is given the north pole of the
+/// ellipsoid. Points inside the ellipsoid are supported as long as they are not close to its
+/// center, where the closest point of the surface becomes ambiguous. Vectors that are not finite
+/// make the Newton's method fail with an error.
+pub fn cart_to_geod<T, S>(
+    vectors: &VectorsGeneric<T, S>,
+    radii: &Vector<T>,
+) -> Result<Vectors<T>, NewtonMethodError<T>>
+where
+    T: RealField + NumCast,
+    S: Storage<T, U3, Dynamic>,
+{
+    // Allocation.
+    let size = vectors.ncols();
+    let mut geodetics = Vectors::zeros(size);
+
+    // The problem is scaled by the largest radius to be solved with an absolute threshold. As the
+    // components are frozen once converged, the threshold is the square root of the precision so
+    // that the quadratic convergence reaches the precision.
+    let scale = radii.max();
+    let scaled_radii = radii / scale;
+    let squares = scaled_radii.component_mul(&scaled_radii);
+
+    // The origin has no unique closest point and is removed from the resolution.
+    let indices = (0..size)
+        .filter(|&index| !relative_eq!(cast::<T, f64>(vectors.column(index).norm()).unwrap(), 0.0))
+        .collect::<Vec<_>>();
+    let mut scaled = Vectors::zeros(indices.len());
+    let mut start_values = List::zeros(indices.len());
+    for (mut scaled_vector, start_value, &index) in multizip((
+        scaled.column_iter_mut(),
+        start_values.iter_mut(),
+        indices.iter(),
+    )) {
+        scaled_vector.copy_from(&(vectors.column(index) / scale).component_mul(&scaled_radii));
+        // The start value is on the left of the root, where one term of the function is 1.
+        *start_value = T::min_value();
+        for (component, radius) in multizip((scaled_vector.iter(), scaled_radii.iter())) {
+            if *component != T::zero() {
+                *start_value = start_value.max(component.abs() - radius.powi(2));
+            }
+        }
+    }
+
+    // Computation.
+    let options = NewtonMethodOptions {
+        absolute_tolerance: T::default_epsilon().sqrt(),
+        ..NewtonMethodOptions::default()
+    };
+    let roots = newton_method_with_options(
+        start_values,
+        geodetic_function,
+        geodetic_derivative,
+        GeodeticArguments { scaled, squares },
+        &options,
+    )?
+    .values;
+
+    for mut geodetic in geodetics.column_iter_mut() {
+        geodetic.copy_from_slice(&[T::zero(), T::frac_pi_2(), -radii[2]]);
+    }
+    for (root, &index) in multizip((roots.iter(), indices.iter())) {
+        let cartesian = vectors.column(index) / scale;
+        let gradient = cartesian.component_div(&squares.add_scalar(*root));
+        geodetics.column_mut(index).copy_from_slice(&[
+            gradient[1].atan2(gradient[0]),
+            gradient[2].atan2(gradient[0].hypot(gradient[1])),
+            *root * gradient.norm() * scale,
+        ]);
+    }
+    Ok(geodetics)
+}
+
+/// Convert a [`Vector`] from cartesian to geodetic coordinates on an ellipsoid. See
+/// [`cart_to_geod`] for the convention.
+pub fn cart_to_geod_vector<T>(
+    vector: &Vector<T>,
+    radii: &Vector<T>,
+) -> Result<Vector<T>, NewtonMethodError<T>>
+where
+    T: RealField + NumCast,
+{
+    cart_to_geod(&Vectors::from_columns(&[*vector]), radii)
+        .map(|geodetics| geodetics.column(0).into_owned())
+}
+
 /// Dot product component-wise between two lists of [`Vector`]s.
 pub fn dot_products<T>(vectors_1: &Vectors<T>, vectors_2: &Vectors<T>) -> List<T>
 where
@@ -155,6 +568,7 @@ where
 /// The direct angle is defined as the angle between the two vectors, from 0 to
 /// [$\tau$][crate::TAU]. It is opposed to the smallest angle between two vectors, from 0 to $\pi$.
 /// The direct angle is built using the normal vector from the plane defined by the two vectors.
+/// See [`Angle::direct`][crate::Angle::direct] for a typed angle.
 pub fn direct_angle<T>(v1: &Vector<T>, v2: &Vector<T>, up: &Vector<T>) -> T
 where
     T: RealField,
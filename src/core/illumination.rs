@@ -0,0 +1,94 @@
+use crate::{
+    clip, direct_angle, directions, dot_products, projection_plane, List, Vector, Vectors,
+};
+use itertools::multizip;
+use na::RealField;
+
+/// Illumination and viewing geometry of a list of facets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Illumination<T>
+where
+    T: RealField,
+{
+    /// Cosines of the incidence angles, between the normals and the directions of the sun. Facets
+    /// on the night side have a null cosine.
+    pub cos_incidence: List<T>,
+    /// Cosines of the emission angles, between the normals and the directions of the observer.
+    /// Facets not visible by the observer have a null cosine.
+    pub cos_emission: List<T>,
+    /// Phase angles, between the directions of the sun and the observer, from 0 to $\pi$.
+    pub phase: List<T>,
+    /// Azimuth angles, from the plane of incidence to the plane of emission around the normals,
+    /// from 0 to [$\tau$][crate::TAU]. The azimuth is null when the sun or the observer is along
+    /// the normal.
+    pub azimuth: List<T>,
+}
+
+/// Compute the illumination geometry of facets, from their centroids and normals, and the
+/// positions of the sun and of the observer.
+///
+/// ## Expression
+///
+/// $$\cos i=\max\left(\bm{n}\cdot\bm{s},0\right)$$
+/// $$\cos e=\max\left(\bm{n}\cdot\bm{o},0\right)$$
+/// $$\alpha=\arccos\left(\bm{s}\cdot\bm{o}\right)$$
+///
+/// where $\bm{n}$ is the normal of the facet, $\bm{s}$ and $\bm{o}$ the [directions] from the
+/// facet to the sun and to the observer. The night side and the hidden side are clamped the same
+/// way, with [`clip`], so that the cosines can be used directly as weights in photometric and
+/// thermal models. The azimuth is the [direct angle][direct_angle] between the projections of
+/// $\bm{s}$ and $\bm{o}$ onto the plane of the facet.
+pub fn illumination<T>(
+    centroids: &Vectors<T>,
+    normals: &Vectors<T>,
+    sun: &Vector<T>,
+    observer: &Vector<T>,
+) -> Illumination<T>
+where
+    T: RealField,
+{
+    let size = crate::number_vectors(centroids);
+    let sun_directions = directions(centroids, &Vectors::from_fn(size, |row, _| sun[row]));
+    let observer_directions =
+        directions(centroids, &Vectors::from_fn(size, |row, _| observer[row]));
+
+    let cos_incidence = clip(
+        &dot_products(normals, &sun_directions),
+        Some(T::zero()),
+        Some(T::one()),
+    );
+    let cos_emission = clip(
+        &dot_products(normals, &observer_directions),
+        Some(T::zero()),
+        Some(T::one()),
+    );
+    let phase = clip(
+        &dot_products(&sun_directions, &observer_directions),
+        Some(-T::one()),
+        Some(T::one()),
+    )
+    .map(|cosine| cosine.acos());
+
+    let mut azimuth = List::zeros(size);
+    for (res, normal, sun_direction, observer_direction) in multizip((
+        azimuth.iter_mut(),
+        normals.column_iter(),
+        sun_directions.column_iter(),
+        observer_directions.column_iter(),
+    )) {
+        let normal = normal.into_owned();
+        let sun_projection = projection_plane(&sun_direction.into_owned(), &normal);
+        let observer_projection = projection_plane(&observer_direction.into_owned(), &normal);
+        let threshold = T::default_epsilon().sqrt();
+        if sun_projection.norm() > threshold && observer_projection.norm() > threshold {
+            *res = direct_angle(&sun_projection, &observer_projection, &normal);
+        }
+    }
+
+    Illumination {
+        cos_incidence,
+        cos_emission,
+        phase,
+        azimuth,
+    }
+}
@@ -23,11 +23,12 @@ where
 macro_rules! addjs {
     ($map:expr, $(($key:expr, $value:expr)), *) => {{
         use serde_json::{from_str, to_string_pretty};
+        let map = $map;
         $(
             let json_value = from_str(&to_string_pretty(&$value).unwrap()).unwrap();
-            $map[$key] = json_value;
+            map[$key] = json_value;
         )*
-        $map
+        map
     }}
 }
 
@@ -0,0 +1,207 @@
+use crate::{is_parabolic, List, TAU};
+use std::f64::consts::PI;
+use std::fmt;
+
+/// Maximum number of iterations of the Kepler's equation solver.
+pub const KEPLER_MAX_ITERATIONS: usize = 100;
+/// Relative tolerance on the anomaly of the Kepler's equation solver.
+pub const KEPLER_TOLERANCE: f64 = 1e-15;
+
+/// Error raised by the Kepler's equation solver.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeplerError {
+    /// The eccentricity is negative or not finite.
+    InvalidEccentricity(f64),
+    /// The mean anomaly at the index of the list is not finite.
+    InvalidMeanAnomaly {
+        /// Index of the mean anomaly in the list.
+        index: usize,
+        /// Value of the mean anomaly.
+        mean_anomaly: f64,
+    },
+    /// The solver did not converge for the mean anomaly at the index of the list.
+    NotConverged {
+        /// Index of the mean anomaly in the list.
+        index: usize,
+        /// Value of the mean anomaly.
+        mean_anomaly: f64,
+        /// Last value of the anomaly.
+        last_value: f64,
+    },
+}
+
+impl fmt::Display for KeplerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeplerError::InvalidEccentricity(eccentricity) => {
+                write!(f, "invalid eccentricity {}", eccentricity)
+            }
+            KeplerError::InvalidMeanAnomaly {
+                index,
+                mean_anomaly,
+            } => write!(
+                f,
+                "invalid mean anomaly {} at index {}",
+                mean_anomaly, index
+            ),
+            KeplerError::NotConverged {
+                index,
+                mean_anomaly,
+                last_value,
+            } => write!(
+                f,
+                "Kepler's equation did not converge for the mean anomaly {} at index {}, last \
+                 value {}",
+                mean_anomaly, index, last_value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for KeplerError {}
+
+/// Safeguarded Newton's method for an increasing function whose root is in the bracket. The
+/// iterate is bisected when the Newton step leaves the bracket.
+pub(crate) fn safeguarded_newton(
+    function: impl Fn(f64) -> (f64, f64),
+    start: f64,
+    mut lower: f64,
+    mut upper: f64,
+) -> Result<f64, f64> {
+    let mut value = start.max(lower).min(upper);
+    for _ in 0..KEPLER_MAX_ITERATIONS {
+        let (residual, derivative) = function(value);
+        if residual == 0.0 {
+            return Ok(value);
+        } else if residual < 0.0 {
+            lower = value;
+        } else {
+            upper = value;
+        }
+        let mut next = value - residual / derivative;
+        if !(next > lower && next < upper) {
+            next = (lower + upper) / 2.0;
+        }
+        let step = (next - value).abs();
+        value = next;
+        if step <= KEPLER_TOLERANCE * value.abs().max(1.0) || upper - lower <= KEPLER_TOLERANCE {
+            return Ok(value);
+        }
+    }
+    Err(value)
+}
+
+/// Solve Barker's equation for parabolic orbits, $M=D+\frac{D^3}{3}$, where $D=\tan\frac{\nu}{2}$
+/// is the parabolic anomaly.
+///
+/// ## Expression
+///
+/// The cubic equation has the closed-form solution,
+///
+/// $$D=Y-\frac{1}{Y}$$
+///
+/// with $Y=\left(\frac{3M}{2}+\sqrt{\frac{9M^2}{4}+1}\right)^{1/3}$.
+pub fn solve_barker(mean_anomaly: f64) -> f64 {
+    let w = 1.5 * mean_anomaly;
+    // The expression is written to avoid the cancellation for negative mean anomalies.
+    let y = (w.abs() + (w * w + 1.0).sqrt()).cbrt();
+    (y - 1.0 / y) * mean_anomaly.signum()
+}
+
+/// Solve Kepler's equation for one mean anomaly.
+///
+/// The result is the eccentric anomaly $E$ for elliptic orbits ($e<1$), the parabolic anomaly
+/// $D$ for parabolic orbits ($e=1$, see [`is_parabolic`]), and the hyperbolic anomaly $H$ for
+/// hyperbolic orbits ($e>1$),
+///
+/// $$M=E-e\sin E$$
+/// $$M=D+\frac{D^3}{3}$$
+/// $$M=e\sinh H-H$$
+///
+/// ## Method
+///
+/// The parabolic case is solved with [Barker's equation][solve_barker]. The other cases use a
+/// Newton's method safeguarded by bisection, as both functions are increasing: the root is
+/// bracketed and a step leaving the bracket is replaced by a bisection, so that the solver
+/// converges for any eccentricity, including close to 1. The starting guesses are,
+///
+/// + elliptic: $E_0=M+0.85e\,{\rm sign}\left(M\right)$, or $E_0=\left(6M\right)^{1/3}$ for
+///   $e>0.8$ and small $M$ where the cubic term of the series dominates,
+/// + hyperbolic: $H_0={\rm sign}\left(M\right)\ln\left(\frac{2\left|M\right|}{e}+1.8\right)$, or
+///   $H_0=\left(\frac{6M}{e}\right)^{1/3}$ close to the periapsis.
+///
+/// The elliptic mean anomaly is reduced to $\left[-\pi,\pi\right]$ and the revolutions are added
+/// back to the result.
+pub fn solve_kepler(mean_anomaly: f64, eccentricity: f64) -> Result<f64, KeplerError> {
+    let result = solve_kepler_list(&List::from_element(1, mean_anomaly), eccentricity)?;
+    Ok(result[0])
+}
+
+/// Solve Kepler's equation for a [`List`] of mean anomalies. See [`solve_kepler`].
+pub fn solve_kepler_list(
+    mean_anomalies: &List<f64>,
+    eccentricity: f64,
+) -> Result<List<f64>, KeplerError> {
+    if !eccentricity.is_finite() || eccentricity < 0.0 {
+        return Err(KeplerError::InvalidEccentricity(eccentricity));
+    }
+    let mut anomalies = List::zeros(mean_anomalies.len());
+    for (index, (anomaly, &mean_anomaly)) in
+        anomalies.iter_mut().zip(mean_anomalies.iter()).enumerate()
+    {
+        if !mean_anomaly.is_finite() {
+            return Err(KeplerError::InvalidMeanAnomaly {
+                index,
+                mean_anomaly,
+            });
+        }
+        let result = if is_parabolic(eccentricity) {
+            Ok(solve_barker(mean_anomaly))
+        } else if eccentricity < 1.0 {
+            let revolutions = (mean_anomaly / TAU).round() * TAU;
+            let mean = mean_anomaly - revolutions;
+            let start = if eccentricity > 0.8 && mean.abs() < 0.5 {
+                (6.0 * mean).cbrt()
+            } else {
+                mean + 0.85 * eccentricity * mean.signum()
+            };
+            safeguarded_newton(
+                |value| {
+                    let (sin, cos) = value.sin_cos();
+                    (value - eccentricity * sin - mean, 1.0 - eccentricity * cos)
+                },
+                start,
+                -PI,
+                PI,
+            )
+            .map(|value| value + revolutions)
+            .map_err(|value| value + revolutions)
+        } else {
+            let mean = mean_anomaly.abs();
+            let start = if mean < 1.0 {
+                (6.0 * mean / eccentricity).cbrt()
+            } else {
+                (2.0 * mean / eccentricity + 1.8).ln()
+            };
+            safeguarded_newton(
+                |value| {
+                    (
+                        eccentricity * value.sinh() - value - mean,
+                        eccentricity * value.cosh() - 1.0,
+                    )
+                },
+                start,
+                (mean / eccentricity).asinh(),
+                (mean / (eccentricity - 1.0)).asinh(),
+            )
+            .map(|value| value * mean_anomaly.signum())
+            .map_err(|value| value * mean_anomaly.signum())
+        };
+        *anomaly = result.map_err(|last_value| KeplerError::NotConverged {
+            index,
+            mean_anomaly,
+            last_value,
+        })?;
+    }
+    Ok(anomalies)
+}
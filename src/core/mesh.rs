@@ -0,0 +1,201 @@
+use crate::{magnitudes, List, Vectors};
+use itertools::multizip;
+use na::RealField;
+use std::collections::HashMap;
+use std::fmt;
+
+/// Error raised when building a [`Mesh`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum MeshError {
+    /// A face refers to a vertex that does not exist.
+    VertexOutOfRange {
+        /// Index of the face.
+        face: usize,
+        /// Index of the vertex the face refers to.
+        vertex: usize,
+        /// Number of vertices of the mesh.
+        vertices: usize,
+    },
+}
+
+impl fmt::Display for MeshError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshError::VertexOutOfRange {
+                face,
+                vertex,
+                vertices,
+            } => write!(
+                f,
+                "Face {} refers to vertex {} out of the {} vertices of the mesh.",
+                face, vertex, vertices
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MeshError {}
+
+/// Triangular mesh, described by its vertices and the indices of the vertices of its faces.
+///
+/// The vertices of a face are given in counterclockwise order when seen from the outside, so that
+/// the normals computed with the right-hand rule point outward. The faces are checked to refer to
+/// existing vertices when the mesh is created, and the mesh cannot be modified afterwards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mesh<T>
+where
+    T: RealField,
+{
+    vertices: Vectors<T>,
+    faces: Vec<[usize; 3]>,
+}
+
+/// Normalize the non-zero vectors, the zero vectors are left unchanged.
+fn normalize_nonzero<T>(mut vectors: Vectors<T>) -> Vectors<T>
+where
+    T: RealField,
+{
+    for mut vector in vectors.column_iter_mut() {
+        let norm = vector.norm();
+        if norm > T::zero() {
+            vector /= norm;
+        }
+    }
+    vectors
+}
+
+impl<T> Mesh<T>
+where
+    T: RealField,
+{
+    /// Create a new mesh.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a face refers to a vertex that does not exist. Use [`Mesh::try_new`] to get an
+    /// error instead.
+    pub fn new(vertices: Vectors<T>, faces: Vec<[usize; 3]>) -> Self {
+        Self::try_new(vertices, faces).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Create a new mesh, or return an error if a face refers to a vertex that does not exist.
+    pub fn try_new(vertices: Vectors<T>, faces: Vec<[usize; 3]>) -> Result<Self, MeshError> {
+        let number_vertices = crate::number_vectors(&vertices);
+        for (index, face) in faces.iter().enumerate() {
+            if let Some(&vertex) = face.iter().find(|&&vertex| vertex >= number_vertices) {
+                return Err(MeshError::VertexOutOfRange {
+                    face: index,
+                    vertex,
+                    vertices: number_vertices,
+                });
+            }
+        }
+        Ok(Self { vertices, faces })
+    }
+
+    /// Get the vertices.
+    pub fn vertices(&self) -> &Vectors<T> {
+        &self.vertices
+    }
+
+    /// Get the indices of the three vertices of each face.
+    pub fn faces(&self) -> &[[usize; 3]] {
+        &self.faces
+    }
+
+    /// Get the number of vertices.
+    pub fn number_vertices(&self) -> usize {
+        crate::number_vectors(&self.vertices)
+    }
+
+    /// Get the number of faces.
+    pub fn number_faces(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Cross products of the edges of each face, whose directions are the normals of the faces and
+    /// whose magnitudes are twice the areas of the faces.
+    fn face_cross_products(&self) -> Vectors<T> {
+        let mut crosses = Vectors::zeros(self.number_faces());
+        for (mut cross, face) in multizip((crosses.column_iter_mut(), self.faces.iter())) {
+            let origin = self.vertices.column(face[0]);
+            let edge_1 = self.vertices.column(face[1]) - origin;
+            let edge_2 = self.vertices.column(face[2]) - origin;
+            cross.copy_from(&edge_1.cross(&edge_2));
+        }
+        crosses
+    }
+
+    /// Unit normals of the faces. The normal of a degenerate face, whose vertices are collinear,
+    /// is the zero vector.
+    pub fn face_normals(&self) -> Vectors<T> {
+        normalize_nonzero(self.face_cross_products())
+    }
+
+    /// Areas of the faces, null for degenerate faces.
+    pub fn face_areas(&self) -> List<T> {
+        magnitudes(&self.face_cross_products()) / (T::one() + T::one())
+    }
+
+    /// Centroids of the faces.
+    pub fn face_centroids(&self) -> Vectors<T> {
+        let mut centroids = Vectors::zeros(self.number_faces());
+        let three = T::one() + T::one() + T::one();
+        for (mut centroid, face) in multizip((centroids.column_iter_mut(), self.faces.iter())) {
+            centroid.copy_from(
+                &((self.vertices.column(face[0])
+                    + self.vertices.column(face[1])
+                    + self.vertices.column(face[2]))
+                    / three),
+            );
+        }
+        centroids
+    }
+
+    /// Unit normals of the vertices, computed as the average of the normals of the faces sharing
+    /// the vertex, weighted by their areas. The normal of a vertex used by no face is the zero
+    /// vector.
+    pub fn vertex_normals(&self) -> Vectors<T> {
+        let mut normals = Vectors::zeros(self.number_vertices());
+        for (cross, face) in multizip((self.face_cross_products().column_iter(), self.faces.iter()))
+        {
+            for &vertex in face.iter() {
+                let mut normal = normals.column_mut(vertex);
+                normal += cross;
+            }
+        }
+        normalize_nonzero(normals)
+    }
+
+    /// Count the faces using each directed edge.
+    fn directed_edges(&self) -> HashMap<(usize, usize), usize> {
+        let mut edges = HashMap::new();
+        for face in self.faces.iter() {
+            for index in 0..3 {
+                *edges
+                    .entry((face[index], face[(index + 1) % 3]))
+                    .or_insert(0) += 1;
+            }
+        }
+        edges
+    }
+
+    /// Check that the mesh is closed: each edge is shared by exactly two faces. A mesh without
+    /// faces is not closed.
+    pub fn is_closed(&self) -> bool {
+        if self.faces.is_empty() {
+            return false;
+        }
+        let mut edges = HashMap::new();
+        for ((start, end), count) in self.directed_edges() {
+            *edges.entry((start.min(end), start.max(end))).or_insert(0) += count;
+        }
+        edges.values().all(|&count| count == 2)
+    }
+
+    /// Check that the faces are consistently oriented: each edge is traversed at most once in each
+    /// direction, so that two neighbouring faces list their common edge in opposite directions.
+    pub fn is_consistently_oriented(&self) -> bool {
+        self.directed_edges().values().all(|&count| count == 1)
+    }
+}
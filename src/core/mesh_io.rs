@@ -0,0 +1,764 @@
+use crate::{List, Mesh, Vectors};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// Error raised while reading or writing a shape model.
+#[derive(Debug)]
+pub enum MeshIoError {
+    /// Error from the file system.
+    Io(std::io::Error),
+    /// Malformed line of a text file, with the line number starting at 1.
+    Parse {
+        /// Number of the line.
+        line: usize,
+        /// Description of the error.
+        message: String,
+    },
+    /// Malformed binary content or invalid data.
+    Format(String),
+}
+
+impl fmt::Display for MeshIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeshIoError::Io(error) => write!(f, "{}", error),
+            MeshIoError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            MeshIoError::Format(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for MeshIoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MeshIoError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for MeshIoError {
+    fn from(error: std::io::Error) -> Self {
+        MeshIoError::Io(error)
+    }
+}
+
+/// Scalar attribute of the vertices or the faces of a [`Mesh`], to be written along the shape
+/// model when the format allows it.
+#[derive(Debug, Clone, Copy)]
+pub enum MeshAttribute<'a> {
+    /// One value per vertex, with the name of the attribute, which cannot contain whitespace.
+    Vertex(&'a str, &'a List<f64>),
+    /// One value per face, with the name of the attribute, which cannot contain whitespace.
+    Face(&'a str, &'a List<f64>),
+}
+
+/// Build a parse error.
+fn parse_error<S: Into<String>>(line: usize, message: S) -> MeshIoError {
+    MeshIoError::Parse {
+        line,
+        message: message.into(),
+    }
+}
+
+/// Parse the three coordinates of a vertex from the tokens.
+fn parse_coordinates<'a>(
+    tokens: &mut impl Iterator<Item = &'a str>,
+    line: usize,
+) -> Result<[f64; 3], MeshIoError> {
+    let mut coordinates = [0.0; 3];
+    for coordinate in coordinates.iter_mut() {
+        let token = tokens
+            .next()
+            .ok_or_else(|| parse_error(line, "expected three coordinates"))?;
+        *coordinate = token
+            .parse()
+            .map_err(|_| parse_error(line, format!("invalid coordinate `{}`", token)))?;
+    }
+    Ok(coordinates)
+}
+
+/// Triangulate a polygon as a fan around its first vertex.
+fn triangulate(polygon: &[usize], faces: &mut Vec<[usize; 3]>) {
+    for index in 1..polygon.len() - 1 {
+        faces.push([polygon[0], polygon[index], polygon[index + 1]]);
+    }
+}
+
+/// Check that the faces refer to existing vertices.
+fn check_faces(
+    number_vertices: usize,
+    faces: &[[usize; 3]],
+    lines: Option<&[usize]>,
+) -> Result<(), MeshIoError> {
+    for (index, face) in faces.iter().enumerate() {
+        if let Some(&vertex) = face.iter().find(|&&vertex| vertex >= number_vertices) {
+            let message = format!(
+                "face refers to vertex {} out of the {} vertices",
+                vertex, number_vertices
+            );
+            return Err(match lines {
+                Some(lines) => parse_error(lines[index], message),
+                None => MeshIoError::Format(format!("face {}: {}", index, message)),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Build the [`Mesh`] from the coordinates of the vertices.
+fn build_mesh(coordinates: Vec<f64>, faces: Vec<[usize; 3]>) -> Mesh<f64> {
+    Mesh::new(Vectors::from_vec(coordinates), faces)
+}
+
+/// Read a shape model from a [Wavefront OBJ](https://en.wikipedia.org/wiki/Wavefront_.obj_file)
+/// file.
+///
+/// Only the vertices (`v`) and the faces (`f`) are read. Polygonal faces are triangulated, texture
+/// and normal indices of the faces are ignored, and negative indices are relative to the last
+/// vertex read.
+pub fn read_obj<P: AsRef<Path>>(path: P) -> Result<Mesh<f64>, MeshIoError> {
+    parse_obj(&fs::read_to_string(path)?)
+}
+
+/// Parse the content of an OBJ file.
+fn parse_obj(content: &str) -> Result<Mesh<f64>, MeshIoError> {
+    let mut coordinates = vec![];
+    let mut faces = vec![];
+    let mut face_lines = vec![];
+    for (index, text) in content.lines().enumerate() {
+        let line = index + 1;
+        let mut tokens = text.split_whitespace();
+        match tokens.next() {
+            Some("v") => coordinates.extend_from_slice(&parse_coordinates(&mut tokens, line)?),
+            Some("f") => {
+                let number_vertices = coordinates.len() / 3;
+                let mut polygon = vec![];
+                for token in tokens {
+                    let vertex = token.split('/').next().unwrap_or("");
+                    let vertex: isize = vertex.parse().map_err(|_| {
+                        parse_error(line, format!("invalid vertex index `{}`", token))
+                    })?;
+                    let vertex = match vertex {
+                        0 => return Err(parse_error(line, "vertex indices start at 1")),
+                        v if v > 0 => v as usize - 1,
+                        v if (-v) as usize <= number_vertices => {
+                            (number_vertices as isize + v) as usize
+                        }
+                        v => {
+                            return Err(parse_error(
+                                line,
+                                format!("relative vertex index {} out of range", v),
+                            ))
+                        }
+                    };
+                    polygon.push(vertex);
+                }
+                if polygon.len() < 3 {
+                    return Err(parse_error(line, "a face needs at least three vertices"));
+                }
+                let previous_number_faces = faces.len();
+                triangulate(&polygon, &mut faces);
+                face_lines.resize(face_lines.len() + faces.len() - previous_number_faces, line);
+            }
+            _ => (),
+        }
+    }
+    check_faces(coordinates.len() / 3, &faces, Some(&face_lines))?;
+    Ok(build_mesh(coordinates, faces))
+}
+
+/// Write a shape model to a Wavefront OBJ file. The format has no standard way to store scalar
+/// attributes, see [`write_ply`] for that.
+pub fn write_obj<P: AsRef<Path>>(path: P, mesh: &Mesh<f64>) -> Result<(), MeshIoError> {
+    let mut content = String::new();
+    for vertex in mesh.vertices().column_iter() {
+        content += &format!("v {} {} {}\n", vertex[0], vertex[1], vertex[2]);
+    }
+    for face in mesh.faces().iter() {
+        content += &format!("f {} {} {}\n", face[0] + 1, face[1] + 1, face[2] + 1);
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Encoding of the body of a PLY file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PlyFormat {
+    Ascii,
+    BinaryLittleEndian,
+    BinaryBigEndian,
+}
+
+/// Scalar types of the PLY format.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PlyType {
+    Int8,
+    Uint8,
+    Int16,
+    Uint16,
+    Int32,
+    Uint32,
+    Float32,
+    Float64,
+}
+
+impl PlyType {
+    /// Parse the name of the type.
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(PlyType::Int8),
+            "uchar" | "uint8" => Some(PlyType::Uint8),
+            "short" | "int16" => Some(PlyType::Int16),
+            "ushort" | "uint16" => Some(PlyType::Uint16),
+            "int" | "int32" => Some(PlyType::Int32),
+            "uint" | "uint32" => Some(PlyType::Uint32),
+            "float" | "float32" => Some(PlyType::Float32),
+            "double" | "float64" => Some(PlyType::Float64),
+            _ => None,
+        }
+    }
+
+    /// Number of bytes of the type.
+    fn size(&self) -> usize {
+        match self {
+            PlyType::Int8 | PlyType::Uint8 => 1,
+            PlyType::Int16 | PlyType::Uint16 => 2,
+            PlyType::Int32 | PlyType::Uint32 | PlyType::Float32 => 4,
+            PlyType::Float64 => 8,
+        }
+    }
+
+    /// Decode a value of the type from bytes.
+    fn decode(&self, bytes: &[u8], format: PlyFormat) -> f64 {
+        macro_rules! decode {
+            ($type:ty) => {{
+                let mut buffer = [0; std::mem::size_of::<$type>()];
+                buffer.copy_from_slice(bytes);
+                if format == PlyFormat::BinaryBigEndian {
+                    <$type>::from_be_bytes(buffer) as f64
+                } else {
+                    <$type>::from_le_bytes(buffer) as f64
+                }
+            }};
+        }
+        match self {
+            PlyType::Int8 => decode!(i8),
+            PlyType::Uint8 => decode!(u8),
+            PlyType::Int16 => decode!(i16),
+            PlyType::Uint16 => decode!(u16),
+            PlyType::Int32 => decode!(i32),
+            PlyType::Uint32 => decode!(u32),
+            PlyType::Float32 => decode!(f32),
+            PlyType::Float64 => decode!(f64),
+        }
+    }
+}
+
+/// Property of an element of a PLY file.
+#[derive(Debug, Clone)]
+enum PlyProperty {
+    Scalar(String, PlyType),
+    List(String, PlyType, PlyType),
+}
+
+impl PlyProperty {
+    /// Name of the property.
+    fn name(&self) -> &str {
+        match self {
+            PlyProperty::Scalar(name, _) | PlyProperty::List(name, _, _) => name,
+        }
+    }
+}
+
+/// Element of a PLY file.
+#[derive(Debug, Clone)]
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+/// Source of the values of the body of a PLY file.
+enum PlyBody<'a> {
+    Ascii {
+        lines: std::iter::Enumerate<std::str::Lines<'a>>,
+        offset: usize,
+    },
+    Binary {
+        bytes: &'a [u8],
+        position: usize,
+        format: PlyFormat,
+    },
+}
+
+impl<'a> PlyBody<'a> {
+    /// Read the values of the next element instance.
+    fn next_values(
+        &mut self,
+        element: &PlyElement,
+        instance: usize,
+    ) -> Result<Vec<Vec<f64>>, MeshIoError> {
+        match self {
+            PlyBody::Ascii { lines, offset } => {
+                let (index, text) = lines.next().ok_or_else(|| {
+                    MeshIoError::Format(format!(
+                        "unexpected end of file in {} {}",
+                        element.name, instance
+                    ))
+                })?;
+                let line = index + *offset;
+                let mut tokens = text.split_whitespace();
+                let mut next = |name: &str| -> Result<f64, MeshIoError> {
+                    let token = tokens
+                        .next()
+                        .ok_or_else(|| parse_error(line, format!("missing property `{}`", name)))?;
+                    token
+                        .parse()
+                        .map_err(|_| parse_error(line, format!("invalid value `{}`", token)))
+                };
+                let mut values = vec![];
+                for property in element.properties.iter() {
+                    match property {
+                        PlyProperty::Scalar(name, _) => values.push(vec![next(name)?]),
+                        PlyProperty::List(name, _, _) => {
+                            let count = next(name)? as usize;
+                            values.push((0..count).map(|_| next(name)).collect::<Result<_, _>>()?);
+                        }
+                    }
+                }
+                Ok(values)
+            }
+            PlyBody::Binary {
+                bytes,
+                position,
+                format,
+            } => {
+                let mut read = |kind: PlyType| -> Result<f64, MeshIoError> {
+                    let end = *position + kind.size();
+                    if end > bytes.len() {
+                        return Err(MeshIoError::Format(format!(
+                            "unexpected end of file in {} {}",
+                            element.name, instance
+                        )));
+                    }
+                    let value = kind.decode(&bytes[*position..end], *format);
+                    *position = end;
+                    Ok(value)
+                };
+                let mut values = vec![];
+                for property in element.properties.iter() {
+                    match property {
+                        PlyProperty::Scalar(_, kind) => values.push(vec![read(*kind)?]),
+                        PlyProperty::List(_, count_kind, kind) => {
+                            let count = read(*count_kind)? as usize;
+                            values.push((0..count).map(|_| read(*kind)).collect::<Result<_, _>>()?);
+                        }
+                    }
+                }
+                Ok(values)
+            }
+        }
+    }
+
+    /// Line of the last element read, for ascii files.
+    fn line(&self, index: usize) -> Option<usize> {
+        match self {
+            PlyBody::Ascii { offset, .. } => Some(index + offset),
+            PlyBody::Binary { .. } => None,
+        }
+    }
+}
+
+/// Read a shape model from a [PLY](https://en.wikipedia.org/wiki/PLY_(file_format)) file, in
+/// ascii or binary format.
+///
+/// The vertices are read from the properties `x`, `y` and `z` of the element `vertex`, and the
+/// faces from the list property `vertex_indices` (or `vertex_index`) of the element `face`.
+/// Polygonal faces are triangulated and other elements and properties are ignored.
+pub fn read_ply<P: AsRef<Path>>(path: P) -> Result<Mesh<f64>, MeshIoError> {
+    parse_ply(&fs::read(path)?)
+}
+
+/// Parse the content of a PLY file.
+fn parse_ply(content: &[u8]) -> Result<Mesh<f64>, MeshIoError> {
+    // Header.
+    let mut format = None;
+    let mut elements: Vec<PlyElement> = vec![];
+    let mut position = 0;
+    let mut line = 0;
+    loop {
+        let end = content[position..]
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .ok_or_else(|| parse_error(line + 1, "missing `end_header`"))?;
+        let text = String::from_utf8_lossy(&content[position..position + end]);
+        position += end + 1;
+        line += 1;
+        let mut tokens = text.split_whitespace();
+        match (line, tokens.next()) {
+            (1, Some("ply")) => (),
+            (1, _) => return Err(parse_error(line, "expected `ply` magic number")),
+            (_, Some("format")) => {
+                format = Some(match tokens.next() {
+                    Some("ascii") => PlyFormat::Ascii,
+                    Some("binary_little_endian") => PlyFormat::BinaryLittleEndian,
+                    Some("binary_big_endian") => PlyFormat::BinaryBigEndian,
+                    _ => return Err(parse_error(line, "unknown format")),
+                })
+            }
+            (_, Some("element")) => {
+                let name = tokens
+                    .next()
+                    .ok_or_else(|| parse_error(line, "missing element name"))?;
+                let count = tokens
+                    .next()
+                    .and_then(|count| count.parse().ok())
+                    .ok_or_else(|| parse_error(line, "invalid element count"))?;
+                elements.push(PlyElement {
+                    name: name.to_string(),
+                    count,
+                    properties: vec![],
+                });
+            }
+            (_, Some("property")) => {
+                let element = elements
+                    .last_mut()
+                    .ok_or_else(|| parse_error(line, "property declared before any element"))?;
+                let kind = tokens.next().unwrap_or("");
+                let property = if kind == "list" {
+                    let count_kind = tokens.next().and_then(PlyType::parse);
+                    let item_kind = tokens.next().and_then(PlyType::parse);
+                    match (count_kind, item_kind, tokens.next()) {
+                        (Some(count_kind), Some(item_kind), Some(name)) => {
+                            PlyProperty::List(name.to_string(), count_kind, item_kind)
+                        }
+                        _ => return Err(parse_error(line, "invalid list property")),
+                    }
+                } else {
+                    match (PlyType::parse(kind), tokens.next()) {
+                        (Some(kind), Some(name)) => PlyProperty::Scalar(name.to_string(), kind),
+                        _ => return Err(parse_error(line, format!("invalid property `{}`", kind))),
+                    }
+                };
+                element.properties.push(property);
+            }
+            (_, Some("end_header")) => break,
+            (_, Some("comment")) | (_, Some("obj_info")) | (_, None) => (),
+            (_, Some(keyword)) => {
+                return Err(parse_error(line, format!("unknown keyword `{}`", keyword)))
+            }
+        }
+    }
+    let format = format.ok_or_else(|| parse_error(line, "missing format"))?;
+
+    // Body.
+    let body_text;
+    let mut body = if format == PlyFormat::Ascii {
+        body_text = String::from_utf8_lossy(&content[position..]);
+        PlyBody::Ascii {
+            lines: body_text.lines().enumerate(),
+            offset: line + 1,
+        }
+    } else {
+        PlyBody::Binary {
+            bytes: &content[position..],
+            position: 0,
+            format,
+        }
+    };
+    let mut coordinates = vec![];
+    let mut faces = vec![];
+    let mut face_lines = vec![];
+    let mut number_ascii_lines = 0;
+    for element in elements.iter() {
+        let find = |names: &[&str]| {
+            element
+                .properties
+                .iter()
+                .position(|property| names.contains(&property.name()))
+        };
+        match element.name.as_str() {
+            "vertex" => {
+                let indices = [find(&["x"]), find(&["y"]), find(&["z"])];
+                let is_scalar = |index: &Option<usize>| {
+                    matches!(
+                        index.map(|index| &element.properties[index]),
+                        Some(PlyProperty::Scalar(..))
+                    )
+                };
+                if !indices.iter().all(is_scalar) {
+                    return Err(MeshIoError::Format(
+                        "element vertex needs the scalar properties x, y and z".to_string(),
+                    ));
+                }
+                for instance in 0..element.count {
+                    let values = body.next_values(element, instance)?;
+                    for index in indices.iter().flatten() {
+                        coordinates.push(values[*index][0]);
+                    }
+                    number_ascii_lines += 1;
+                }
+            }
+            "face" => {
+                let index = find(&["vertex_indices", "vertex_index"]).ok_or_else(|| {
+                    MeshIoError::Format(
+                        "element face needs the list property vertex_indices".to_string(),
+                    )
+                })?;
+                for instance in 0..element.count {
+                    let values = body.next_values(element, instance)?;
+                    let face_error = |message: String| match body.line(number_ascii_lines) {
+                        Some(line) => parse_error(line, message),
+                        None => MeshIoError::Format(format!("face {}: {}", instance, message)),
+                    };
+                    let mut polygon = vec![];
+                    for &vertex in values[index].iter() {
+                        if !vertex.is_finite() || vertex < 0.0 || vertex.fract() != 0.0 {
+                            return Err(face_error(format!("invalid vertex index `{}`", vertex)));
+                        }
+                        polygon.push(vertex as usize);
+                    }
+                    if polygon.len() < 3 {
+                        return Err(face_error(
+                            "a face needs at least three vertices".to_string(),
+                        ));
+                    }
+                    let previous_number_faces = faces.len();
+                    triangulate(&polygon, &mut faces);
+                    face_lines.resize(
+                        face_lines.len() + faces.len() - previous_number_faces,
+                        body.line(number_ascii_lines).unwrap_or(0),
+                    );
+                    number_ascii_lines += 1;
+                }
+            }
+            _ => {
+                for instance in 0..element.count {
+                    body.next_values(element, instance)?;
+                    number_ascii_lines += 1;
+                }
+            }
+        }
+    }
+    let lines = match format {
+        PlyFormat::Ascii => Some(face_lines.as_slice()),
+        _ => None,
+    };
+    check_faces(coordinates.len() / 3, &faces, lines)?;
+    Ok(build_mesh(coordinates, faces))
+}
+
+/// Check the names and the sizes of the attributes and sort them by vertex and face. The names
+/// are written in the header of PLY files, so they cannot be empty or contain whitespace.
+#[allow(clippy::type_complexity)]
+fn split_attributes<'a>(
+    mesh: &Mesh<f64>,
+    attributes: &[MeshAttribute<'a>],
+) -> Result<(Vec<(&'a str, &'a List<f64>)>, Vec<(&'a str, &'a List<f64>)>), MeshIoError> {
+    let mut vertex_attributes = vec![];
+    let mut face_attributes = vec![];
+    for attribute in attributes.iter() {
+        let (name, list, expected_size, kind) = match *attribute {
+            MeshAttribute::Vertex(name, list) => {
+                vertex_attributes.push((name, list));
+                (name, list, mesh.number_vertices(), "vertices")
+            }
+            MeshAttribute::Face(name, list) => {
+                face_attributes.push((name, list));
+                (name, list, mesh.number_faces(), "faces")
+            }
+        };
+        if name.is_empty() || name.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return Err(MeshIoError::Format(format!(
+                "invalid attribute name `{}`",
+                name.escape_debug()
+            )));
+        }
+        if list.len() != expected_size {
+            return Err(MeshIoError::Format(format!(
+                "attribute `{}` has {} values for {} {}",
+                name,
+                list.len(),
+                expected_size,
+                kind
+            )));
+        }
+    }
+    Ok((vertex_attributes, face_attributes))
+}
+
+/// Write a shape model to an ascii PLY file, with optional scalar attributes of the vertices or
+/// the faces stored as properties of type `double`.
+pub fn write_ply<P: AsRef<Path>>(
+    path: P,
+    mesh: &Mesh<f64>,
+    attributes: &[MeshAttribute],
+) -> Result<(), MeshIoError> {
+    let (vertex_attributes, face_attributes) = split_attributes(mesh, attributes)?;
+    let mut content = String::from("ply\nformat ascii 1.0\n");
+    content += &format!("element vertex {}\n", mesh.number_vertices());
+    content += "property double x\nproperty double y\nproperty double z\n";
+    for (name, _) in vertex_attributes.iter() {
+        content += &format!("property double {}\n", name);
+    }
+    content += &format!("element face {}\n", mesh.number_faces());
+    content += "property list uchar int vertex_indices\n";
+    for (name, _) in face_attributes.iter() {
+        content += &format!("property double {}\n", name);
+    }
+    content += "end_header\n";
+    for (index, vertex) in mesh.vertices().column_iter().enumerate() {
+        content += &format!("{} {} {}", vertex[0], vertex[1], vertex[2]);
+        for (_, list) in vertex_attributes.iter() {
+            content += &format!(" {}", list[index]);
+        }
+        content += "\n";
+    }
+    for (index, face) in mesh.faces().iter().enumerate() {
+        content += &format!("3 {} {} {}", face[0], face[1], face[2]);
+        for (_, list) in face_attributes.iter() {
+            content += &format!(" {}", list[index]);
+        }
+        content += "\n";
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
+
+/// Index of a vertex of a STL file, merging the vertices with the same coordinates.
+fn stl_vertex(
+    vertex: [f64; 3],
+    coordinates: &mut Vec<f64>,
+    indices: &mut HashMap<[u64; 3], usize>,
+) -> usize {
+    // The negative zero is merged with the zero.
+    let key = [
+        (vertex[0] + 0.0).to_bits(),
+        (vertex[1] + 0.0).to_bits(),
+        (vertex[2] + 0.0).to_bits(),
+    ];
+    *indices.entry(key).or_insert_with(|| {
+        coordinates.extend_from_slice(&vertex);
+        coordinates.len() / 3 - 1
+    })
+}
+
+/// Read a shape model from a [STL](https://en.wikipedia.org/wiki/STL_(file_format)) file, in
+/// ascii or binary format.
+///
+/// STL files store each facet with its own vertices, the vertices with the exact same coordinates
+/// are merged to build the list of vertices of the mesh. The normals of the file are ignored.
+pub fn read_stl<P: AsRef<Path>>(path: P) -> Result<Mesh<f64>, MeshIoError> {
+    let content = fs::read(path)?;
+    let is_binary = content.len() >= 84 && {
+        let mut buffer = [0; 4];
+        buffer.copy_from_slice(&content[80..84]);
+        content.len() == 84 + 50 * u32::from_le_bytes(buffer) as usize
+    };
+    if is_binary || !content.starts_with(b"solid") {
+        parse_stl_binary(&content)
+    } else {
+        parse_stl_ascii(&String::from_utf8_lossy(&content))
+    }
+}
+
+/// Parse the content of a binary STL file.
+fn parse_stl_binary(content: &[u8]) -> Result<Mesh<f64>, MeshIoError> {
+    if content.len() < 84 {
+        return Err(MeshIoError::Format(
+            "binary STL file shorter than its header".to_string(),
+        ));
+    }
+    let mut buffer = [0; 4];
+    buffer.copy_from_slice(&content[80..84]);
+    let number_faces = u32::from_le_bytes(buffer) as usize;
+    if content.len() < 84 + 50 * number_faces {
+        return Err(MeshIoError::Format(format!(
+            "binary STL file too short for its {} facets",
+            number_faces
+        )));
+    }
+    let mut coordinates = vec![];
+    let mut indices = HashMap::new();
+    let mut faces = Vec::with_capacity(number_faces);
+    for facet in content[84..84 + 50 * number_faces].chunks(50) {
+        let mut face = [0; 3];
+        for (index, vertex) in face.iter_mut().enumerate() {
+            let mut coordinate = [0.0; 3];
+            for (component, value) in coordinate.iter_mut().enumerate() {
+                let start = 12 * (index + 1) + 4 * component;
+                buffer.copy_from_slice(&facet[start..start + 4]);
+                *value = f32::from_le_bytes(buffer) as f64;
+            }
+            *vertex = stl_vertex(coordinate, &mut coordinates, &mut indices);
+        }
+        faces.push(face);
+    }
+    Ok(build_mesh(coordinates, faces))
+}
+
+/// Parse the content of an ascii STL file.
+fn parse_stl_ascii(content: &str) -> Result<Mesh<f64>, MeshIoError> {
+    let mut coordinates = vec![];
+    let mut indices = HashMap::new();
+    let mut faces = vec![];
+    let mut face = vec![];
+    let mut last_line = 0;
+    for (index, text) in content.lines().enumerate() {
+        let line = index + 1;
+        last_line = line;
+        let mut tokens = text.split_whitespace();
+        match tokens.next() {
+            Some("vertex") => {
+                if face.len() == 3 {
+                    return Err(parse_error(line, "a facet has exactly three vertices"));
+                }
+                let vertex = parse_coordinates(&mut tokens, line)?;
+                face.push(stl_vertex(vertex, &mut coordinates, &mut indices));
+            }
+            Some("endfacet") => {
+                if face.len() != 3 {
+                    return Err(parse_error(line, "a facet has exactly three vertices"));
+                }
+                faces.push([face[0], face[1], face[2]]);
+                face.clear();
+            }
+            Some("solid") | Some("facet") | Some("outer") | Some("endloop") | Some("endsolid")
+            | None => (),
+            Some(keyword) => {
+                return Err(parse_error(line, format!("unknown keyword `{}`", keyword)))
+            }
+        }
+    }
+    if !face.is_empty() {
+        return Err(parse_error(last_line, "unterminated facet"));
+    }
+    Ok(build_mesh(coordinates, faces))
+}
+
+/// Write a shape model to a binary STL file. The format has no standard way to store scalar
+/// attributes, see [`write_ply`] for that.
+pub fn write_stl<P: AsRef<Path>>(path: P, mesh: &Mesh<f64>) -> Result<(), MeshIoError> {
+    let mut content = Vec::with_capacity(84 + 50 * mesh.number_faces());
+    let mut header = [b' '; 80];
+    header[..7].copy_from_slice(b"rustool");
+    content.write_all(&header)?;
+    content.write_all(&(mesh.number_faces() as u32).to_le_bytes())?;
+    for (normal, face) in mesh.face_normals().column_iter().zip(mesh.faces().iter()) {
+        for component in normal.iter() {
+            content.write_all(&(*component as f32).to_le_bytes())?;
+        }
+        for &vertex in face.iter() {
+            for component in mesh.vertices().column(vertex).iter() {
+                content.write_all(&(*component as f32).to_le_bytes())?;
+            }
+        }
+        content.write_all(&[0, 0])?;
+    }
+    fs::write(path, content)?;
+    Ok(())
+}
@@ -1,3 +1,7 @@
+/// Angles with normalisation and sexagesimal formats.
+pub mod angle;
+/// Physical properties of Solar System bodies.
+pub mod body;
 /// Collection of functions for geometry computations.
 pub mod geometry;
 /// Log aliases.
@@ -15,14 +19,66 @@ pub mod numerical_algorithms;
 /// General macros.
 #[macro_use]
 pub mod macros;
+/// Dual numbers for automatic differentiation.
+pub mod dual;
+/// Low-precision analytic ephemerides of the planets.
+pub mod ephemeris;
+/// Parsers of JPL Horizons and Minor Planet Center text ephemerides.
+pub mod ephemeris_io;
+/// Epochs and time scales.
+pub mod epoch;
+/// Reference frames and frame transforms.
+pub mod frame;
 /// General functions.
 pub mod general;
+/// Illumination geometry of facets.
+pub mod illumination;
+/// Kepler's equation solver.
+pub mod kepler;
+/// Triangular meshes.
+pub mod mesh;
+/// Import and export of shape models.
+pub mod mesh_io;
+/// Keplerian orbits.
+pub mod orbit;
+/// Dimensioned quantities.
+pub mod quantity;
+/// Intersections of rays with triangles.
+pub mod ray;
+/// Rotations of vectors.
+pub mod rotation;
+/// Reader of SPICE SPK ephemeris kernels.
+pub mod spk;
+/// One-dimensional thermophysical model of facets.
+pub mod thermal;
+/// Two-body propagation and Lambert's problem.
+pub mod two_body;
+/// View factors for radiative exchange.
+pub mod view_factor;
 
+pub use self::angle::*;
+pub use self::body::*;
 pub use self::constants::*;
+pub use self::dual::*;
+pub use self::ephemeris::*;
+pub use self::ephemeris_io::*;
+pub use self::epoch::*;
+pub use self::frame::*;
 pub use self::general::*;
 pub use self::geometry::*;
+pub use self::illumination::*;
 pub use self::json::*;
+pub use self::kepler::*;
 pub use self::log::*;
-pub use self::macros::*;
 pub use self::matrix::*;
+pub use self::mesh::*;
+pub use self::mesh_io::*;
 pub use self::numerical_algorithms::*;
+pub use self::orbit::*;
+pub use self::quantity::*;
+pub use self::ray::*;
+pub use self::rotation::*;
+pub use self::spk::*;
+pub use self::thermal::*;
+pub use self::two_body::*;
+pub use self::view_factor::*;
@@ -1,13 +1,216 @@
-use crate::List;
-use itertools::all;
-use na::RealField;
-use num_traits::NumCast;
+use crate::{clip, Dual, List};
+use na::{DMatrix, DVector, RealField};
+use std::fmt;
 
 /// After so many iterations, consider the numerical method has failed to converge.
 pub const NUMBER_ITERATION_FAIL: usize = 1e6 as usize;
 /// Threshold that defines the convergence condition of the numerical Newton method.
 pub const NEWTON_METHOD_THRESHOLD: f64 = 1e-5;
 
+/// Maximum number of halvings of the step by the line search of the [Newton's
+/// method][newton_method_with_options].
+pub const LINE_SEARCH_MAX_HALVINGS: usize = 30;
+
+/// Typical relative step of the finite differences of the [Newton's
+/// method][newton_method_finite_differences].
+pub const FINITE_DIFFERENCE_STEP: f64 = 1e-6;
+
+/// Options of the [Newton's method][newton_method_with_options].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewtonMethodOptions<T>
+where
+    T: RealField,
+{
+    /// Absolute tolerance on the residuals, by default [`NEWTON_METHOD_THRESHOLD`].
+    pub absolute_tolerance: T,
+    /// Tolerance on the residuals relative to the values, by default 0.
+    pub relative_tolerance: T,
+    /// Tolerance on the absolute value of the function, not used by default.
+    pub residual_tolerance: Option<T>,
+    /// Maximum number of iterations, by default [`NUMBER_ITERATION_FAIL`].
+    pub max_iterations: usize,
+    /// Factor in $\left(0,1\right]$ of the Newton steps, by default 1.
+    pub damping: T,
+    /// Whether to halve the steps that increase the absolute value of the function, by default
+    /// not.
+    pub line_search: bool,
+    /// Lower bound of the values, not used by default.
+    pub lower_bound: Option<T>,
+    /// Upper bound of the values, not used by default.
+    pub upper_bound: Option<T>,
+}
+
+impl<T> Default for NewtonMethodOptions<T>
+where
+    T: RealField,
+{
+    fn default() -> Self {
+        Self {
+            absolute_tolerance: na::convert(NEWTON_METHOD_THRESHOLD),
+            relative_tolerance: T::zero(),
+            residual_tolerance: None,
+            max_iterations: NUMBER_ITERATION_FAIL,
+            damping: T::one(),
+            line_search: false,
+            lower_bound: None,
+            upper_bound: None,
+        }
+    }
+}
+
+impl<T> NewtonMethodOptions<T>
+where
+    T: RealField,
+{
+    /// Check the consistency of the options.
+    fn check(&self) -> Result<(), String> {
+        let invalid = |message: &str| Err(message.to_string());
+        if self.absolute_tolerance < T::zero()
+            || self.relative_tolerance < T::zero()
+            || self
+                .residual_tolerance
+                .is_some_and(|tolerance| tolerance < T::zero())
+        {
+            return invalid("the tolerances must be positive");
+        }
+        if self.max_iterations == 0 {
+            return invalid("the maximum number of iterations must be positive");
+        }
+        if self.damping <= T::zero() || self.damping > T::one() {
+            return invalid("the damping must be in (0, 1]");
+        }
+        if let (Some(lower), Some(upper)) = (self.lower_bound, self.upper_bound) {
+            if lower > upper {
+                return invalid("the lower bound must be below the upper bound");
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether a component has converged from its value, its residual and its function.
+    fn converged(&self, value: T, residual: T, function: T) -> bool {
+        residual.abs() <= self.absolute_tolerance + self.relative_tolerance * value.abs()
+            && self
+                .residual_tolerance
+                .is_none_or(|tolerance| function.abs() <= tolerance)
+    }
+}
+
+/// Solution of the [Newton's method][newton_method].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewtonMethodSolution<T>
+where
+    T: RealField,
+{
+    /// Values of the solution.
+    pub values: List<T>,
+    /// Number of iterations performed, until the last component converged.
+    pub iterations: usize,
+    /// Residuals of the last iteration of each component, the differences between the solution
+    /// and the previous values.
+    pub residuals: List<T>,
+    /// Number of iterations performed for each component, before it converged.
+    pub component_iterations: Vec<usize>,
+}
+
+/// Error raised by the [Newton's method][newton_method], with the indices of the faulty
+/// components and their last finite values.
+#[derive(Debug, Clone, PartialEq)]
+pub enum NewtonMethodError<T>
+where
+    T: RealField,
+{
+    /// Some components did not converge within the maximum number of iterations.
+    NotConverged {
+        /// Indices of the components that did not converge.
+        components: Vec<usize>,
+        /// Last values of these components.
+        values: Vec<T>,
+        /// Last residuals of these components.
+        residuals: Vec<T>,
+    },
+    /// The derivative is null for some components.
+    DivisionByZero {
+        /// Iteration at which the derivative is null.
+        iteration: usize,
+        /// Indices of the components with a null derivative.
+        components: Vec<usize>,
+        /// Values of these components.
+        values: Vec<T>,
+    },
+    /// The function or the derivative gives NaN or infinite values for some components.
+    NonFinite {
+        /// Iteration at which the values are not finite.
+        iteration: usize,
+        /// Indices of the components with values not finite.
+        components: Vec<usize>,
+        /// Values of these components giving the values not finite.
+        values: Vec<T>,
+    },
+    /// Inconsistent options.
+    InvalidOptions(String),
+}
+
+impl<T> fmt::Display for NewtonMethodError<T>
+where
+    T: RealField,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewtonMethodError::NotConverged {
+                components,
+                values,
+                residuals,
+            } => write!(
+                f,
+                "Newton's method did not converge for the components {:?} with the values {:?} \
+                 and the residuals {:?}",
+                components, values, residuals
+            ),
+            NewtonMethodError::DivisionByZero {
+                iteration,
+                components,
+                values,
+            } => write!(
+                f,
+                "null derivative of Newton's method at iteration {} for the components {:?} \
+                 with the values {:?}",
+                iteration, components, values
+            ),
+            NewtonMethodError::NonFinite {
+                iteration,
+                components,
+                values,
+            } => write!(
+                f,
+                "values not finite in Newton's method at iteration {} for the components {:?} \
+                 from the values {:?}",
+                iteration, components, values
+            ),
+            NewtonMethodError::InvalidOptions(message) => {
+                write!(f, "invalid options of Newton's method: {}", message)
+            }
+        }
+    }
+}
+
+impl<T> std::error::Error for NewtonMethodError<T> where T: RealField {}
+
+/// Indices and values of the active components satisfying a predicate on their position.
+fn select<T>(
+    active: &[usize],
+    values: &List<T>,
+    predicate: impl Fn(usize) -> bool,
+) -> (Vec<usize>, Vec<T>)
+where
+    T: RealField,
+{
+    (0..active.len())
+        .filter(|&index| predicate(index))
+        .map(|index| (active[index], values[index]))
+        .unzip()
+}
+
 /// Newton's method algorithm.
 ///
 /// ## Definition
@@ -31,13 +234,18 @@ pub const NEWTON_METHOD_THRESHOLD: f64 = 1e-5;
 /// [this](https://en.wikipedia.org/wiki/Newton%27s_method#Failure_analysis)).
 /// The maximum number of iterations is bounded to assume the algorithm could not converge.
 ///
+/// The solution comes with the number of iterations and the last residuals. The algorithm stops
+/// with an error naming the faulty components when it does not converge, when the derivative is
+/// null, or when the function or the derivative is not finite.
+///
 /// ## Usage
 ///
 /// In order to use this Newton's method implementaton, your need to:
 ///
 /// + create a struct to hold the arguments `*args` to be sent to both the function and the
 ///   derivative of the Newton's method
-/// + add the trait [`NewtonMethodArguments`] to your struct
+/// + add the trait [`NewtonMethodArguments`] to your struct, implementing its
+///   [`subset`][NewtonMethodArguments::subset] if the arguments are given for each component
 /// + create the function of the Newton's method
 /// + create the derivative of the Newton's method
 ///
@@ -82,44 +290,682 @@ pub const NEWTON_METHOD_THRESHOLD: f64 = 1e-5;
 /// let args = MyArguments { some_value: 12.0 };
 ///
 /// // Calling the Newton's method.
-/// let results = newton_method(
+/// let solution = newton_method(
 ///     initial_values,
 ///     newton_method_function,
 ///     newton_method_derivative,
 ///     args,
-/// );
+/// )
+/// .unwrap();
+/// assert!(solution.residuals.iter().all(|residual| residual.abs() < 1e-5));
 /// ```
 pub fn newton_method<T, A>(
     start_value: List<T>,
     newton_method_function: impl Fn(&List<T>, &A) -> List<T>,
     newton_method_derivative: impl Fn(&List<T>, &A) -> List<T>,
     newton_method_arguments: A,
-) -> List<T>
+) -> Result<NewtonMethodSolution<T>, NewtonMethodError<T>>
 where
-    T: RealField + NumCast,
+    T: RealField,
     A: NewtonMethodArguments,
 {
-    let mut new_value: List<T>;
-    let mut current_value = start_value;
-    let mut iteration = 0;
-    'convergence: loop {
-        let func_res = newton_method_function(&current_value, &newton_method_arguments);
-        let deri_res = newton_method_derivative(&current_value, &newton_method_arguments);
-        new_value = &current_value - func_res.component_div(&deri_res);
-        let all_converged = all((&new_value - &current_value).iter(), |residual| {
-            residual.abs() < NumCast::from(NEWTON_METHOD_THRESHOLD).unwrap()
-        });
-        if all_converged {
-            break 'convergence;
-        } else if iteration > NUMBER_ITERATION_FAIL {
-            panic!("Newton Method could not converge.");
+    newton_method_with_options(
+        start_value,
+        newton_method_function,
+        newton_method_derivative,
+        newton_method_arguments,
+        &NewtonMethodOptions::default(),
+    )
+}
+
+/// [Newton's method][newton_method] with [options][NewtonMethodOptions] for the convergence
+/// criteria, the maximum number of iterations, the damping, the line search and the bounds.
+///
+/// ## Method
+///
+/// The step of each component is the Newton step scaled by the damping. With the line search,
+/// the step of the components whose function grows in absolute value is halved, up to
+/// [`LINE_SEARCH_MAX_HALVINGS`] times. The iterates are clipped to the bounds with [`clip`].
+///
+/// A component $x$ has converged when the residual $\Delta x$ of the last iteration satisfies,
+///
+/// $$\left|\Delta x\right|\leq\epsilon_a+\epsilon_r\left|x\right|$$
+///
+/// and, if a residual tolerance $\epsilon_f$ is set, when $\left|f\left(x\right)\right|\leq
+/// \epsilon_f$. Each component is frozen once it has converged, and the functions are then
+/// evaluated on the remaining components only when the arguments provide their
+/// [subset][NewtonMethodArguments::subset]. The method stops when all the components have
+/// converged.
+///
+/// ```
+/// use tool::{newton_method_with_options, List, NewtonMethodArguments, NewtonMethodOptions};
+///
+/// struct Arguments;
+/// impl NewtonMethodArguments for Arguments {}
+///
+/// // Root of 1 - x², the positive one being forced by the lower bound.
+/// let options = NewtonMethodOptions {
+///     absolute_tolerance: 0.0,
+///     relative_tolerance: 1e-12,
+///     lower_bound: Some(0.5),
+///     ..NewtonMethodOptions::default()
+/// };
+/// let solution = newton_method_with_options(
+///     List::from_element(1, -3.0),
+///     |values, _| values.map(|value| 1.0 - value * value),
+///     |values, _| values * -2.0,
+///     Arguments,
+///     &options,
+/// )
+/// .unwrap();
+/// assert!((solution.values[0] - 1.0f64).abs() < 1e-12);
+/// ```
+pub fn newton_method_with_options<T, A>(
+    start_value: List<T>,
+    newton_method_function: impl Fn(&List<T>, &A) -> List<T>,
+    newton_method_derivative: impl Fn(&List<T>, &A) -> List<T>,
+    newton_method_arguments: A,
+    options: &NewtonMethodOptions<T>,
+) -> Result<NewtonMethodSolution<T>, NewtonMethodError<T>>
+where
+    T: RealField,
+    A: NewtonMethodArguments,
+{
+    options.check().map_err(NewtonMethodError::InvalidOptions)?;
+    let bounded = |values: &List<T>| clip(values, options.lower_bound, options.upper_bound);
+    let function = |values: &List<T>, active: &[usize]| {
+        evaluate(
+            &newton_method_function,
+            values,
+            active,
+            &newton_method_arguments,
+        )
+    };
+    let non_finite = |iteration: usize, active: &[usize], values: &List<T>, results: &List<T>| {
+        let (components, values) = select(active, values, |index| !results[index].is_finite());
+        if components.is_empty() {
+            Ok(())
         } else {
-            current_value = new_value;
+            Err(NewtonMethodError::NonFinite {
+                iteration,
+                components,
+                values,
+            })
         }
+    };
+
+    let size = start_value.len();
+    let mut current_value = bounded(&start_value);
+    let mut active = (0..size).collect::<Vec<_>>();
+    let mut func_res = function(&current_value, &active);
+    non_finite(1, &active, &current_value, &func_res)?;
+    let mut residuals = List::zeros(size);
+    let mut component_iterations = vec![0; size];
+    let mut iteration = 0;
+    while !active.is_empty() && iteration < options.max_iterations {
         iteration += 1;
+
+        let values = gather(&current_value, &active);
+        let deri_res = evaluate(
+            &newton_method_derivative,
+            &current_value,
+            &active,
+            &newton_method_arguments,
+        );
+        non_finite(iteration, &active, &values, &deri_res)?;
+        let (components, zeros) = select(&active, &values, |index| deri_res[index].is_zero());
+        if !components.is_empty() {
+            return Err(NewtonMethodError::DivisionByZero {
+                iteration,
+                components,
+                values: zeros,
+            });
+        }
+        let mut step = -func_res.component_div(&deri_res) * options.damping;
+        let mut new_values = bounded(&(&values + &step));
+        let mut new_func_res = function(&scatter(&current_value, &active, &new_values), &active);
+        if options.line_search {
+            for _ in 0..LINE_SEARCH_MAX_HALVINGS {
+                let increasing = (0..step.len())
+                    .filter(|&index| {
+                        !new_func_res[index].is_finite()
+                            || new_func_res[index].abs() > func_res[index].abs()
+                    })
+                    .collect::<Vec<_>>();
+                if increasing.is_empty() {
+                    break;
+                }
+                for index in increasing {
+                    step[index] /= T::one() + T::one();
+                }
+                new_values = bounded(&(&values + &step));
+                new_func_res = function(&scatter(&current_value, &active, &new_values), &active);
+            }
+        }
+        non_finite(iteration, &active, &values, &new_values)?;
+        non_finite(iteration, &active, &new_values, &new_func_res)?;
+
+        // The converged components are frozen and leave the active ones.
+        let mut still_active = Vec::with_capacity(active.len());
+        let mut still_func_res = Vec::with_capacity(active.len());
+        for (index, &component) in active.iter().enumerate() {
+            let residual = new_values[index] - values[index];
+            current_value[component] = new_values[index];
+            residuals[component] = residual;
+            component_iterations[component] = iteration;
+            if !options.converged(new_values[index], residual, new_func_res[index]) {
+                still_active.push(component);
+                still_func_res.push(new_func_res[index]);
+            }
+        }
+        active = still_active;
+        func_res = List::from_iterator(active.len(), still_func_res);
+    }
+
+    if active.is_empty() {
+        Ok(NewtonMethodSolution {
+            values: current_value,
+            iterations: iteration,
+            residuals,
+            component_iterations,
+        })
+    } else {
+        Err(NewtonMethodError::NotConverged {
+            values: active.iter().map(|&index| current_value[index]).collect(),
+            residuals: active.iter().map(|&index| residuals[index]).collect(),
+            components: active,
+        })
     }
-    new_value
+}
+
+/// [Newton's method][newton_method_with_options] with the derivative approximated by finite
+/// differences, so that only the function is supplied.
+///
+/// ## Expression
+///
+/// The derivative is approximated by central differences, of step $h$ relative to the values,
+///
+/// $$f'\left(x\right)\approx\frac{f\left(x+\delta\right)-f\left(x-\delta\right)}{2\delta}
+/// \quad\text{with}\quad\delta=h\max\left(1,\left|x\right|\right)$$
+///
+/// The step is typically [`FINITE_DIFFERENCE_STEP`]. A step not strictly positive and finite
+/// gives an [`InvalidOptions`][NewtonMethodError::InvalidOptions] error.
+///
+/// ```
+/// use tool::{
+///     newton_method_finite_differences, List, NewtonMethodArguments, NewtonMethodOptions,
+///     FINITE_DIFFERENCE_STEP,
+/// };
+///
+/// struct Arguments;
+/// impl NewtonMethodArguments for Arguments {}
+///
+/// let solution = newton_method_finite_differences(
+///     List::from_element(1, 1.0),
+///     |values, _| values.map(|value| value.exp() - 2.0),
+///     Arguments,
+///     FINITE_DIFFERENCE_STEP,
+///     &NewtonMethodOptions::default(),
+/// )
+/// .unwrap();
+/// assert!((solution.values[0] - 2f64.ln()).abs() < 1e-10);
+/// ```
+pub fn newton_method_finite_differences<T, A>(
+    start_value: List<T>,
+    newton_method_function: impl Fn(&List<T>, &A) -> List<T>,
+    newton_method_arguments: A,
+    step: T,
+    options: &NewtonMethodOptions<T>,
+) -> Result<NewtonMethodSolution<T>, NewtonMethodError<T>>
+where
+    T: RealField,
+    A: NewtonMethodArguments,
+{
+    if step <= T::zero() || !step.is_finite() {
+        return Err(NewtonMethodError::InvalidOptions(
+            "the step of the finite differences must be positive and finite".to_string(),
+        ));
+    }
+    let derivative = |values: &List<T>, args: &A| {
+        let deltas = values.map(|value| step * value.abs().max(T::one()));
+        (newton_method_function(&(values + &deltas), args)
+            - newton_method_function(&(values - &deltas), args))
+        .component_div(&(deltas * (T::one() + T::one())))
+    };
+    newton_method_with_options(
+        start_value,
+        &newton_method_function,
+        derivative,
+        newton_method_arguments,
+        options,
+    )
+}
+
+/// [Newton's method][newton_method_with_options] with the derivative computed by forward-mode
+/// automatic differentiation, so that only the function is supplied.
+///
+/// ## Method
+///
+/// The function is written for [`Dual`] numbers, typically as a function generic over
+/// [`RealField`]. It is evaluated on [constants][Dual::constant] for its values, and on
+/// [variables][Dual::variable] for its derivatives, read in the [dual parts][Dual::dual]. As
+/// all the components are differentiated at once, each component of the function must only
+/// depend on the same component of the values, as for the other variants.
+///
+/// ```
+/// use nalgebra::{self as na, ComplexField};
+/// use tool::{newton_method_dual, List, NewtonMethodArguments, NewtonMethodOptions};
+///
+/// struct Arguments {
+///     target: f64,
+/// }
+/// impl NewtonMethodArguments for Arguments {}
+///
+/// let solution = newton_method_dual(
+///     List::from_element(1, 1.0),
+///     |values, args: &Arguments| values.map(|value| value.powi(3) - na::convert(args.target)),
+///     Arguments { target: 27.0 },
+///     &NewtonMethodOptions::default(),
+/// )
+/// .unwrap();
+/// assert!((solution.values[0] - 3.0f64).abs() < 1e-10);
+/// ```
+pub fn newton_method_dual<T, A>(
+    start_value: List<T>,
+    newton_method_function: impl Fn(&List<Dual<T>>, &A) -> List<Dual<T>>,
+    newton_method_arguments: A,
+    options: &NewtonMethodOptions<T>,
+) -> Result<NewtonMethodSolution<T>, NewtonMethodError<T>>
+where
+    T: RealField,
+    A: NewtonMethodArguments,
+{
+    newton_method_with_options(
+        start_value,
+        |values, args| {
+            newton_method_function(&values.map(Dual::constant), args).map(|value| value.real)
+        },
+        |values, args| {
+            newton_method_function(&values.map(Dual::variable), args).map(|value| value.dual)
+        },
+        newton_method_arguments,
+        options,
+    )
+}
+
+/// Values of the active components.
+fn gather<T>(values: &List<T>, active: &[usize]) -> List<T>
+where
+    T: RealField,
+{
+    List::from_iterator(active.len(), active.iter().map(|&index| values[index]))
+}
+
+/// Values where the active components are replaced.
+fn scatter<T>(values: &List<T>, active: &[usize], active_values: &List<T>) -> List<T>
+where
+    T: RealField,
+{
+    let mut values = values.clone();
+    for (&index, &value) in active.iter().zip(active_values.iter()) {
+        values[index] = value;
+    }
+    values
+}
+
+/// Evaluate a function of the Newton's method on the active components, with the
+/// [subset][NewtonMethodArguments::subset] of the arguments if available, and otherwise on all
+/// the components.
+fn evaluate<T, A>(
+    function: &impl Fn(&List<T>, &A) -> List<T>,
+    values: &List<T>,
+    active: &[usize],
+    arguments: &A,
+) -> List<T>
+where
+    T: RealField,
+    A: NewtonMethodArguments,
+{
+    if active.len() < values.len() {
+        if let Some(subset) = arguments.subset(active) {
+            return function(&gather(values, active), &subset);
+        }
+    }
+    gather(&function(values, arguments), active)
 }
 
 /// Trait to be added to your custom struc holding Newton's method arguments.
-pub trait NewtonMethodArguments {}
+///
+/// When the arguments hold values for each component, implementing
+/// [`subset`][NewtonMethodArguments::subset] lets the Newton's method evaluate the functions on
+/// the components that have not converged yet only.
+pub trait NewtonMethodArguments {
+    /// Arguments of some components only, in the order of their indices, or `None` to evaluate
+    /// the functions on all the components, by default.
+    fn subset(&self, _components: &[usize]) -> Option<Self>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+/// Sufficient decrease of the squared norm of the function required by the backtracking of the
+/// [multivariate Newton's method][newton_raphson], relative to the decrease of the full step.
+pub const ARMIJO_CONDITION: f64 = 1e-4;
+
+/// Solution of the [multivariate Newton's method][newton_raphson].
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewtonRaphsonSolution<T>
+where
+    T: RealField,
+{
+    /// Values of the solution.
+    pub values: DVector<T>,
+    /// Number of iterations performed.
+    pub iterations: usize,
+    /// Values of the function at the solution.
+    pub function_values: DVector<T>,
+    /// Last step, the difference between the solution and the previous values.
+    pub step: DVector<T>,
+}
+
+/// Error raised by the [multivariate Newton's method][newton_raphson].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NewtonRaphsonError<T>
+where
+    T: RealField,
+{
+    /// The method did not converge within the maximum number of iterations.
+    NotConverged {
+        /// Last values.
+        values: DVector<T>,
+        /// Norm of the function at the last values.
+        function_norm: T,
+        /// Norm of the last step.
+        step_norm: T,
+    },
+    /// The Jacobian matrix is singular.
+    SingularJacobian {
+        /// Iteration at which the Jacobian matrix is singular.
+        iteration: usize,
+        /// Values giving the singular Jacobian matrix.
+        values: DVector<T>,
+    },
+    /// The function, the Jacobian matrix or the step is not finite.
+    NonFinite {
+        /// Iteration at which the values are not finite.
+        iteration: usize,
+        /// Values giving the values not finite.
+        values: DVector<T>,
+    },
+    /// The backtracking did not find a step decreasing enough the norm of the function.
+    LineSearch {
+        /// Iteration at which the backtracking failed.
+        iteration: usize,
+        /// Values from which no step was found.
+        values: DVector<T>,
+        /// Norm of the function at these values.
+        function_norm: T,
+    },
+    /// The function or the Jacobian matrix has not the dimensions of the values.
+    DimensionMismatch {
+        /// Expected numbers of rows and columns.
+        expected: (usize, usize),
+        /// Numbers of rows and columns found.
+        found: (usize, usize),
+    },
+    /// Inconsistent options.
+    InvalidOptions(String),
+}
+
+impl<T> fmt::Display for NewtonRaphsonError<T>
+where
+    T: RealField,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NewtonRaphsonError::NotConverged {
+                function_norm,
+                step_norm,
+                ..
+            } => write!(
+                f,
+                "multivariate Newton's method did not converge, with a function of norm {} and a \
+                 last step of norm {}",
+                function_norm, step_norm
+            ),
+            NewtonRaphsonError::SingularJacobian { iteration, .. } => write!(
+                f,
+                "singular Jacobian matrix in multivariate Newton's method at iteration {}",
+                iteration
+            ),
+            NewtonRaphsonError::NonFinite { iteration, .. } => write!(
+                f,
+                "values not finite in multivariate Newton's method at iteration {}",
+                iteration
+            ),
+            NewtonRaphsonError::LineSearch {
+                iteration,
+                function_norm,
+                ..
+            } => write!(
+                f,
+                "no step decreasing the function of norm {} in multivariate Newton's method at \
+                 iteration {}",
+                function_norm, iteration
+            ),
+            NewtonRaphsonError::DimensionMismatch { expected, found } => write!(
+                f,
+                "dimensions {:?} instead of {:?} in multivariate Newton's method",
+                found, expected
+            ),
+            NewtonRaphsonError::InvalidOptions(message) => write!(
+                f,
+                "invalid options of multivariate Newton's method: {}",
+                message
+            ),
+        }
+    }
+}
+
+impl<T> std::error::Error for NewtonRaphsonError<T> where T: RealField {}
+
+/// Multivariate Newton's method, also known as Newton-Raphson method, for coupled systems of
+/// equations.
+///
+/// ## Expression
+///
+/// The root of a function $\bm{F}:\mathbb{R}^n\to\mathbb{R}^n$ is approached by solving the
+/// linear system of its Jacobian matrix $\bm{J}$ at each iteration,
+///
+/// $$\bm{J}\left(\bm{x}_k\right)\bm{p}_k=-\bm{F}\left(\bm{x}_k\right)\quad\quad
+/// \bm{x}_{k+1}=\bm{x}_k+\alpha_k\bm{p}_k$$
+///
+/// ## Method
+///
+/// The linear system is solved by LU decomposition. The step $\alpha_k$ is the damping of the
+/// [options][NewtonMethodOptions]. With the line search, it is halved, up to
+/// [`LINE_SEARCH_MAX_HALVINGS`] times, until the backtracking condition of Armijo holds,
+///
+/// $$\left\Vert\bm{F}\left(\bm{x}_{k+1}\right)\right\Vert^2\leq\left(1-2c\alpha_k\right)
+/// \left\Vert\bm{F}\left(\bm{x}_k\right)\right\Vert^2$$
+///
+/// with $c$ the [`ARMIJO_CONDITION`]. The iterates are clipped to the bounds with [`clip`]. The
+/// criteria of the options apply to the norms of the values, of the step and of the function.
+///
+/// ```
+/// use nalgebra::{DMatrix, DVector};
+/// use tool::{newton_raphson, NewtonMethodArguments, NewtonMethodOptions};
+///
+/// struct Arguments;
+/// impl NewtonMethodArguments for Arguments {}
+///
+/// // Intersection of the unit circle and the line y = x.
+/// let solution = newton_raphson(
+///     DVector::from_row_slice(&[1.0, 0.0]),
+///     |x, _| DVector::from_row_slice(&[x[0] * x[0] + x[1] * x[1] - 1.0, x[0] - x[1]]),
+///     |x, _| DMatrix::from_row_slice(2, 2, &[2.0 * x[0], 2.0 * x[1], 1.0, -1.0]),
+///     Arguments,
+///     &NewtonMethodOptions::default(),
+/// )
+/// .unwrap();
+/// assert!((solution.values[0] - 0.5f64.sqrt()).abs() < 1e-10);
+/// assert!((solution.values[1] - 0.5f64.sqrt()).abs() < 1e-10);
+/// ```
+pub fn newton_raphson<T, A>(
+    start_value: DVector<T>,
+    function: impl Fn(&DVector<T>, &A) -> DVector<T>,
+    jacobian: impl Fn(&DVector<T>, &A) -> DMatrix<T>,
+    arguments: A,
+    options: &NewtonMethodOptions<T>,
+) -> Result<NewtonRaphsonSolution<T>, NewtonRaphsonError<T>>
+where
+    T: RealField,
+    A: NewtonMethodArguments,
+{
+    options
+        .check()
+        .map_err(NewtonRaphsonError::InvalidOptions)?;
+    let size = start_value.len();
+    let bounded = |values: &DVector<T>| {
+        clip(
+            &values.transpose(),
+            options.lower_bound,
+            options.upper_bound,
+        )
+        .transpose()
+    };
+    let evaluate = |iteration: usize, values: &DVector<T>| {
+        let function_values = function(values, &arguments);
+        if function_values.len() != size {
+            return Err(NewtonRaphsonError::DimensionMismatch {
+                expected: (size, 1),
+                found: (function_values.len(), 1),
+            });
+        }
+        if function_values.iter().all(|value| value.is_finite()) {
+            Ok(function_values)
+        } else {
+            Err(NewtonRaphsonError::NonFinite {
+                iteration,
+                values: values.clone(),
+            })
+        }
+    };
+    let armijo: T = na::convert(ARMIJO_CONDITION);
+    let two = T::one() + T::one();
+
+    let mut values = bounded(&start_value);
+    let mut function_values = evaluate(1, &values)?;
+    let mut step = DVector::zeros(size);
+    for iteration in 1..=options.max_iterations {
+        let jacobian_matrix = jacobian(&values, &arguments);
+        if jacobian_matrix.shape() != (size, size) {
+            return Err(NewtonRaphsonError::DimensionMismatch {
+                expected: (size, size),
+                found: jacobian_matrix.shape(),
+            });
+        }
+        if jacobian_matrix.iter().any(|value| !value.is_finite()) {
+            return Err(NewtonRaphsonError::NonFinite { iteration, values });
+        }
+        let direction = match jacobian_matrix.lu().solve(&-&function_values) {
+            Some(direction) => direction,
+            None => return Err(NewtonRaphsonError::SingularJacobian { iteration, values }),
+        };
+        if direction.iter().any(|value| !value.is_finite()) {
+            return Err(NewtonRaphsonError::NonFinite { iteration, values });
+        }
+
+        let squared_norm = function_values.norm_squared();
+        let mut factor = options.damping;
+        let mut new_values = bounded(&(&values + &direction * factor));
+        let mut new_function_values = evaluate(iteration, &new_values);
+        if options.line_search {
+            let mut halvings = 0;
+            while !new_function_values
+                .as_ref()
+                .is_ok_and(|new_function_values| {
+                    new_function_values.norm_squared()
+                        <= (T::one() - two * armijo * factor) * squared_norm
+                })
+            {
+                if halvings == LINE_SEARCH_MAX_HALVINGS {
+                    return Err(NewtonRaphsonError::LineSearch {
+                        iteration,
+                        values,
+                        function_norm: squared_norm.sqrt(),
+                    });
+                }
+                halvings += 1;
+                factor /= two;
+                new_values = bounded(&(&values + &direction * factor));
+                new_function_values = evaluate(iteration, &new_values);
+            }
+        }
+
+        step = &new_values - &values;
+        values = new_values;
+        function_values = new_function_values?;
+        if options.converged(values.norm(), step.norm(), function_values.norm()) {
+            return Ok(NewtonRaphsonSolution {
+                values,
+                iterations: iteration,
+                function_values,
+                step,
+            });
+        }
+    }
+
+    Err(NewtonRaphsonError::NotConverged {
+        function_norm: function_values.norm(),
+        step_norm: step.norm(),
+        values,
+    })
+}
+
+/// [Multivariate Newton's method][newton_raphson] with the Jacobian matrix approximated by
+/// finite differences, so that only the function is supplied.
+///
+/// ## Expression
+///
+/// Each column of the Jacobian matrix is approximated by central differences along one value,
+/// of step $h$ relative to the value,
+///
+/// $$\frac{\partial\bm{F}}{\partial x_j}\approx\frac{\bm{F}\left(\bm{x}+\delta_j\bm{e}_j\right)
+/// -\bm{F}\left(\bm{x}-\delta_j\bm{e}_j\right)}{2\delta_j}\quad\text{with}\quad
+/// \delta_j=h\max\left(1,\left|x_j\right|\right)$$
+///
+/// The step is typically [`FINITE_DIFFERENCE_STEP`]. A step not strictly positive and finite
+/// gives an [`InvalidOptions`][NewtonRaphsonError::InvalidOptions] error.
+pub fn newton_raphson_finite_differences<T, A>(
+    start_value: DVector<T>,
+    function: impl Fn(&DVector<T>, &A) -> DVector<T>,
+    arguments: A,
+    step: T,
+    options: &NewtonMethodOptions<T>,
+) -> Result<NewtonRaphsonSolution<T>, NewtonRaphsonError<T>>
+where
+    T: RealField,
+    A: NewtonMethodArguments,
+{
+    if step <= T::zero() || !step.is_finite() {
+        return Err(NewtonRaphsonError::InvalidOptions(
+            "the step of the finite differences must be positive and finite".to_string(),
+        ));
+    }
+    let jacobian = |values: &DVector<T>, args: &A| {
+        let mut columns = Vec::with_capacity(values.len());
+        for index in 0..values.len() {
+            let delta = step * values[index].abs().max(T::one());
+            let mut forward = values.clone();
+            forward[index] += delta;
+            let mut backward = values.clone();
+            backward[index] -= delta;
+            columns.push((function(&forward, args) - function(&backward, args)) / (delta + delta));
+        }
+        DMatrix::from_columns(&columns)
+    };
+    newton_raphson(start_value, &function, jacobian, arguments, options)
+}
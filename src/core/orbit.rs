@@ -0,0 +1,302 @@
+use crate::{
+    direct_angle, rotation_from_euler, solve_kepler_list, EulerSequence, KeplerError, List, Vector,
+    TAU,
+};
+use std::fmt;
+
+/// Distance of the eccentricity to 1 below which an orbit is treated as parabolic.
+pub const PARABOLIC_TOLERANCE: f64 = 1e-12;
+
+/// Whether an orbit of the eccentricity is treated as parabolic, see [`PARABOLIC_TOLERANCE`].
+pub fn is_parabolic(eccentricity: f64) -> bool {
+    (eccentricity - 1.0).abs() <= PARABOLIC_TOLERANCE
+}
+
+/// Error raised by the conversions of [`OrbitalElements`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrbitError {
+    /// The orbit is parabolic, its semi-major axis is infinite and does not give its size.
+    ParabolicOrbit,
+    /// Kepler's equation could not be solved for the mean anomaly.
+    Kepler(KeplerError),
+}
+
+impl fmt::Display for OrbitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrbitError::ParabolicOrbit => {
+                write!(
+                    f,
+                    "parabolic orbits are not described by their semi-major axis"
+                )
+            }
+            OrbitError::Kepler(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for OrbitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            OrbitError::Kepler(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<KeplerError> for OrbitError {
+    fn from(error: KeplerError) -> Self {
+        OrbitError::Kepler(error)
+    }
+}
+
+/// Keplerian elements of an orbit.
+///
+/// The angles are in radians and the semi-major axis is negative for hyperbolic orbits. The
+/// anomaly conversions support parabolic orbits, but their semi-major axis is infinite and does
+/// not give the size of the orbit, so [`OrbitalElements::to_state`] returns an error for them.
+/// The orbits whose eccentricity is within [`PARABOLIC_TOLERANCE`] of 1 are parabolic.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OrbitalElements {
+    /// Semi-major axis $a$.
+    pub semi_major_axis: f64,
+    /// Eccentricity $e$.
+    pub eccentricity: f64,
+    /// Inclination $i$.
+    pub inclination: f64,
+    /// Longitude of the ascending node $\Omega$.
+    pub longitude_ascending_node: f64,
+    /// Argument of the periapsis $\omega$.
+    pub argument_periapsis: f64,
+    /// Mean anomaly $M$.
+    pub mean_anomaly: f64,
+}
+
+impl OrbitalElements {
+    /// Create the elements from the true anomaly $\nu$ instead of the mean anomaly, or return an
+    /// error if the eccentricity is negative or not finite.
+    pub fn from_true_anomaly(
+        semi_major_axis: f64,
+        eccentricity: f64,
+        inclination: f64,
+        longitude_ascending_node: f64,
+        argument_periapsis: f64,
+        true_anomaly: f64,
+    ) -> Result<Self, KeplerError> {
+        if !eccentricity.is_finite() || eccentricity < 0.0 {
+            return Err(KeplerError::InvalidEccentricity(eccentricity));
+        }
+        Ok(Self {
+            semi_major_axis,
+            eccentricity,
+            inclination,
+            longitude_ascending_node,
+            argument_periapsis,
+            mean_anomaly: true_to_mean_anomaly(&List::from_element(1, true_anomaly), eccentricity)
+                [0],
+        })
+    }
+
+    /// True anomaly $\nu$, or an error if Kepler's equation cannot be solved for the elements.
+    pub fn true_anomaly(&self) -> Result<f64, KeplerError> {
+        Ok(mean_to_true_anomaly(&List::from_element(1, self.mean_anomaly), self.eccentricity)?[0])
+    }
+
+    /// Mean motion $n=\sqrt{\mu/\left|a\right|^3}$, for the standard gravitational parameter
+    /// $\mu$.
+    pub fn mean_motion(&self, gm: f64) -> f64 {
+        (gm / self.semi_major_axis.abs().powi(3)).sqrt()
+    }
+
+    /// Orbital period $2\pi/n$, for elliptic orbits.
+    pub fn period(&self, gm: f64) -> f64 {
+        TAU / self.mean_motion(gm)
+    }
+
+    /// Semi-latus rectum $p=a\left(1-e^2\right)$, not defined for parabolic orbits.
+    pub fn semi_latus_rectum(&self) -> f64 {
+        self.semi_major_axis * (1.0 - self.eccentricity.powi(2))
+    }
+
+    /// Position and velocity [`Vector`]s of the orbiting body, for the standard gravitational
+    /// parameter $\mu$.
+    ///
+    /// ## Expression
+    ///
+    /// In the perifocal frame, with the x axis towards the periapsis,
+    ///
+    /// $$\bm{r}=\frac{p}{1+e\cos\nu}\left(\cos\nu, \sin\nu, 0\right)$$
+    /// $$\bm{v}=\sqrt{\frac{\mu}{p}}\left(-\sin\nu, e+\cos\nu, 0\right)$$
+    ///
+    /// The vectors are then rotated by the [Euler sequence][EulerSequence::ZXZ]
+    /// $\left(\Omega, i, \omega\right)$. Parabolic orbits return an error, as well as elements
+    /// for which Kepler's equation cannot be solved.
+    pub fn to_state(&self, gm: f64) -> Result<(Vector<f64>, Vector<f64>), OrbitError> {
+        if is_parabolic(self.eccentricity) {
+            return Err(OrbitError::ParabolicOrbit);
+        }
+        let true_anomaly = self.true_anomaly()?;
+        let p = self.semi_latus_rectum();
+        let (sin, cos) = true_anomaly.sin_cos();
+        let radius = p / (1.0 + self.eccentricity * cos);
+        let position = Vector::new(radius * cos, radius * sin, 0.0);
+        let velocity = Vector::new(-sin, self.eccentricity + cos, 0.0) * (gm / p).sqrt();
+        let rotation = rotation_from_euler(
+            EulerSequence::ZXZ,
+            &Vector::new(
+                self.longitude_ascending_node,
+                self.inclination,
+                self.argument_periapsis,
+            ),
+        );
+        Ok((rotation * position, rotation * velocity))
+    }
+
+    /// Elements of the orbit from the position and velocity [`Vector`]s of the orbiting body, for
+    /// the standard gravitational parameter $\mu$.
+    ///
+    /// ## Expression
+    ///
+    /// $$\bm{h}=\bm{r}\times\bm{v}$$
+    /// $$\bm{e}=\frac{\bm{v}\times\bm{h}}{\mu}-\frac{\bm{r}}{\left\Vert\bm{r}\right\Vert}$$
+    /// $$a=\left(\frac{2}{\left\Vert\bm{r}\right\Vert}-\frac{\left\Vert\bm{v}\right\Vert^2}{\mu}\right)^{-1}$$
+    ///
+    /// The angles are [direct angles][direct_angle] around $\bm{h}$ from the ascending node
+    /// $\bm{z}\times\bm{h}$. For equatorial orbits the ascending node is taken along the x axis,
+    /// and for circular orbits the periapsis is taken at the ascending node. States that do not give
+    /// a finite eccentricity return an error.
+    pub fn from_state(
+        position: &Vector<f64>,
+        velocity: &Vector<f64>,
+        gm: f64,
+    ) -> Result<Self, KeplerError> {
+        let threshold = 1e-11;
+        let momentum = position.cross(velocity);
+        let eccentricity_vector = velocity.cross(&momentum) / gm - position.normalize();
+        let eccentricity = eccentricity_vector.norm();
+        let semi_major_axis = 1.0 / (2.0 / position.norm() - velocity.norm_squared() / gm);
+        let inclination = (momentum[2] / momentum.norm()).clamp(-1.0, 1.0).acos();
+
+        let mut node = Vector::z().cross(&momentum);
+        if node.norm() <= threshold * momentum.norm() {
+            node = Vector::x();
+        }
+        let periapsis = if eccentricity <= threshold {
+            node
+        } else {
+            eccentricity_vector
+        };
+        let longitude_ascending_node = node[1].atan2(node[0]).rem_euclid(TAU);
+        let argument_periapsis = if eccentricity <= threshold {
+            0.0
+        } else {
+            direct_angle(&node, &periapsis, &momentum) % TAU
+        };
+        let mut true_anomaly = direct_angle(&periapsis, position, &momentum) % TAU;
+        // Hyperbolic orbits have true anomalies between the two asymptotes.
+        if eccentricity >= 1.0 && true_anomaly > std::f64::consts::PI {
+            true_anomaly -= TAU;
+        }
+
+        Self::from_true_anomaly(
+            semi_major_axis,
+            eccentricity,
+            inclination,
+            longitude_ascending_node,
+            argument_periapsis,
+            true_anomaly,
+        )
+    }
+}
+
+/// Convert mean anomalies to eccentric anomalies, or to parabolic and hyperbolic anomalies for
+/// parabolic and hyperbolic orbits, by solving Kepler's equation with [`solve_kepler_list`].
+///
+/// ## Expression
+///
+/// $$M=E-e\sin E$$
+/// $$M=D+\frac{D^3}{3}$$
+/// $$M=e\sinh H-H$$
+///
+/// for elliptic, parabolic and hyperbolic orbits respectively.
+///
+/// Returns the error of [`solve_kepler_list`] if the eccentricity or the mean anomalies are not
+/// valid.
+pub fn mean_to_eccentric_anomaly(
+    mean_anomalies: &List<f64>,
+    eccentricity: f64,
+) -> Result<List<f64>, KeplerError> {
+    solve_kepler_list(mean_anomalies, eccentricity)
+}
+
+/// Convert eccentric anomalies, or parabolic and hyperbolic anomalies for parabolic and
+/// hyperbolic orbits, to mean anomalies.
+pub fn eccentric_to_mean_anomaly(eccentric_anomalies: &List<f64>, eccentricity: f64) -> List<f64> {
+    if is_parabolic(eccentricity) {
+        eccentric_anomalies.map(|anomaly| anomaly + anomaly.powi(3) / 3.0)
+    } else if eccentricity < 1.0 {
+        eccentric_anomalies.map(|anomaly| anomaly - eccentricity * anomaly.sin())
+    } else {
+        eccentric_anomalies.map(|anomaly| eccentricity * anomaly.sinh() - anomaly)
+    }
+}
+
+/// Convert eccentric anomalies, or parabolic and hyperbolic anomalies for parabolic and
+/// hyperbolic orbits, to true anomalies.
+///
+/// ## Expression
+///
+/// $$\tan\frac{\nu}{2}=\sqrt{\frac{1+e}{1-e}}\tan\frac{E}{2}$$
+/// $$\tan\frac{\nu}{2}=D$$
+/// $$\tan\frac{\nu}{2}=\sqrt{\frac{e+1}{e-1}}\tanh\frac{H}{2}$$
+pub fn eccentric_to_true_anomaly(eccentric_anomalies: &List<f64>, eccentricity: f64) -> List<f64> {
+    if is_parabolic(eccentricity) {
+        eccentric_anomalies.map(|anomaly| 2.0 * anomaly.atan())
+    } else if eccentricity < 1.0 {
+        let factor = ((1.0 + eccentricity) / (1.0 - eccentricity)).sqrt();
+        eccentric_anomalies.map(|anomaly| {
+            // The revolutions are kept to return a continuous anomaly.
+            let revolutions = (anomaly / TAU).round() * TAU;
+            revolutions + 2.0 * (factor * ((anomaly - revolutions) / 2.0).tan()).atan()
+        })
+    } else {
+        let factor = ((eccentricity + 1.0) / (eccentricity - 1.0)).sqrt();
+        eccentric_anomalies.map(|anomaly| 2.0 * (factor * (anomaly / 2.0).tanh()).atan())
+    }
+}
+
+/// Convert true anomalies to eccentric anomalies, or parabolic and hyperbolic anomalies for
+/// parabolic and hyperbolic orbits.
+pub fn true_to_eccentric_anomaly(true_anomalies: &List<f64>, eccentricity: f64) -> List<f64> {
+    if is_parabolic(eccentricity) {
+        true_anomalies.map(|anomaly| (anomaly / 2.0).tan())
+    } else if eccentricity < 1.0 {
+        let factor = ((1.0 - eccentricity) / (1.0 + eccentricity)).sqrt();
+        true_anomalies.map(|anomaly| {
+            let revolutions = (anomaly / TAU).round() * TAU;
+            revolutions + 2.0 * (factor * ((anomaly - revolutions) / 2.0).tan()).atan()
+        })
+    } else {
+        let factor = ((eccentricity - 1.0) / (eccentricity + 1.0)).sqrt();
+        true_anomalies.map(|anomaly| 2.0 * (factor * (anomaly / 2.0).tan()).atanh())
+    }
+}
+
+/// Convert mean anomalies to true anomalies, see [`mean_to_eccentric_anomaly`] for the errors.
+pub fn mean_to_true_anomaly(
+    mean_anomalies: &List<f64>,
+    eccentricity: f64,
+) -> Result<List<f64>, KeplerError> {
+    Ok(eccentric_to_true_anomaly(
+        &mean_to_eccentric_anomaly(mean_anomalies, eccentricity)?,
+        eccentricity,
+    ))
+}
+
+/// Convert true anomalies to mean anomalies.
+pub fn true_to_mean_anomaly(true_anomalies: &List<f64>, eccentricity: f64) -> List<f64> {
+    eccentric_to_mean_anomaly(
+        &true_to_eccentric_anomaly(true_anomalies, eccentricity),
+        eccentricity,
+    )
+}
@@ -0,0 +1,515 @@
+use crate::{
+    List, Vector, ASTRONAUMICAL_UNIT, DAY, DEG2RAD, HOUR, MINUTE, SOLAR_LUMINOSITY, SOLAR_RADIUS,
+    TAU, YEAR,
+};
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Physical dimension of a [`Quantity`], identified by its SI unit.
+pub trait Dimension {
+    /// Symbol of the SI unit.
+    const SYMBOL: &'static str;
+}
+
+/// Dimension of lengths, in meters.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Meter;
+/// Dimension of durations, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Second;
+/// Dimension of angles, in radians.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Radian;
+/// Dimension of masses, in kilograms.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Kilogram;
+/// Dimension of temperatures, in kelvins.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Kelvin;
+/// Dimension of powers, in watts.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Watt;
+/// Dimension of areas, in square meters.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct SquareMeter;
+/// Dimension of velocities, in meters per second.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MeterPerSecond;
+/// Dimension of accelerations, in meters per second squared.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct MeterPerSecondSquared;
+/// Dimension of angular velocities, in radians per second.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct RadianPerSecond;
+/// Dimension of energies, in joules.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Joule;
+/// Dimension of irradiances and flux densities, in watts per square meter.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct WattPerSquareMeter;
+
+impl Dimension for Meter {
+    const SYMBOL: &'static str = "m";
+}
+impl Dimension for Second {
+    const SYMBOL: &'static str = "s";
+}
+impl Dimension for Radian {
+    const SYMBOL: &'static str = "rad";
+}
+impl Dimension for Kilogram {
+    const SYMBOL: &'static str = "kg";
+}
+impl Dimension for Kelvin {
+    const SYMBOL: &'static str = "K";
+}
+impl Dimension for Watt {
+    const SYMBOL: &'static str = "W";
+}
+impl Dimension for SquareMeter {
+    const SYMBOL: &'static str = "m²";
+}
+impl Dimension for MeterPerSecond {
+    const SYMBOL: &'static str = "m/s";
+}
+impl Dimension for MeterPerSecondSquared {
+    const SYMBOL: &'static str = "m/s²";
+}
+impl Dimension for RadianPerSecond {
+    const SYMBOL: &'static str = "rad/s";
+}
+impl Dimension for Joule {
+    const SYMBOL: &'static str = "J";
+}
+impl Dimension for WattPerSquareMeter {
+    const SYMBOL: &'static str = "W/m²";
+}
+
+/// Value with a physical dimension, stored in the SI unit of the dimension.
+///
+/// The value can be a scalar, a [`List`] or a [`Vector`], so that a whole array carries a unit.
+/// Quantities of the same dimension can be added, subtracted and compared, and scaled by numbers.
+/// Quantities of different dimensions are multiplied and divided by scalar quantities when the
+/// result has one of the derived dimensions: [`Area`], [`Velocity`], [`Acceleration`],
+/// [`AngularVelocity`], [`Energy`] and [`Irradiance`]. Any other mix of dimensions does not
+/// compile, and values in other units are created and read through explicit conversions.
+///
+/// ```
+/// use tool::{Length, Time, Velocity};
+///
+/// let distance = Length::from_kilometers(1.5) + Length::from_meters(500.0);
+/// assert_eq!(distance.kilometers(), 2.0);
+/// assert_eq!(Time::HOUR.minutes(), 60.0);
+///
+/// let speed: Velocity = distance / Time::from_seconds(4.0);
+/// assert_eq!(speed.meters_per_second(), 500.0);
+/// assert_eq!((speed * Time::MINUTE).kilometers(), 30.0);
+/// ```
+///
+/// ```compile_fail
+/// use tool::{Length, Time};
+///
+/// let sum = Length::from_meters(1.0) + Time::from_seconds(1.0);
+/// ```
+///
+/// ```compile_fail
+/// use tool::{Length, Mass, Velocity};
+///
+/// let speed: Velocity = Length::from_meters(1.0) / Mass::from_kilograms(1.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Quantity<T, D> {
+    value: T,
+    dimension: PhantomData<D>,
+}
+
+/// Length, in meters.
+pub type Length<T = f64> = Quantity<T, Meter>;
+/// Duration, in seconds.
+pub type Time<T = f64> = Quantity<T, Second>;
+/// Angle, in radians.
+pub type Angle<T = f64> = Quantity<T, Radian>;
+/// Mass, in kilograms.
+pub type Mass<T = f64> = Quantity<T, Kilogram>;
+/// Thermodynamic temperature, in kelvins.
+pub type Temperature<T = f64> = Quantity<T, Kelvin>;
+/// Power, in watts.
+pub type Power<T = f64> = Quantity<T, Watt>;
+/// Area, in square meters.
+pub type Area<T = f64> = Quantity<T, SquareMeter>;
+/// Velocity, in meters per second.
+pub type Velocity<T = f64> = Quantity<T, MeterPerSecond>;
+/// Acceleration, in meters per second squared.
+pub type Acceleration<T = f64> = Quantity<T, MeterPerSecondSquared>;
+/// Angular velocity, in radians per second.
+pub type AngularVelocity<T = f64> = Quantity<T, RadianPerSecond>;
+/// Energy, in joules.
+pub type Energy<T = f64> = Quantity<T, Joule>;
+/// Irradiance or flux density, in watts per square meter.
+pub type Irradiance<T = f64> = Quantity<T, WattPerSquareMeter>;
+
+impl<T, D> Quantity<T, D> {
+    /// Create a quantity from its value in the SI unit of the dimension.
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            dimension: PhantomData,
+        }
+    }
+
+    /// Reference to the value in the SI unit of the dimension.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Value in the SI unit of the dimension.
+    pub fn into_value(self) -> T {
+        self.value
+    }
+
+    /// Apply a function to the value, keeping the dimension.
+    pub fn map<V>(self, function: impl FnOnce(T) -> V) -> Quantity<V, D> {
+        Quantity::new(function(self.value))
+    }
+}
+
+impl<D> Quantity<List<f64>, D> {
+    /// Number of elements of the list.
+    pub fn len(&self) -> usize {
+        self.value.len()
+    }
+
+    /// Whether the list is empty.
+    pub fn is_empty(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    /// Element of the list at an index.
+    pub fn at(&self, index: usize) -> Quantity<f64, D> {
+        Quantity::new(self.value[index])
+    }
+
+    /// Iterator over the elements of the list.
+    pub fn iter(&self) -> impl Iterator<Item = Quantity<f64, D>> + '_ {
+        self.value.iter().map(|&value| Quantity::new(value))
+    }
+}
+
+impl<D> Quantity<Vector<f64>, D> {
+    /// Component of the vector at an index.
+    pub fn at(&self, index: usize) -> Quantity<f64, D> {
+        Quantity::new(self.value[index])
+    }
+
+    /// Norm of the vector.
+    pub fn norm(&self) -> Quantity<f64, D> {
+        Quantity::new(self.value.norm())
+    }
+}
+
+/// Conversions of a dimension from and to units, defined by their value in the SI unit.
+macro_rules! conversions {
+    ($dimension:ty, $(($from:ident, $to:ident, $factor:expr, $unit:literal)),* $(,)?) => {
+        impl<T> Quantity<T, $dimension>
+        where
+            T: Clone + Mul<f64, Output = T> + Div<f64, Output = T>,
+        {
+            $(
+                #[doc = concat!("Create the quantity from a value in ", $unit, ".")]
+                pub fn $from(value: T) -> Self {
+                    Self::new(value * $factor)
+                }
+
+                #[doc = concat!("Value of the quantity in ", $unit, ".")]
+                pub fn $to(&self) -> T {
+                    self.value.clone() / $factor
+                }
+            )*
+        }
+    };
+}
+
+conversions!(
+    Meter,
+    (from_meters, meters, 1.0, "meters"),
+    (from_kilometers, kilometers, 1e3, "kilometers"),
+    (
+        from_astronomical_units,
+        astronomical_units,
+        ASTRONAUMICAL_UNIT,
+        "astronomical units"
+    ),
+);
+conversions!(
+    Second,
+    (from_seconds, seconds, 1.0, "seconds"),
+    (from_minutes, minutes, MINUTE, "minutes"),
+    (from_hours, hours, HOUR, "hours"),
+    (from_days, days, DAY, "days"),
+    (from_years, years, YEAR, "Julian years"),
+);
+conversions!(
+    Radian,
+    (from_radians, radians, 1.0, "radians"),
+    (from_degrees, degrees, DEG2RAD, "degrees"),
+);
+conversions!(
+    Kilogram,
+    (from_kilograms, kilograms, 1.0, "kilograms"),
+    (from_grams, grams, 1e-3, "grams"),
+);
+conversions!(Kelvin, (from_kelvins, kelvins, 1.0, "kelvins"));
+conversions!(
+    Watt,
+    (from_watts, watts, 1.0, "watts"),
+    (from_kilowatts, kilowatts, 1e3, "kilowatts"),
+);
+conversions!(
+    SquareMeter,
+    (from_square_meters, square_meters, 1.0, "square meters"),
+    (
+        from_square_kilometers,
+        square_kilometers,
+        1e6,
+        "square kilometers"
+    ),
+);
+conversions!(
+    MeterPerSecond,
+    (
+        from_meters_per_second,
+        meters_per_second,
+        1.0,
+        "meters per second"
+    ),
+    (
+        from_kilometers_per_second,
+        kilometers_per_second,
+        1e3,
+        "kilometers per second"
+    ),
+);
+conversions!(
+    MeterPerSecondSquared,
+    (
+        from_meters_per_second_squared,
+        meters_per_second_squared,
+        1.0,
+        "meters per second squared"
+    ),
+);
+conversions!(
+    RadianPerSecond,
+    (
+        from_radians_per_second,
+        radians_per_second,
+        1.0,
+        "radians per second"
+    ),
+    (
+        from_degrees_per_day,
+        degrees_per_day,
+        DEG2RAD / DAY,
+        "degrees per day"
+    ),
+);
+conversions!(Joule, (from_joules, joules, 1.0, "joules"));
+conversions!(
+    WattPerSquareMeter,
+    (
+        from_watts_per_square_meter,
+        watts_per_square_meter,
+        1.0,
+        "watts per square meter"
+    ),
+);
+
+impl Length {
+    /// Typed [`ASTRONAUMICAL_UNIT`].
+    pub const ASTRONOMICAL_UNIT: Self = Self::new(ASTRONAUMICAL_UNIT);
+    /// Typed [`SOLAR_RADIUS`].
+    pub const SOLAR_RADIUS: Self = Self::new(SOLAR_RADIUS);
+}
+
+impl Time {
+    /// Typed [`MINUTE`].
+    pub const MINUTE: Self = Self::new(MINUTE);
+    /// Typed [`HOUR`].
+    pub const HOUR: Self = Self::new(HOUR);
+    /// Typed [`DAY`].
+    pub const DAY: Self = Self::new(DAY);
+    /// Typed [`YEAR`].
+    pub const YEAR: Self = Self::new(YEAR);
+}
+
+impl Angle {
+    /// Typed [`TAU`], the full turn.
+    pub const TURN: Self = Self::new(TAU);
+    /// Typed [`DEG2RAD`], one degree.
+    pub const DEGREE: Self = Self::new(DEG2RAD);
+
+    /// Sine of the angle.
+    pub fn sin(&self) -> f64 {
+        self.value.sin()
+    }
+
+    /// Cosine of the angle.
+    pub fn cos(&self) -> f64 {
+        self.value.cos()
+    }
+
+    /// Tangent of the angle.
+    pub fn tan(&self) -> f64 {
+        self.value.tan()
+    }
+}
+
+impl Power {
+    /// Typed [`SOLAR_LUMINOSITY`].
+    pub const SOLAR_LUMINOSITY: Self = Self::new(SOLAR_LUMINOSITY);
+}
+
+impl Temperature {
+    /// Create the temperature from a value in degrees Celsius.
+    pub fn from_celsius(value: f64) -> Self {
+        Self::new(value + 273.15)
+    }
+
+    /// Value of the temperature in degrees Celsius.
+    pub fn celsius(&self) -> f64 {
+        self.value - 273.15
+    }
+}
+
+impl<T: Add<Output = T>, D> Add for Quantity<T, D> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.value + other.value)
+    }
+}
+
+impl<T: Sub<Output = T>, D> Sub for Quantity<T, D> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.value - other.value)
+    }
+}
+
+impl<T: AddAssign, D> AddAssign for Quantity<T, D> {
+    fn add_assign(&mut self, other: Self) {
+        self.value += other.value;
+    }
+}
+
+impl<T: SubAssign, D> SubAssign for Quantity<T, D> {
+    fn sub_assign(&mut self, other: Self) {
+        self.value -= other.value;
+    }
+}
+
+impl<T: Neg<Output = T>, D> Neg for Quantity<T, D> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.value)
+    }
+}
+
+impl<T: Mul<f64, Output = T>, D> Mul<f64> for Quantity<T, D> {
+    type Output = Self;
+
+    fn mul(self, factor: f64) -> Self {
+        Self::new(self.value * factor)
+    }
+}
+
+impl<T: Div<f64, Output = T>, D> Div<f64> for Quantity<T, D> {
+    type Output = Self;
+
+    fn div(self, divisor: f64) -> Self {
+        Self::new(self.value / divisor)
+    }
+}
+
+/// Ratio of two quantities of the same dimension.
+impl<D> Div for Quantity<f64, D> {
+    type Output = f64;
+
+    fn div(self, other: Self) -> f64 {
+        self.value / other.value
+    }
+}
+
+/// Product and quotients of the dimensions of scalar quantities: the first dimension times the
+/// second gives the third, and the third divided by the second gives the first. The left operand
+/// can be a scalar, a [`List`] or a [`Vector`].
+macro_rules! derived_dimension {
+    ($($first:ty, $second:ty => $product:ty);* $(;)?) => {
+        $(
+            impl<T> Mul<Quantity<f64, $second>> for Quantity<T, $first>
+            where
+                T: Mul<f64, Output = T>,
+            {
+                type Output = Quantity<T, $product>;
+
+                fn mul(self, other: Quantity<f64, $second>) -> Self::Output {
+                    Quantity::new(self.value * other.value)
+                }
+            }
+
+            impl<T> Div<Quantity<f64, $second>> for Quantity<T, $product>
+            where
+                T: Div<f64, Output = T>,
+            {
+                type Output = Quantity<T, $first>;
+
+                fn div(self, other: Quantity<f64, $second>) -> Self::Output {
+                    Quantity::new(self.value / other.value)
+                }
+            }
+        )*
+    };
+}
+
+derived_dimension!(
+    Meter, Meter => SquareMeter;
+    MeterPerSecond, Second => Meter;
+    Second, MeterPerSecond => Meter;
+    MeterPerSecondSquared, Second => MeterPerSecond;
+    Second, MeterPerSecondSquared => MeterPerSecond;
+    RadianPerSecond, Second => Radian;
+    Second, RadianPerSecond => Radian;
+    Watt, Second => Joule;
+    Second, Watt => Joule;
+    WattPerSquareMeter, SquareMeter => Watt;
+    SquareMeter, WattPerSquareMeter => Watt;
+);
+
+/// Give the unit of the quantity to all the elements of a list.
+impl<D> Mul<List<f64>> for Quantity<f64, D> {
+    type Output = Quantity<List<f64>, D>;
+
+    fn mul(self, list: List<f64>) -> Self::Output {
+        Quantity::new(list * self.value)
+    }
+}
+
+/// Give the unit of the quantity to all the components of a vector.
+impl<D> Mul<Vector<f64>> for Quantity<f64, D> {
+    type Output = Quantity<Vector<f64>, D>;
+
+    fn mul(self, vector: Vector<f64>) -> Self::Output {
+        Quantity::new(vector * self.value)
+    }
+}
+
+/// Format the value followed by the symbol of the SI unit.
+impl<T: fmt::Display, D: Dimension> fmt::Display for Quantity<T, D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt(f)?;
+        write!(f, " {}", D::SYMBOL)
+    }
+}
@@ -0,0 +1,402 @@
+use crate::{Mesh, Vector, Vectors};
+use itertools::multizip;
+use na::RealField;
+
+/// Intersection of a ray with a face.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Hit<T>
+where
+    T: RealField,
+{
+    /// Distance from the origin of the ray to the intersection, in units of the length of the
+    /// direction of the ray.
+    pub distance: T,
+    /// Index of the face.
+    pub face: usize,
+}
+
+/// Intersection of a ray with a triangle, with the
+/// [Möller–Trumbore algorithm](https://en.wikipedia.org/wiki/M%C3%B6ller%E2%80%93Trumbore_intersection_algorithm).
+///
+/// The triangle is two-sided and the ray starts at `origin`. The distance to the intersection is
+/// returned in units of the length of `direction`, and only if it is positive. A ray parallel to
+/// the plane of the triangle does not intersect it.
+///
+/// ## Expression
+///
+/// The intersection $\bm{o}+t\bm{d}=\left(1-u-v\right)\bm{v}_0+u\bm{v}_1+v\bm{v}_2$ is solved
+/// with Cramer's rule,
+///
+/// $$t=\frac{\left(\bm{s}\times\bm{e}_1\right)\cdot\bm{e}_2}{\left(\bm{d}\times\bm{e}_2\right)\cdot\bm{e}_1}$$
+///
+/// where $\bm{e}_1=\bm{v}_1-\bm{v}_0$, $\bm{e}_2=\bm{v}_2-\bm{v}_0$ and $\bm{s}=\bm{o}-\bm{v}_0$.
+/// The intersection is inside the triangle for $u\geq0$, $v\geq0$ and $u+v\leq1$.
+pub fn ray_triangle_intersection<T>(
+    origin: &Vector<T>,
+    direction: &Vector<T>,
+    vertices: &[Vector<T>; 3],
+) -> Option<T>
+where
+    T: RealField,
+{
+    let edge_1 = vertices[1] - vertices[0];
+    let edge_2 = vertices[2] - vertices[0];
+    let p = direction.cross(&edge_2);
+    let determinant = edge_1.dot(&p);
+    if determinant.abs() <= T::default_epsilon() * edge_1.norm() * p.norm() {
+        return None;
+    }
+    let inverse_determinant = T::one() / determinant;
+    let s = origin - vertices[0];
+    let u = s.dot(&p) * inverse_determinant;
+    if u < T::zero() || u > T::one() {
+        return None;
+    }
+    let q = s.cross(&edge_1);
+    let v = direction.dot(&q) * inverse_determinant;
+    if v < T::zero() || u + v > T::one() {
+        return None;
+    }
+    let distance = edge_2.dot(&q) * inverse_determinant;
+    if distance > T::zero() {
+        Some(distance)
+    } else {
+        None
+    }
+}
+
+/// Axis-aligned bounding box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct BoundingBox<T>
+where
+    T: RealField,
+{
+    min: Vector<T>,
+    max: Vector<T>,
+}
+
+impl<T> BoundingBox<T>
+where
+    T: RealField,
+{
+    /// Empty box.
+    fn empty() -> Self {
+        Self {
+            min: Vector::repeat(T::max_value()),
+            max: Vector::repeat(T::min_value()),
+        }
+    }
+
+    /// Grow the box to include the point.
+    fn grow(&mut self, point: &Vector<T>) {
+        self.min = self.min.inf(point);
+        self.max = self.max.sup(point);
+    }
+
+    /// Grow the box to include another box.
+    fn merge(&mut self, other: &Self) {
+        self.min = self.min.inf(&other.min);
+        self.max = self.max.sup(&other.max);
+    }
+
+    /// Check if the ray enters the box before the maximum distance, with the slab method.
+    fn intersect(
+        &self,
+        origin: &Vector<T>,
+        direction: &Vector<T>,
+        inverse_direction: &Vector<T>,
+        max: T,
+    ) -> bool {
+        let mut near = T::zero();
+        let mut far = max;
+        for axis in 0..3 {
+            // A ray parallel to the slab never enters it if it starts outside.
+            if direction[axis] == T::zero() {
+                if origin[axis] < self.min[axis] || origin[axis] > self.max[axis] {
+                    return false;
+                }
+                continue;
+            }
+            let mut t_1 = (self.min[axis] - origin[axis]) * inverse_direction[axis];
+            let mut t_2 = (self.max[axis] - origin[axis]) * inverse_direction[axis];
+            if t_1 > t_2 {
+                std::mem::swap(&mut t_1, &mut t_2);
+            }
+            near = near.max(t_1);
+            far = far.min(t_2);
+            if near > far {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Node of the bounding volume hierarchy.
+#[derive(Debug, Clone)]
+enum Node<T>
+where
+    T: RealField,
+{
+    /// Node with the indices of its two children.
+    Branch(BoundingBox<T>, usize, usize),
+    /// Node with the range of its triangles.
+    Leaf(BoundingBox<T>, usize, usize),
+}
+
+/// Maximum number of triangles in a leaf of the bounding volume hierarchy.
+const BVH_LEAF_SIZE: usize = 4;
+
+/// [Bounding volume hierarchy](https://en.wikipedia.org/wiki/Bounding_volume_hierarchy) of the
+/// faces of a triangular mesh, to compute the intersections of rays with the faces.
+///
+/// The hierarchy is built by splitting recursively the faces at the median of their centroids
+/// along the longest axis of their bounding box. The queries run on the CPU, one ray after the
+/// other.
+///
+/// Intersections closer than a tolerance, proportional to the size of the mesh, are ignored, so
+/// that rays can start from the faces of the mesh without intersecting their own face.
+#[derive(Debug, Clone)]
+pub struct Bvh<T>
+where
+    T: RealField,
+{
+    triangles: Vec<[Vector<T>; 3]>,
+    faces: Vec<usize>,
+    nodes: Vec<Node<T>>,
+    tolerance: T,
+}
+
+impl<T> Bvh<T>
+where
+    T: RealField,
+{
+    /// Build the hierarchy from the vertices and the faces of a mesh.
+    pub fn new(vertices: &Vectors<T>, faces: &[[usize; 3]]) -> Self {
+        let triangles = faces
+            .iter()
+            .map(|face| {
+                [
+                    vertices.column(face[0]).into_owned(),
+                    vertices.column(face[1]).into_owned(),
+                    vertices.column(face[2]).into_owned(),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let mut bvh = Self {
+            triangles,
+            faces: (0..faces.len()).collect(),
+            nodes: vec![],
+            tolerance: T::zero(),
+        };
+        if !faces.is_empty() {
+            bvh.build(0, faces.len());
+            let bounds = bvh.bounds(0);
+            bvh.tolerance = (bounds.max - bounds.min).norm() * T::default_epsilon().sqrt();
+        }
+        bvh
+    }
+
+    /// Build the hierarchy from a [`Mesh`].
+    pub fn from_mesh(mesh: &Mesh<T>) -> Self {
+        Self::new(mesh.vertices(), mesh.faces())
+    }
+
+    /// Get the number of faces.
+    pub fn number_faces(&self) -> usize {
+        self.triangles.len()
+    }
+
+    /// Bounding box of a node.
+    fn bounds(&self, node: usize) -> &BoundingBox<T> {
+        match &self.nodes[node] {
+            Node::Branch(bounds, _, _) | Node::Leaf(bounds, _, _) => bounds,
+        }
+    }
+
+    /// Centroid of a face, times three.
+    fn centroid(&self, face: usize) -> Vector<T> {
+        let triangle = &self.triangles[face];
+        triangle[0] + triangle[1] + triangle[2]
+    }
+
+    /// Build the node for the faces in the range and return its index.
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let mut bounds = BoundingBox::empty();
+        let mut centroid_bounds = BoundingBox::empty();
+        for &face in self.faces[start..end].iter() {
+            for vertex in self.triangles[face].iter() {
+                bounds.grow(vertex);
+            }
+            centroid_bounds.grow(&self.centroid(face));
+        }
+        let index = self.nodes.len();
+        if end - start <= BVH_LEAF_SIZE {
+            self.nodes.push(Node::Leaf(bounds, start, end));
+            return index;
+        }
+        self.nodes.push(Node::Leaf(bounds, start, end));
+        let axis = (centroid_bounds.max - centroid_bounds.min).imax();
+        let middle = (start + end) / 2;
+        let triangles = &self.triangles;
+        self.faces[start..end].sort_by(|&face_1, &face_2| {
+            let centroid_1 = triangles[face_1][0][axis] + triangles[face_1][1][axis];
+            let centroid_1 = centroid_1 + triangles[face_1][2][axis];
+            let centroid_2 = triangles[face_2][0][axis] + triangles[face_2][1][axis];
+            let centroid_2 = centroid_2 + triangles[face_2][2][axis];
+            centroid_1
+                .partial_cmp(&centroid_2)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let left = self.build(start, middle);
+        let right = self.build(middle, end);
+        let mut merged = *self.bounds(left);
+        merged.merge(self.bounds(right));
+        self.nodes[index] = Node::Branch(merged, left, right);
+        index
+    }
+
+    /// Visit the faces whose bounding boxes intersect the ray, until the visitor returns `false`.
+    /// The visitor returns the new maximum distance to explore.
+    fn traverse(
+        &self,
+        origin: &Vector<T>,
+        direction: &Vector<T>,
+        max_distance: Option<T>,
+        mut visitor: impl FnMut(Hit<T>) -> Option<T>,
+    ) {
+        if self.nodes.is_empty() {
+            return;
+        }
+        let inverse_direction = direction.map(|component| T::one() / component);
+        let mut max = max_distance.unwrap_or_else(T::max_value);
+        let mut stack = vec![0];
+        while let Some(node) = stack.pop() {
+            if !self
+                .bounds(node)
+                .intersect(origin, direction, &inverse_direction, max)
+            {
+                continue;
+            }
+            match self.nodes[node] {
+                Node::Branch(_, left, right) => {
+                    stack.push(right);
+                    stack.push(left);
+                }
+                Node::Leaf(_, start, end) => {
+                    for &face in self.faces[start..end].iter() {
+                        if let Some(distance) =
+                            ray_triangle_intersection(origin, direction, &self.triangles[face])
+                        {
+                            if distance > self.tolerance / direction.norm() && distance <= max {
+                                match visitor(Hit { distance, face }) {
+                                    Some(new_max) => max = new_max,
+                                    None => return,
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Closest intersection of the ray with the faces, within the optional maximum distance.
+    pub fn first_hit(
+        &self,
+        origin: &Vector<T>,
+        direction: &Vector<T>,
+        max_distance: Option<T>,
+    ) -> Option<Hit<T>> {
+        let mut first: Option<Hit<T>> = None;
+        self.traverse(origin, direction, max_distance, |hit| {
+            match first {
+                Some(first) if first.distance <= hit.distance => (),
+                _ => first = Some(hit),
+            }
+            first.map(|first| first.distance)
+        });
+        first
+    }
+
+    /// Check if the ray intersects any face within the optional maximum distance. The traversal
+    /// stops at the first intersection found.
+    pub fn any_hit(
+        &self,
+        origin: &Vector<T>,
+        direction: &Vector<T>,
+        max_distance: Option<T>,
+    ) -> bool {
+        let mut found = false;
+        self.traverse(origin, direction, max_distance, |_| {
+            found = true;
+            None
+        });
+        found
+    }
+
+    /// All the intersections of the ray with the faces within the optional maximum distance,
+    /// sorted by distance.
+    pub fn all_hits(
+        &self,
+        origin: &Vector<T>,
+        direction: &Vector<T>,
+        max_distance: Option<T>,
+    ) -> Vec<Hit<T>> {
+        let mut hits = vec![];
+        let max = max_distance.unwrap_or_else(T::max_value);
+        self.traverse(origin, direction, max_distance, |hit| {
+            hits.push(hit);
+            Some(max)
+        });
+        hits.sort_by(|hit_1, hit_2| {
+            hit_1
+                .distance
+                .partial_cmp(&hit_2.distance)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        hits
+    }
+
+    /// [First hits][Bvh::first_hit] for a list of rays.
+    pub fn first_hits(
+        &self,
+        origins: &Vectors<T>,
+        directions: &Vectors<T>,
+        max_distance: Option<T>,
+    ) -> Vec<Option<Hit<T>>> {
+        multizip((origins.column_iter(), directions.column_iter()))
+            .map(|(origin, direction)| {
+                self.first_hit(&origin.into_owned(), &direction.into_owned(), max_distance)
+            })
+            .collect()
+    }
+
+    /// [Any hits][Bvh::any_hit] for a list of rays.
+    pub fn any_hits(
+        &self,
+        origins: &Vectors<T>,
+        directions: &Vectors<T>,
+        max_distance: Option<T>,
+    ) -> Vec<bool> {
+        multizip((origins.column_iter(), directions.column_iter()))
+            .map(|(origin, direction)| {
+                self.any_hit(&origin.into_owned(), &direction.into_owned(), max_distance)
+            })
+            .collect()
+    }
+
+    /// [All hits][Bvh::all_hits] for a list of rays.
+    pub fn all_hits_list(
+        &self,
+        origins: &Vectors<T>,
+        directions: &Vectors<T>,
+        max_distance: Option<T>,
+    ) -> Vec<Vec<Hit<T>>> {
+        multizip((origins.column_iter(), directions.column_iter()))
+            .map(|(origin, direction)| {
+                self.all_hits(&origin.into_owned(), &direction.into_owned(), max_distance)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,156 @@
+use crate::{Rotation, Vector, Vectors, VectorsGeneric};
+use na::{storage::Storage, Dynamic, RealField, Unit, UnitQuaternion, U3};
+
+/// Sequences of rotations about the axes of a frame.
+///
+/// The six proper Euler sequences repeat the first axis, the six Tait-Bryan sequences use the
+/// three axes. The sequences are intrinsic: each rotation is about the axis of the frame
+/// obtained after the previous rotations. An extrinsic sequence, with rotations about the axes of
+/// the fixed frame, is the intrinsic sequence [reversed][EulerSequence::reversed] with the angles
+/// in the reversed order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EulerSequence {
+    /// Proper Euler sequence x-y-x.
+    XYX,
+    /// Proper Euler sequence x-z-x.
+    XZX,
+    /// Proper Euler sequence y-x-y.
+    YXY,
+    /// Proper Euler sequence y-z-y.
+    YZY,
+    /// Proper Euler sequence z-x-z.
+    ZXZ,
+    /// Proper Euler sequence z-y-z.
+    ZYZ,
+    /// Tait-Bryan sequence x-y-z.
+    XYZ,
+    /// Tait-Bryan sequence x-z-y.
+    XZY,
+    /// Tait-Bryan sequence y-x-z.
+    YXZ,
+    /// Tait-Bryan sequence y-z-x.
+    YZX,
+    /// Tait-Bryan sequence z-x-y.
+    ZXY,
+    /// Tait-Bryan sequence z-y-x, also known as yaw-pitch-roll.
+    ZYX,
+}
+
+impl EulerSequence {
+    /// Indices of the axes of the sequence, 0 for x, 1 for y and 2 for z.
+    pub fn axes(&self) -> [usize; 3] {
+        match self {
+            EulerSequence::XYX => [0, 1, 0],
+            EulerSequence::XZX => [0, 2, 0],
+            EulerSequence::YXY => [1, 0, 1],
+            EulerSequence::YZY => [1, 2, 1],
+            EulerSequence::ZXZ => [2, 0, 2],
+            EulerSequence::ZYZ => [2, 1, 2],
+            EulerSequence::XYZ => [0, 1, 2],
+            EulerSequence::XZY => [0, 2, 1],
+            EulerSequence::YXZ => [1, 0, 2],
+            EulerSequence::YZX => [1, 2, 0],
+            EulerSequence::ZXY => [2, 0, 1],
+            EulerSequence::ZYX => [2, 1, 0],
+        }
+    }
+
+    /// Sequence with the axes in the reversed order.
+    pub fn reversed(&self) -> Self {
+        match self {
+            EulerSequence::XYZ => EulerSequence::ZYX,
+            EulerSequence::XZY => EulerSequence::YZX,
+            EulerSequence::YXZ => EulerSequence::ZXY,
+            EulerSequence::YZX => EulerSequence::XZY,
+            EulerSequence::ZXY => EulerSequence::YXZ,
+            EulerSequence::ZYX => EulerSequence::XYZ,
+            proper => *proper,
+        }
+    }
+}
+
+/// Rotation about one of the axes of the frame, 0 for x, 1 for y and 2 for z.
+pub fn elementary_rotation<T>(axis: usize, angle: T) -> Rotation<T>
+where
+    T: RealField,
+{
+    Rotation::from_axis_angle(&Vector::ith_axis(axis), angle)
+}
+
+/// Rotation from a sequence of three angles about the axes of an [`EulerSequence`].
+///
+/// ## Expression
+///
+/// For the sequence of axes $i$, $j$, $k$ and the angles $\alpha$, $\beta$, $\gamma$, the
+/// rotation is,
+///
+/// $$R=R_i\left(\alpha\right)R_j\left(\beta\right)R_k\left(\gamma\right)$$
+///
+/// where $R_i$ is the [elementary rotation][elementary_rotation] about the axis $i$.
+pub fn rotation_from_euler<T>(sequence: EulerSequence, angles: &Vector<T>) -> Rotation<T>
+where
+    T: RealField,
+{
+    let [first, second, third] = sequence.axes();
+    elementary_rotation(first, angles[0])
+        * elementary_rotation(second, angles[1])
+        * elementary_rotation(third, angles[2])
+}
+
+/// Rotation of `angle` about `axis`. The axis does not need to be normalized.
+pub fn rotation_from_axis_angle<T>(axis: &Vector<T>, angle: T) -> Rotation<T>
+where
+    T: RealField,
+{
+    Rotation::from_axis_angle(&Unit::new_normalize(*axis), angle)
+}
+
+/// Rotation from a unit quaternion.
+pub fn rotation_from_quaternion<T>(quaternion: &UnitQuaternion<T>) -> Rotation<T>
+where
+    T: RealField,
+{
+    quaternion.to_rotation_matrix()
+}
+
+/// Rotate a list of [`Vector`]s.
+pub fn rotate<T, S>(vectors: &VectorsGeneric<T, S>, rotation: &Rotation<T>) -> Vectors<T>
+where
+    T: RealField,
+    S: Storage<T, U3, Dynamic>,
+{
+    rotation.matrix() * vectors
+}
+
+/// Rotate a list of [`Vector`]s with the inverse of the rotation.
+pub fn rotate_inverse<T, S>(vectors: &VectorsGeneric<T, S>, rotation: &Rotation<T>) -> Vectors<T>
+where
+    T: RealField,
+    S: Storage<T, U3, Dynamic>,
+{
+    rotation.matrix().tr_mul(vectors)
+}
+
+/// Rotate a list of [`Vector`]s with a unit quaternion.
+pub fn rotate_quaternion<T, S>(
+    vectors: &VectorsGeneric<T, S>,
+    quaternion: &UnitQuaternion<T>,
+) -> Vectors<T>
+where
+    T: RealField,
+    S: Storage<T, U3, Dynamic>,
+{
+    rotate(vectors, &rotation_from_quaternion(quaternion))
+}
+
+/// Rotate a list of [`Vector`]s with the inverse of a unit quaternion.
+pub fn rotate_quaternion_inverse<T, S>(
+    vectors: &VectorsGeneric<T, S>,
+    quaternion: &UnitQuaternion<T>,
+) -> Vectors<T>
+where
+    T: RealField,
+    S: Storage<T, U3, Dynamic>,
+{
+    rotate_inverse(vectors, &rotation_from_quaternion(quaternion))
+}
@@ -0,0 +1,456 @@
+use crate::{ecliptic_to_equatorial, Epoch, TimeScale, Vector};
+use std::convert::TryInto;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+/// Size of the records of a DAF file, in bytes.
+const RECORD_SIZE: usize = 1024;
+/// Identifier of the J2000 frame.
+const FRAME_J2000: i32 = 1;
+/// Identifier of the ECLIPJ2000 frame.
+const FRAME_ECLIPJ2000: i32 = 17;
+
+/// Position and velocity.
+type State = (Vector<f64>, Vector<f64>);
+
+/// Error raised while reading or evaluating an SPK kernel.
+#[derive(Debug)]
+pub enum SpkError {
+    /// Error from the file system.
+    Io(std::io::Error),
+    /// Malformed DAF or SPK content.
+    Format(String),
+    /// Segment of a data type other than 2, 3 and 13.
+    UnsupportedType(i32),
+    /// Segment in a reference frame other than J2000 and ECLIPJ2000.
+    UnsupportedFrame(i32),
+    /// No segment of the body covers the epoch, in seconds of TDB past J2000.
+    NoCoverage {
+        /// NAIF identifier of the body.
+        body: i32,
+        /// Epoch in seconds of TDB past J2000.
+        epoch: f64,
+    },
+    /// The segments do not link the target to the center.
+    NoPath {
+        /// NAIF identifier of the target.
+        target: i32,
+        /// NAIF identifier of the center.
+        center: i32,
+    },
+}
+
+impl fmt::Display for SpkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpkError::Io(error) => write!(f, "{}", error),
+            SpkError::Format(message) => write!(f, "invalid SPK kernel: {}", message),
+            SpkError::UnsupportedType(data_type) => {
+                write!(f, "unsupported SPK segment type {}", data_type)
+            }
+            SpkError::UnsupportedFrame(frame) => write!(f, "unsupported frame {}", frame),
+            SpkError::NoCoverage { body, epoch } => write!(
+                f,
+                "no segment for the body {} at {} s past J2000",
+                body, epoch
+            ),
+            SpkError::NoPath { target, center } => write!(
+                f,
+                "no chain of segments from the target {} to the center {}",
+                target, center
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SpkError {}
+
+impl From<std::io::Error> for SpkError {
+    fn from(error: std::io::Error) -> Self {
+        SpkError::Io(error)
+    }
+}
+
+/// Summary of a segment of an SPK kernel.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpkSegment {
+    /// Name of the segment.
+    pub name: String,
+    /// NAIF identifier of the target body.
+    pub target: i32,
+    /// NAIF identifier of the center body.
+    pub center: i32,
+    /// NAIF identifier of the reference frame.
+    pub frame: i32,
+    /// SPK data type.
+    pub data_type: i32,
+    /// Start of the coverage, in seconds of TDB past J2000.
+    pub start: f64,
+    /// End of the coverage, in seconds of TDB past J2000.
+    pub end: f64,
+    /// Address of the first double precision number of the data, starting at 1.
+    begin_address: usize,
+    /// Address of the last double precision number of the data.
+    end_address: usize,
+}
+
+/// SPICE SPK ephemeris kernel, read from the DAF binary format.
+///
+/// The segments of types 2 and 3 (Chebyshev polynomials of the position, or of the position and
+/// the velocity, on intervals of equal length) and 13 (Hermite interpolation of states with
+/// unequal time steps) are evaluated. The states are returned in meters and meters per second, in
+/// the J2000 equatorial frame, the segments in the ECLIPJ2000 frame being rotated.
+///
+/// As in SPICE, the segments are searched from the last one of the file, so that later segments
+/// take precedence.
+#[derive(Debug, Clone)]
+pub struct Spk {
+    data: Vec<u8>,
+    little_endian: bool,
+    segments: Vec<SpkSegment>,
+}
+
+impl Spk {
+    /// Read an SPK kernel from a file.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, SpkError> {
+        Self::from_bytes(fs::read(path)?)
+    }
+
+    /// Read an SPK kernel from the bytes of a file.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, SpkError> {
+        if data.len() < RECORD_SIZE {
+            return Err(SpkError::Format("file shorter than a record".to_string()));
+        }
+        let identifier = String::from_utf8_lossy(&data[0..8]).to_string();
+        if identifier != "DAF/SPK " && identifier != "NAIF/DAF" {
+            return Err(SpkError::Format(format!(
+                "unknown identification word {:?}",
+                identifier
+            )));
+        }
+        let little_endian = match &data[88..96] {
+            b"LTL-IEEE" => true,
+            b"BIG-IEEE" => false,
+            _ => i32::from_le_bytes(data[8..12].try_into().unwrap()) == 2,
+        };
+        let mut spk = Self {
+            data,
+            little_endian,
+            segments: vec![],
+        };
+        let nd = spk.integer(8) as usize;
+        let ni = spk.integer(12) as usize;
+        if nd != 2 || ni != 6 {
+            return Err(SpkError::Format(format!(
+                "{} double and {} integer components instead of 2 and 6",
+                nd, ni
+            )));
+        }
+        // Summaries of 2 doubles and 6 integers packed in 3 doubles, and names of 40 characters.
+        let summary_size = nd + ni.div_ceil(2);
+        let mut record = spk.integer(76) as usize;
+        let mut visited = 0;
+        while record != 0 {
+            visited += 1;
+            let offset = (record - 1) * RECORD_SIZE;
+            if offset + 2 * RECORD_SIZE > spk.data.len() || visited > spk.data.len() / RECORD_SIZE {
+                return Err(SpkError::Format(format!(
+                    "invalid summary record {}",
+                    record
+                )));
+            }
+            let next = spk.double(offset) as usize;
+            let count = spk.double(offset + 16) as usize;
+            if 3 + count * summary_size > RECORD_SIZE / 8 {
+                return Err(SpkError::Format(format!(
+                    "{} summaries in the record {}",
+                    count, record
+                )));
+            }
+            for index in 0..count {
+                let summary = offset + 24 + index * summary_size * 8;
+                let integer = |component: usize| spk.integer(summary + 16 + component * 4);
+                let name_offset = offset + RECORD_SIZE + index * summary_size * 8;
+                let name = String::from_utf8_lossy(&spk.data[name_offset..name_offset + 40])
+                    .trim_end()
+                    .to_string();
+                let segment = SpkSegment {
+                    name,
+                    target: integer(0),
+                    center: integer(1),
+                    frame: integer(2),
+                    data_type: integer(3),
+                    start: spk.double(summary),
+                    end: spk.double(summary + 8),
+                    begin_address: integer(4) as usize,
+                    end_address: integer(5) as usize,
+                };
+                if segment.begin_address == 0
+                    || segment.end_address < segment.begin_address
+                    || segment.end_address * 8 > spk.data.len()
+                {
+                    return Err(SpkError::Format(format!(
+                        "invalid addresses of the segment {}",
+                        segment.name
+                    )));
+                }
+                spk.segments.push(segment);
+            }
+            record = next;
+        }
+        Ok(spk)
+    }
+
+    /// Segments of the kernel, in the order of the file.
+    pub fn segments(&self) -> &[SpkSegment] {
+        &self.segments
+    }
+
+    /// Position and velocity of a target relative to a center at an epoch, in meters and meters
+    /// per second in the J2000 equatorial frame.
+    ///
+    /// The bodies are given by their NAIF identifiers. The segments are chained through the
+    /// intermediate centers up to the closest common center of the target and the center.
+    pub fn state(
+        &self,
+        target: i32,
+        center: i32,
+        epoch: &Epoch,
+    ) -> Result<(Vector<f64>, Vector<f64>), SpkError> {
+        let time = epoch.seconds_past_j2000(TimeScale::TDB);
+        let target_chain = self.chain(target, time)?;
+        let center_chain = self.chain(center, time)?;
+        // States of the bodies relative to the common center, which is the first body of the
+        // target chain found in the center chain.
+        for (body, target_state) in &target_chain {
+            if let Some((_, center_state)) = center_chain.iter().find(|(other, _)| other == body) {
+                return Ok((
+                    (target_state.0 - center_state.0) * 1e3,
+                    (target_state.1 - center_state.1) * 1e3,
+                ));
+            }
+        }
+        Err(SpkError::NoPath { target, center })
+    }
+
+    /// States in kilometers of a body relative to the successive centers of its segments,
+    /// starting with the body itself.
+    fn chain(&self, body: i32, time: f64) -> Result<Vec<(i32, State)>, SpkError> {
+        let mut chain = vec![(body, (Vector::zeros(), Vector::zeros()))];
+        let mut current = body;
+        while let Some(segment) = self.segments.iter().rev().find(|segment| {
+            segment.target == current && segment.start <= time && time <= segment.end
+        }) {
+            let (position, velocity) = self.evaluate(segment, time)?;
+            let last = chain.last().unwrap().1;
+            current = segment.center;
+            if chain.iter().any(|(other, _)| *other == current) {
+                return Err(SpkError::Format(format!(
+                    "cycle of segments through the body {}",
+                    current
+                )));
+            }
+            chain.push((current, (last.0 + position, last.1 + velocity)));
+        }
+        if chain.len() == 1 && self.segments.iter().any(|segment| segment.target == body) {
+            return Err(SpkError::NoCoverage { body, epoch: time });
+        }
+        Ok(chain)
+    }
+
+    /// State of the target of a segment relative to its center, in kilometers in the J2000
+    /// frame.
+    fn evaluate(
+        &self,
+        segment: &SpkSegment,
+        time: f64,
+    ) -> Result<(Vector<f64>, Vector<f64>), SpkError> {
+        let (position, velocity) = match segment.data_type {
+            2 | 3 => self.evaluate_chebyshev(segment, time)?,
+            13 => self.evaluate_hermite(segment, time)?,
+            data_type => return Err(SpkError::UnsupportedType(data_type)),
+        };
+        match segment.frame {
+            FRAME_J2000 => Ok((position, velocity)),
+            FRAME_ECLIPJ2000 => {
+                let transform = ecliptic_to_equatorial();
+                Ok((
+                    transform.rotation() * position,
+                    transform.rotation() * velocity,
+                ))
+            }
+            frame => Err(SpkError::UnsupportedFrame(frame)),
+        }
+    }
+
+    /// Evaluate a segment of Chebyshev polynomials, of type 2 or 3.
+    ///
+    /// ## Expression
+    ///
+    /// Each record covers an interval of center $t_m$ and radius $r$, and the components are,
+    ///
+    /// $$x\left(t\right)=\sum_{k=0}^{n}c_kT_k\left(s\right),\quad s=\frac{t-t_m}{r}$$
+    ///
+    /// The velocity of the type 2 is the derivative of the position, divided by $r$.
+    fn evaluate_chebyshev(
+        &self,
+        segment: &SpkSegment,
+        time: f64,
+    ) -> Result<(Vector<f64>, Vector<f64>), SpkError> {
+        let directory = segment.end_address - 4;
+        let initial = self.word(directory);
+        let length = self.word(directory + 1);
+        let record_size = self.word(directory + 2) as usize;
+        let number_records = self.word(directory + 3) as usize;
+        let components = if segment.data_type == 2 { 3 } else { 6 };
+        if number_records == 0
+            || record_size < 2 + components
+            || !(record_size - 2).is_multiple_of(components)
+            || segment.begin_address + number_records * record_size > directory + 1
+        {
+            return Err(SpkError::Format(format!(
+                "invalid directory of the segment {}",
+                segment.name
+            )));
+        }
+        let number_coefficients = (record_size - 2) / components;
+        let index = (((time - initial) / length).floor().max(0.0) as usize).min(number_records - 1);
+        let record = segment.begin_address - 1 + index * record_size;
+        let middle = self.word(record);
+        let radius = self.word(record + 1);
+        let s = (time - middle) / radius;
+
+        // Chebyshev polynomials and their derivatives.
+        let mut polynomials = vec![1.0; number_coefficients];
+        let mut derivatives = vec![0.0; number_coefficients];
+        if number_coefficients > 1 {
+            polynomials[1] = s;
+            derivatives[1] = 1.0;
+        }
+        for k in 2..number_coefficients {
+            polynomials[k] = 2.0 * s * polynomials[k - 1] - polynomials[k - 2];
+            derivatives[k] =
+                2.0 * polynomials[k - 1] + 2.0 * s * derivatives[k - 1] - derivatives[k - 2];
+        }
+        let series = |component: usize, basis: &[f64]| {
+            let start = record + 2 + component * number_coefficients;
+            (0..number_coefficients)
+                .map(|k| self.word(start + k) * basis[k])
+                .sum::<f64>()
+        };
+        let position = Vector::from_fn(|component, _| series(component, &polynomials));
+        let velocity = if segment.data_type == 2 {
+            Vector::from_fn(|component, _| series(component, &derivatives) / radius)
+        } else {
+            Vector::from_fn(|component, _| series(component + 3, &polynomials))
+        };
+        Ok((position, velocity))
+    }
+
+    /// Evaluate a segment of Hermite interpolation with unequal time steps, of type 13.
+    ///
+    /// The window of states is centered on the time, and each component is interpolated by the
+    /// Hermite polynomial matching the positions and the velocities of the window.
+    fn evaluate_hermite(
+        &self,
+        segment: &SpkSegment,
+        time: f64,
+    ) -> Result<(Vector<f64>, Vector<f64>), SpkError> {
+        let number_states = self.word(segment.end_address - 1) as usize;
+        let window = self.word(segment.end_address - 2) as usize + 1;
+        let number_directories = number_states.saturating_sub(1) / 100;
+        if number_states == 0
+            || !window.is_multiple_of(2)
+            || segment.begin_address - 1 + 7 * number_states + number_directories + 2
+                != segment.end_address
+        {
+            return Err(SpkError::Format(format!(
+                "invalid directory of the segment {}",
+                segment.name
+            )));
+        }
+        let window = window.min(number_states);
+        let epochs = segment.begin_address - 1 + 6 * number_states;
+        let epoch = |index: usize| self.word(epochs + index);
+        // First epoch after the time, found by bisection.
+        let (mut lower, mut upper) = (0, number_states);
+        while lower < upper {
+            let middle = (lower + upper) / 2;
+            if epoch(middle) <= time {
+                lower = middle + 1;
+            } else {
+                upper = middle;
+            }
+        }
+        let first = lower.saturating_sub(window / 2).min(number_states - window);
+        let times: Vec<f64> = (first..first + window).map(epoch).collect();
+
+        let mut position = Vector::zeros();
+        let mut velocity = Vector::zeros();
+        for component in 0..3 {
+            let state = |index: usize, offset: usize| {
+                self.word(segment.begin_address - 1 + 6 * (first + index) + component + offset)
+            };
+            let values: Vec<f64> = (0..window).map(|index| state(index, 0)).collect();
+            let derivatives: Vec<f64> = (0..window).map(|index| state(index, 3)).collect();
+            let (value, derivative) = hermite(&times, &values, &derivatives, time);
+            position[component] = value;
+            velocity[component] = derivative;
+        }
+        Ok((position, velocity))
+    }
+
+    /// Double precision number at an address, starting at 0.
+    fn word(&self, address: usize) -> f64 {
+        self.double(address * 8)
+    }
+
+    /// Double precision number at a byte offset.
+    fn double(&self, offset: usize) -> f64 {
+        let bytes = self.data[offset..offset + 8].try_into().unwrap();
+        if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        }
+    }
+
+    /// Integer at a byte offset.
+    fn integer(&self, offset: usize) -> i32 {
+        let bytes = self.data[offset..offset + 4].try_into().unwrap();
+        if self.little_endian {
+            i32::from_le_bytes(bytes)
+        } else {
+            i32::from_be_bytes(bytes)
+        }
+    }
+}
+
+/// Value and derivative at a time of the Hermite polynomial matching values and derivatives at
+/// nodes, computed with the divided differences of the doubled nodes.
+pub(crate) fn hermite(times: &[f64], values: &[f64], derivatives: &[f64], time: f64) -> (f64, f64) {
+    let size = 2 * times.len();
+    let nodes: Vec<f64> = (0..size).map(|index| times[index / 2]).collect();
+    let mut differences: Vec<f64> = (0..size).map(|index| values[index / 2]).collect();
+    let mut coefficients = vec![differences[0]];
+    for order in 1..size {
+        for index in (order..size).rev() {
+            differences[index] = if order == 1 && index % 2 == 1 {
+                derivatives[index / 2]
+            } else {
+                (differences[index] - differences[index - 1])
+                    / (nodes[index] - nodes[index - order])
+            };
+        }
+        coefficients.push(differences[order]);
+    }
+    // Horner's scheme of the Newton form and of its derivative.
+    let mut value = 0.0;
+    let mut derivative = 0.0;
+    for order in (0..size).rev() {
+        derivative = derivative * (time - nodes[order]) + value;
+        value = value * (time - nodes[order]) + coefficients[order];
+    }
+    (value, derivative)
+}
@@ -0,0 +1,368 @@
+use crate::{
+    newton_method, pows, List, NewtonMethodArguments, NewtonMethodError, STEFAN_BOLTZMANN,
+};
+use na::DMatrix;
+use std::fmt;
+
+/// Largest ratio $\kappa\Delta t/\Delta z^2$ of the explicit scheme for which it is stable.
+pub const EXPLICIT_STABILITY_LIMIT: f64 = 0.5;
+
+/// Error raised by the thermophysical model.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThermalError {
+    /// Invalid parameter of the model or of a time step.
+    InvalidParameter(String),
+    /// Time step of the explicit scheme above its stability limit.
+    Unstable {
+        /// Requested time step, in s.
+        time_step: f64,
+        /// Largest stable time step, in s.
+        maximum_time_step: f64,
+    },
+    /// Failure of the Newton's method of the surface energy balance.
+    SurfaceBalance(NewtonMethodError<f64>),
+}
+
+impl fmt::Display for ThermalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThermalError::InvalidParameter(message) => write!(f, "invalid parameter: {}", message),
+            ThermalError::Unstable {
+                time_step,
+                maximum_time_step,
+            } => write!(
+                f,
+                "time step {} s above the stability limit {} s of the explicit scheme",
+                time_step, maximum_time_step
+            ),
+            ThermalError::SurfaceBalance(error) => {
+                write!(f, "surface energy balance failed: {}", error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThermalError {}
+
+impl From<NewtonMethodError<f64>> for ThermalError {
+    fn from(error: NewtonMethodError<f64>) -> Self {
+        ThermalError::SurfaceBalance(error)
+    }
+}
+
+/// Scheme of the time integration of the conduction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConductionScheme {
+    /// Forward Euler in time, stable for $\kappa\Delta t/\Delta z^2\leq$
+    /// [`EXPLICIT_STABILITY_LIMIT`].
+    Explicit,
+    /// Average of the explicit and implicit schemes, unconditionally stable and of second order
+    /// in time.
+    CrankNicolson,
+}
+
+/// Boundary condition at the bottom of the grid of depths.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BottomBoundary {
+    /// No heat flux through the bottom.
+    ZeroFlux,
+    /// Fixed temperature gradient with depth, in K/m, positive when the temperature increases
+    /// downwards, as for an internal heat flux.
+    Gradient(f64),
+}
+
+/// Thermal properties of the subsurface material, in SI units.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThermalProperties {
+    /// Thermal conductivity $k$, in W/m/K.
+    pub conductivity: f64,
+    /// Density $\rho$, in kg/m³.
+    pub density: f64,
+    /// Specific heat capacity $c$, in J/kg/K.
+    pub heat_capacity: f64,
+    /// Emissivity $\varepsilon$ of the surface in the thermal infrared.
+    pub emissivity: f64,
+}
+
+impl ThermalProperties {
+    /// Thermal diffusivity $\kappa=\frac{k}{\rho c}$, in m²/s.
+    pub fn diffusivity(&self) -> f64 {
+        self.conductivity / (self.density * self.heat_capacity)
+    }
+
+    /// Thermal inertia $\Gamma=\sqrt{k\rho c}$, in J/m²/K/s^½.
+    pub fn thermal_inertia(&self) -> f64 {
+        (self.conductivity * self.density * self.heat_capacity).sqrt()
+    }
+
+    /// Thermal skin depth $\sqrt{\frac{\kappa P}{\pi}}$ of a periodic forcing of period $P$, in
+    /// m.
+    pub fn skin_depth(&self, period: f64) -> f64 {
+        (self.diffusivity() * period / std::f64::consts::PI).sqrt()
+    }
+}
+
+/// Arguments of the Newton's method of the surface energy balance.
+struct SurfaceArguments {
+    /// Emissivity times the Stefan-Boltzmann constant.
+    emission: f64,
+    /// Conductivity divided by twice the depth step.
+    conduction: f64,
+    /// Temperatures of the first layer below the surface.
+    first_layer: List<f64>,
+    /// Temperatures of the second layer below the surface.
+    second_layer: List<f64>,
+    /// Absorbed fluxes.
+    fluxes: List<f64>,
+}
+
+impl NewtonMethodArguments for SurfaceArguments {
+    fn subset(&self, components: &[usize]) -> Option<Self> {
+        let select = |values: &List<f64>| {
+            List::from_iterator(
+                components.len(),
+                components.iter().map(|&index| values[index]),
+            )
+        };
+        Some(Self {
+            emission: self.emission,
+            conduction: self.conduction,
+            first_layer: select(&self.first_layer),
+            second_layer: select(&self.second_layer),
+            fluxes: select(&self.fluxes),
+        })
+    }
+}
+
+/// Function of the Newton's method of the surface energy balance.
+fn surface_function(values: &List<f64>, args: &SurfaceArguments) -> List<f64> {
+    pows(values, 4) * args.emission
+        - (values * -3.0 + &args.first_layer * 4.0 - &args.second_layer) * args.conduction
+        - &args.fluxes
+}
+
+/// Derivative of the Newton's method of the surface energy balance.
+fn surface_derivative(values: &List<f64>, args: &SurfaceArguments) -> List<f64> {
+    (pows(values, 3) * (4.0 * args.emission)).add_scalar(3.0 * args.conduction)
+}
+
+/// One-dimensional thermophysical model of the subsurface of facets.
+///
+/// ## Expression
+///
+/// The temperature $T$ of each facet follows the heat equation along the depth $z$, on a grid of
+/// uniform step $\Delta z$ from the surface,
+///
+/// $$\frac{\partial T}{\partial t}=\kappa\frac{\partial^2T}{\partial z^2}$$
+///
+/// The surface temperature $T_0$ balances the absorbed flux $F$ with the thermal emission and
+/// the conduction into the subsurface, with a one-sided derivative of second order,
+///
+/// $$F=\varepsilon\sigma T_0^4-k\frac{-3T_0+4T_1-T_2}{2\Delta z}$$
+///
+/// ## Method
+///
+/// Each step first advances the layers below the surface with the [scheme][ConductionScheme],
+/// the surface temperature being held, and the [bottom boundary][BottomBoundary] being imposed
+/// by a ghost layer. The energy balance is then solved for the new surface temperatures of all
+/// the facets at once with the [Newton's method][newton_method].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThermalModel {
+    /// Thermal properties of the material.
+    pub properties: ThermalProperties,
+    /// Scheme of the time integration.
+    pub scheme: ConductionScheme,
+    /// Boundary condition at the bottom.
+    pub bottom: BottomBoundary,
+    /// Step of the grid of depths, in m.
+    depth_step: f64,
+    /// Temperatures, with the layers as rows from the surface and the facets as columns, in K.
+    temperatures: DMatrix<f64>,
+}
+
+impl ThermalModel {
+    /// Create a model of facets whose layers all start at the same temperature.
+    ///
+    /// At least three layers are needed for the surface boundary condition.
+    pub fn new(
+        properties: ThermalProperties,
+        number_facets: usize,
+        number_layers: usize,
+        depth_step: f64,
+        initial_temperature: f64,
+    ) -> Result<Self, ThermalError> {
+        let invalid = |message: &str| Err(ThermalError::InvalidParameter(message.to_string()));
+        if number_layers < 3 {
+            return invalid("at least three layers are needed");
+        }
+        if !positive(depth_step) {
+            return invalid("the depth step must be positive");
+        }
+        if !positive(properties.conductivity)
+            || !positive(properties.density)
+            || !positive(properties.heat_capacity)
+        {
+            return invalid("the conductivity, density and heat capacity must be positive");
+        }
+        if !positive(properties.emissivity) || properties.emissivity > 1.0 {
+            return invalid("the emissivity must be in (0, 1]");
+        }
+        if !positive(initial_temperature) {
+            return invalid("the initial temperature must be positive");
+        }
+        Ok(Self {
+            properties,
+            scheme: ConductionScheme::CrankNicolson,
+            bottom: BottomBoundary::ZeroFlux,
+            depth_step,
+            temperatures: DMatrix::from_element(number_layers, number_facets, initial_temperature),
+        })
+    }
+
+    /// Step of the grid of depths, in m.
+    pub fn depth_step(&self) -> f64 {
+        self.depth_step
+    }
+
+    /// Temperatures, with the layers as rows from the surface and the facets as columns, in K.
+    pub fn temperatures(&self) -> &DMatrix<f64> {
+        &self.temperatures
+    }
+
+    /// Surface temperatures of the facets, in K.
+    pub fn surface_temperatures(&self) -> List<f64> {
+        self.temperatures.row(0).into_owned()
+    }
+
+    /// Largest time step for which the explicit scheme is stable,
+    /// $\frac{\Delta z^2}{2\kappa}$, in s.
+    pub fn maximum_explicit_time_step(&self) -> f64 {
+        EXPLICIT_STABILITY_LIMIT * self.depth_step.powi(2) / self.properties.diffusivity()
+    }
+
+    /// Advance the temperatures by a time step with the absorbed fluxes of the facets, in W/m².
+    ///
+    /// The explicit scheme returns an error if the time step is above its
+    /// [stability limit][ThermalModel::maximum_explicit_time_step]. The temperatures are left
+    /// unchanged by any error, including a failure of the surface energy balance.
+    pub fn step(&mut self, fluxes: &List<f64>, time_step: f64) -> Result<(), ThermalError> {
+        if fluxes.len() != self.temperatures.ncols() {
+            return Err(ThermalError::InvalidParameter(format!(
+                "{} fluxes for {} facets",
+                fluxes.len(),
+                self.temperatures.ncols()
+            )));
+        }
+        if fluxes.iter().any(|flux| !positive(*flux) && *flux != 0.0) {
+            return Err(ThermalError::InvalidParameter(
+                "the fluxes must be positive or null".to_string(),
+            ));
+        }
+        if !positive(time_step) {
+            return Err(ThermalError::InvalidParameter(
+                "the time step must be positive".to_string(),
+            ));
+        }
+        let maximum_time_step = self.maximum_explicit_time_step();
+        if self.scheme == ConductionScheme::Explicit && time_step > maximum_time_step {
+            return Err(ThermalError::Unstable {
+                time_step,
+                maximum_time_step,
+            });
+        }
+
+        let ratio = self.properties.diffusivity() * time_step / self.depth_step.powi(2);
+        let gradient = match self.bottom {
+            BottomBoundary::ZeroFlux => 0.0,
+            BottomBoundary::Gradient(gradient) => gradient,
+        };
+        // Temperature of the ghost layer below the bottom imposing the gradient.
+        let ghost = 2.0 * self.depth_step * gradient;
+        let mut temperatures = self.temperatures.clone();
+        for mut column in temperatures.column_iter_mut() {
+            let old: Vec<f64> = column.iter().copied().collect();
+            let new = match self.scheme {
+                ConductionScheme::Explicit => explicit(&old, ratio, ghost),
+                ConductionScheme::CrankNicolson => crank_nicolson(&old, ratio, ghost),
+            };
+            column.rows_mut(1, old.len() - 1).copy_from_slice(&new);
+        }
+
+        let arguments = SurfaceArguments {
+            emission: self.properties.emissivity * STEFAN_BOLTZMANN,
+            conduction: self.properties.conductivity / (2.0 * self.depth_step),
+            first_layer: temperatures.row(1).into_owned(),
+            second_layer: temperatures.row(2).into_owned(),
+            fluxes: fluxes.clone(),
+        };
+        let surface = newton_method(
+            self.surface_temperatures(),
+            surface_function,
+            surface_derivative,
+            arguments,
+        )?;
+        temperatures.row_mut(0).copy_from(&surface.values);
+        self.temperatures = temperatures;
+        Ok(())
+    }
+}
+
+/// Whether a value is finite and strictly positive.
+fn positive(value: f64) -> bool {
+    value > 0.0 && value.is_finite()
+}
+
+/// Temperatures of the layers below the surface after an explicit step.
+fn explicit(old: &[f64], ratio: f64, ghost: f64) -> Vec<f64> {
+    let last = old.len() - 1;
+    (1..=last)
+        .map(|index| {
+            let below = if index == last {
+                old[last - 1] + ghost
+            } else {
+                old[index + 1]
+            };
+            old[index] + ratio * (old[index - 1] - 2.0 * old[index] + below)
+        })
+        .collect()
+}
+
+/// Temperatures of the layers below the surface after a Crank-Nicolson step, solving the
+/// tridiagonal system with the Thomas algorithm.
+fn crank_nicolson(old: &[f64], ratio: f64, ghost: f64) -> Vec<f64> {
+    let size = old.len() - 1;
+    let half = ratio / 2.0;
+    // Sub-diagonal, diagonal, super-diagonal and right-hand side of the layers 1 to size.
+    let mut lower = vec![-half; size];
+    let diagonal = vec![1.0 + ratio; size];
+    let mut upper = vec![-half; size];
+    let mut rhs = explicit(old, half, ghost);
+    // The surface temperature is held, and the ghost layer mirrors the layer above the bottom.
+    rhs[0] += half * old[0];
+    lower[0] = 0.0;
+    lower[size - 1] = -ratio;
+    rhs[size - 1] += half * ghost;
+    upper[size - 1] = 0.0;
+
+    let mut modified_upper = vec![0.0; size];
+    let mut modified_rhs = vec![0.0; size];
+    for index in 0..size {
+        let denominator = if index == 0 {
+            diagonal[0]
+        } else {
+            diagonal[index] - lower[index] * modified_upper[index - 1]
+        };
+        modified_upper[index] = upper[index] / denominator;
+        modified_rhs[index] = if index == 0 {
+            rhs[0] / denominator
+        } else {
+            (rhs[index] - lower[index] * modified_rhs[index - 1]) / denominator
+        };
+    }
+    let mut new = vec![0.0; size];
+    new[size - 1] = modified_rhs[size - 1];
+    for index in (0..size - 1).rev() {
+        new[index] = modified_rhs[index] - modified_upper[index] * new[index + 1];
+    }
+    new
+}
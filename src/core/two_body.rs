@@ -0,0 +1,385 @@
+use crate::kepler::safeguarded_newton;
+use crate::{Vector, TAU};
+use std::fmt;
+
+/// Maximum number of iterations of the root searches of Lambert's problem.
+pub const LAMBERT_MAX_ITERATIONS: usize = 200;
+/// Maximum number of doublings of the bracket of the universal anomaly of the propagation.
+pub const PROPAGATION_MAX_DOUBLINGS: usize = 200;
+/// Relative tolerance on the universal variable of Lambert's problem.
+pub const LAMBERT_TOLERANCE: f64 = 1e-14;
+
+/// Error raised by the two-body propagation and Lambert's problem.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TwoBodyError {
+    /// The gravitational parameter is not strictly positive or not finite.
+    InvalidGravitationalParameter(f64),
+    /// The time of flight is not finite, or not strictly positive for Lambert's problem.
+    InvalidTimeOfFlight(f64),
+    /// A position is zero or not finite.
+    InvalidPosition,
+    /// The two positions of Lambert's problem are collinear and do not define a transfer plane.
+    CollinearPositions,
+    /// The universal Kepler's equation did not converge.
+    NotConverged {
+        /// Last value of the universal anomaly.
+        last_value: f64,
+    },
+}
+
+impl fmt::Display for TwoBodyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TwoBodyError::InvalidGravitationalParameter(gm) => {
+                write!(f, "invalid gravitational parameter {}", gm)
+            }
+            TwoBodyError::InvalidTimeOfFlight(time) => write!(f, "invalid time of flight {}", time),
+            TwoBodyError::InvalidPosition => write!(f, "invalid position"),
+            TwoBodyError::CollinearPositions => write!(
+                f,
+                "collinear positions do not define the plane of the transfer"
+            ),
+            TwoBodyError::NotConverged { last_value } => write!(
+                f,
+                "universal Kepler's equation did not converge, last value {}",
+                last_value
+            ),
+        }
+    }
+}
+
+impl std::error::Error for TwoBodyError {}
+
+/// Solution of Lambert's problem.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LambertSolution {
+    /// Number of complete revolutions of the transfer.
+    pub revolutions: usize,
+    /// Velocity at the departure position.
+    pub velocity_departure: Vector<f64>,
+    /// Velocity at the arrival position.
+    pub velocity_arrival: Vector<f64>,
+}
+
+/// Stumpff function $C\left(z\right)$.
+///
+/// ## Expression
+///
+/// $$C\left(z\right)=\frac{1-\cos\sqrt{z}}{z}=\frac{\cosh\sqrt{-z}-1}{-z}=\sum_{k=0}^{\infty}
+/// \frac{\left(-z\right)^k}{\left(2k+2\right)!}$$
+///
+/// The series is used for $\left|z\right|<1$ to avoid the cancellation.
+pub fn stumpff_c(z: f64) -> f64 {
+    if z.abs() < 1.0 {
+        stumpff_series(z, 2)
+    } else if z > 0.0 {
+        (1.0 - z.sqrt().cos()) / z
+    } else {
+        ((-z).sqrt().cosh() - 1.0) / -z
+    }
+}
+
+/// Stumpff function $S\left(z\right)$.
+///
+/// ## Expression
+///
+/// $$S\left(z\right)=\frac{\sqrt{z}-\sin\sqrt{z}}{\sqrt{z}^3}=\frac{\sinh\sqrt{-z}-\sqrt{-z}}
+/// {\sqrt{-z}^3}=\sum_{k=0}^{\infty}\frac{\left(-z\right)^k}{\left(2k+3\right)!}$$
+///
+/// The series is used for $\left|z\right|<1$ to avoid the cancellation.
+pub fn stumpff_s(z: f64) -> f64 {
+    if z.abs() < 1.0 {
+        stumpff_series(z, 3)
+    } else if z > 0.0 {
+        let root = z.sqrt();
+        (root - root.sin()) / (z * root)
+    } else {
+        let root = (-z).sqrt();
+        (root.sinh() - root) / (-z * root)
+    }
+}
+
+/// Series $\sum_k\frac{\left(-z\right)^k}{\left(2k+n\right)!}$ of the Stumpff functions.
+fn stumpff_series(z: f64, n: usize) -> f64 {
+    let mut term = 1.0 / (1..=n).product::<usize>() as f64;
+    let mut sum = term;
+    for k in 1..12 {
+        term *= -z / ((2 * k + n - 1) * (2 * k + n)) as f64;
+        sum += term;
+    }
+    sum
+}
+
+/// Check that a position is non-zero and finite.
+fn check_position(position: &Vector<f64>) -> Result<f64, TwoBodyError> {
+    let norm = position.norm();
+    if norm > 0.0 && norm.is_finite() {
+        Ok(norm)
+    } else {
+        Err(TwoBodyError::InvalidPosition)
+    }
+}
+
+/// Check that a gravitational parameter is strictly positive and finite.
+fn check_gravitational_parameter(gm: f64) -> Result<(), TwoBodyError> {
+    if gm > 0.0 && gm.is_finite() {
+        Ok(())
+    } else {
+        Err(TwoBodyError::InvalidGravitationalParameter(gm))
+    }
+}
+
+/// Propagate a state of the two-body problem by a duration, with the universal variable
+/// formulation valid for elliptic, parabolic and hyperbolic orbits. The duration can be negative
+/// to propagate backward. The state after the duration is returned as position and velocity.
+///
+/// ## Expression
+///
+/// The universal anomaly $\chi$ is the root of the universal Kepler's equation,
+///
+/// $$\sqrt{\mu}\Delta t=\frac{r_0v_{r0}}{\sqrt{\mu}}\chi^2C\left(\alpha\chi^2\right)+\left(1-\alpha
+/// r_0\right)\chi^3S\left(\alpha\chi^2\right)+r_0\chi$$
+///
+/// where $\alpha=\frac{2}{r_0}-\frac{v_0^2}{\mu}$ is the inverse of the semi-major axis,
+/// $v_{r0}$ is the radial velocity, and $C$ and $S$ are the [Stumpff functions][stumpff_c]. The
+/// state is then given by the Lagrange coefficients,
+///
+/// $$\boldsymbol{r}=f\boldsymbol{r}_0+g\boldsymbol{v}_0,\quad\boldsymbol{v}=\dot{f}\boldsymbol{r}_0
+/// +\dot{g}\boldsymbol{v}_0$$
+///
+/// $$f=1-\frac{\chi^2}{r_0}C,\quad g=\Delta t-\frac{\chi^3}{\sqrt{\mu}}S,\quad\dot{f}=
+/// \frac{\sqrt{\mu}}{rr_0}\left(\alpha\chi^3S-\chi\right),\quad\dot{g}=1-\frac{\chi^2}{r}C$$
+///
+/// ## Method
+///
+/// The right-hand side of the universal Kepler's equation is increasing in $\chi$ with the
+/// derivative $r$, so the root is bracketed from zero and solved with a Newton's method safeguarded
+/// by bisection.
+pub fn propagate(
+    position: &Vector<f64>,
+    velocity: &Vector<f64>,
+    time: f64,
+    gm: f64,
+) -> Result<(Vector<f64>, Vector<f64>), TwoBodyError> {
+    check_gravitational_parameter(gm)?;
+    let r0 = check_position(position)?;
+    if !time.is_finite() {
+        return Err(TwoBodyError::InvalidTimeOfFlight(time));
+    }
+    if time == 0.0 {
+        return Ok((*position, *velocity));
+    }
+    let sqrt_gm = gm.sqrt();
+    let alpha = 2.0 / r0 - velocity.norm_squared() / gm;
+    let radial = position.dot(velocity) / sqrt_gm;
+
+    let equation = |chi: f64| {
+        let z = alpha * chi * chi;
+        let (c, s) = (stumpff_c(z), stumpff_s(z));
+        let residual = radial * chi * chi * c + (1.0 - alpha * r0) * chi.powi(3) * s + r0 * chi
+            - sqrt_gm * time;
+        let derivative = radial * chi * (1.0 - z * s) + (1.0 - alpha * r0) * chi * chi * c + r0;
+        (residual, derivative)
+    };
+
+    // Starting guesses of Vallado, Fundamentals of Astrodynamics and Applications, algorithm 8.
+    let sign = time.signum();
+    let start = if alpha * r0 > 1e-6 {
+        sqrt_gm * alpha * time
+    } else if alpha * r0 < -1e-6 {
+        let a = 1.0 / alpha;
+        let argument = -2.0 * gm * alpha * time
+            / (position.dot(velocity) + sign * (-gm * a).sqrt() * (1.0 - r0 * alpha));
+        if argument > 1.0 && argument.is_finite() {
+            sign * (-a).sqrt() * argument.ln()
+        } else {
+            sqrt_gm * time / r0
+        }
+    } else {
+        sqrt_gm * time / r0
+    };
+    // The residual is negative at zero for a positive duration and positive otherwise, the other
+    // end of the bracket is searched by doubling the starting guess.
+    if start == 0.0 || !start.is_finite() {
+        return Err(TwoBodyError::NotConverged { last_value: start });
+    }
+    let mut end = start;
+    let mut doublings = 0;
+    while sign * equation(end).0 < 0.0 {
+        end *= 2.0;
+        doublings += 1;
+        if doublings > PROPAGATION_MAX_DOUBLINGS {
+            return Err(TwoBodyError::NotConverged { last_value: end });
+        }
+    }
+    let (lower, upper) = if sign > 0.0 { (0.0, end) } else { (end, 0.0) };
+    let chi = safeguarded_newton(equation, start, lower, upper)
+        .map_err(|last_value| TwoBodyError::NotConverged { last_value })?;
+
+    let z = alpha * chi * chi;
+    let (c, s) = (stumpff_c(z), stumpff_s(z));
+    let f = 1.0 - chi * chi / r0 * c;
+    let g = time - chi.powi(3) / sqrt_gm * s;
+    let new_position = position * f + velocity * g;
+    let r = new_position.norm();
+    let f_dot = sqrt_gm / (r * r0) * (alpha * chi.powi(3) * s - chi);
+    let g_dot = 1.0 - chi * chi / r * c;
+    let new_velocity = position * f_dot + velocity * g_dot;
+    Ok((new_position, new_velocity))
+}
+
+/// Solve Lambert's problem: find the orbits connecting two positions in a given time of flight.
+///
+/// The transfer is prograde or retrograde with respect to the $z$-axis. The solution with zero
+/// revolution is computed first, followed by the two solutions for each number of complete
+/// revolutions up to the maximum when the time of flight allows it. The solutions with the same
+/// number of revolutions are ordered by increasing universal variable $z$, i.e. decreasing
+/// semi-major axis.
+///
+/// ## Expression
+///
+/// With the transfer angle $\Delta\nu$ and $A=\sin\Delta\nu\sqrt{\frac{r_1r_2}{1-\cos\Delta\nu}}$,
+/// the time of flight as a function of the universal variable $z=\alpha\chi^2$ is,
+///
+/// $$\sqrt{\mu}\Delta t=\left(\frac{y}{C}\right)^{\frac{3}{2}}S+A\sqrt{y},\quad y=r_1+r_2+A
+/// \frac{zS-1}{\sqrt{C}}$$
+///
+/// and the velocities are given by the Lagrange coefficients $f=1-\frac{y}{r_1}$,
+/// $g=A\sqrt{\frac{y}{\mu}}$ and $\dot{g}=1-\frac{y}{r_2}$,
+///
+/// $$\boldsymbol{v}_1=\frac{\boldsymbol{r}_2-f\boldsymbol{r}_1}{g},\quad\boldsymbol{v}_2=
+/// \frac{\dot{g}\boldsymbol{r}_2-\boldsymbol{r}_1}{g}$$
+///
+/// ## Method
+///
+/// The time of flight is increasing in $z$ below $\left(2\pi\right)^2$, where the solution without
+/// revolution is bisected. For $N$ revolutions, $z$ lies in $\left[\left(2\pi N\right)^2,
+/// \left(2\pi\left(N+1\right)\right)^2\right]$ where the time of flight tends to infinity at both
+/// ends: its minimum is found by a golden-section search and a solution is bisected on each side
+/// when the minimum is below the time of flight.
+pub fn lambert(
+    position_departure: &Vector<f64>,
+    position_arrival: &Vector<f64>,
+    time_of_flight: f64,
+    gm: f64,
+    prograde: bool,
+    max_revolutions: usize,
+) -> Result<Vec<LambertSolution>, TwoBodyError> {
+    check_gravitational_parameter(gm)?;
+    let r1 = check_position(position_departure)?;
+    let r2 = check_position(position_arrival)?;
+    if !(time_of_flight > 0.0 && time_of_flight.is_finite()) {
+        return Err(TwoBodyError::InvalidTimeOfFlight(time_of_flight));
+    }
+    let cross = position_departure.cross(position_arrival);
+    if cross.norm() <= 1e-12 * r1 * r2 {
+        return Err(TwoBodyError::CollinearPositions);
+    }
+    let cos_angle = (position_departure.dot(position_arrival) / (r1 * r2)).clamp(-1.0, 1.0);
+    let short_way = (cross[2] >= 0.0) == prograde;
+    let sin_angle = (1.0 - cos_angle * cos_angle).sqrt() * if short_way { 1.0 } else { -1.0 };
+    let a = sin_angle * (r1 * r2 / (1.0 - cos_angle)).sqrt();
+    let sqrt_gm = gm.sqrt();
+
+    let y = |z: f64| r1 + r2 + a * (z * stumpff_s(z) - 1.0) / stumpff_c(z).sqrt();
+    // Time of flight as a function of the universal variable. Where y is negative the transfer
+    // does not exist, and the time of flight is continued by zero, its limit when y vanishes.
+    let time = |z: f64| {
+        let c = stumpff_c(z);
+        if c <= 0.0 {
+            return f64::INFINITY;
+        }
+        let y = y(z);
+        if y < 0.0 {
+            return 0.0;
+        }
+        ((y / c).powf(1.5) * stumpff_s(z) + a * y.sqrt()) / sqrt_gm
+    };
+    let solution = |z: f64, revolutions: usize| {
+        let y = y(z);
+        let f = 1.0 - y / r1;
+        let g = a * (y / gm).sqrt();
+        let g_dot = 1.0 - y / r2;
+        LambertSolution {
+            revolutions,
+            velocity_departure: (position_arrival - position_departure * f) / g,
+            velocity_arrival: (position_arrival * g_dot - position_departure) / g,
+        }
+    };
+
+    let mut solutions = vec![];
+    let mut lower = -TAU * TAU;
+    let mut iterations = 0;
+    while time(lower) > time_of_flight {
+        lower *= 2.0;
+        iterations += 1;
+        if iterations > LAMBERT_MAX_ITERATIONS {
+            return Err(TwoBodyError::NotConverged { last_value: lower });
+        }
+    }
+    let z = bisection(|z| time(z) - time_of_flight, lower, TAU * TAU)?;
+    solutions.push(solution(z, 0));
+
+    for revolutions in 1..=max_revolutions {
+        let lower = (TAU * revolutions as f64).powi(2);
+        let upper = (TAU * (revolutions + 1) as f64).powi(2);
+        let minimum = golden_section(time, lower, upper);
+        if time(minimum) > time_of_flight {
+            break;
+        }
+        let z = bisection(|z| time_of_flight - time(z), lower, minimum)?;
+        solutions.push(solution(z, revolutions));
+        let z = bisection(|z| time(z) - time_of_flight, minimum, upper)?;
+        solutions.push(solution(z, revolutions));
+    }
+    Ok(solutions)
+}
+
+/// Bisection of an increasing function between a negative and a positive value.
+fn bisection(
+    function: impl Fn(f64) -> f64,
+    mut lower: f64,
+    mut upper: f64,
+) -> Result<f64, TwoBodyError> {
+    for _ in 0..LAMBERT_MAX_ITERATIONS {
+        let middle = (lower + upper) / 2.0;
+        if upper - lower <= LAMBERT_TOLERANCE * middle.abs().max(1.0) {
+            return Ok(middle);
+        }
+        if function(middle) < 0.0 {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+    }
+    Err(TwoBodyError::NotConverged {
+        last_value: (lower + upper) / 2.0,
+    })
+}
+
+/// Golden-section search of the minimum of a unimodal function.
+fn golden_section(function: impl Fn(f64) -> f64, mut lower: f64, mut upper: f64) -> f64 {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut left = upper - ratio * (upper - lower);
+    let mut right = lower + ratio * (upper - lower);
+    let (mut value_left, mut value_right) = (function(left), function(right));
+    for _ in 0..LAMBERT_MAX_ITERATIONS {
+        if upper - lower <= LAMBERT_TOLERANCE * upper {
+            break;
+        }
+        if value_left < value_right {
+            upper = right;
+            right = left;
+            value_right = value_left;
+            left = upper - ratio * (upper - lower);
+            value_left = function(left);
+        } else {
+            lower = left;
+            left = right;
+            value_left = value_right;
+            right = lower + ratio * (upper - lower);
+            value_right = function(right);
+        }
+    }
+    (lower + upper) / 2.0
+}
@@ -0,0 +1,166 @@
+use crate::{directions, distances, dot_products, Bvh, Mesh, Vector, Vectors};
+use na::{DMatrix, RealField};
+
+/// Options of the computation of the [view factors][view_factors].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewFactorOptions<T>
+where
+    T: RealField,
+{
+    /// Pairs of facets whose distance is larger than this ratio times the square root of the
+    /// largest area of the two facets use the analytic approximation. Closer pairs are integrated
+    /// numerically.
+    pub distance_ratio: T,
+    /// Number of subdivisions of each edge of the facets for the numerical integration. Each facet
+    /// is split into the square of this number of triangles.
+    pub subdivisions: usize,
+    /// Whether to check that the view between two facets is not blocked by other facets.
+    pub occlusion: bool,
+}
+
+impl<T> Default for ViewFactorOptions<T>
+where
+    T: RealField,
+{
+    fn default() -> Self {
+        Self {
+            distance_ratio: na::convert(3.0),
+            subdivisions: 4,
+            occlusion: true,
+        }
+    }
+}
+
+/// Centroids of the triangles obtained by splitting each edge of a triangle in `subdivisions`
+/// segments.
+fn subdivide<T>(triangle: &[Vector<T>; 3], subdivisions: usize) -> Vec<Vector<T>>
+where
+    T: RealField,
+{
+    let size: T = na::convert(subdivisions as f64);
+    let three: T = na::convert(3.0);
+    let edge_1 = (triangle[1] - triangle[0]) / size;
+    let edge_2 = (triangle[2] - triangle[0]) / size;
+    let mut centroids = vec![];
+    for i in 0..subdivisions {
+        for j in 0..subdivisions - i {
+            let (u, v): (T, T) = (na::convert(i as f64), na::convert(j as f64));
+            let corner = triangle[0] + edge_1 * u + edge_2 * v;
+            centroids.push(corner + (edge_1 + edge_2) / three);
+            if i + j + 1 < subdivisions {
+                centroids.push(corner + (edge_1 + edge_2) * (three - T::one()) / three);
+            }
+        }
+    }
+    centroids
+}
+
+/// View factors between the facets of a triangular mesh.
+///
+/// ## Definition
+///
+/// The view factor $F_{ij}$ is the fraction of the radiation leaving the facet $i$ that reaches
+/// the facet $j$,
+///
+/// $$F_{ij}=\frac{1}{A_i}\int_{A_i}\int_{A_j}\frac{\cos\theta_i\cos\theta_j}{\pi r^2}dA_jdA_i$$
+///
+/// where $\theta_i$ and $\theta_j$ are the angles between the normals of the facets and the line
+/// of length $r$ joining them. The view factors respect the reciprocity relation
+/// $A_iF_{ij}=A_jF_{ji}$.
+///
+/// For distant facets, the integrand is considered constant and evaluated at the centroids of the
+/// facets, with [`distances`], [`directions`] and [`dot_products`] between each facet and the
+/// following ones,
+///
+/// $$F_{ij}\approx\frac{\cos\theta_i\cos\theta_jA_j}{\pi r^2}$$
+///
+/// Close facets are subdivided and the integral is summed over the pairs of sub-facets. Facets
+/// that do not face each other have null view factors. When the occlusion is enabled, the view
+/// factor is null if the segment between the centroids of the facets intersects another facet.
+///
+/// The output is a dense $n\times n$ matrix with the facets $i$ as rows and the facets $j$ as
+/// columns. Its memory grows with the square of the number of facets: 100,000 facets of `f64`
+/// take 80 GB.
+pub fn view_factors<T>(
+    vertices: &Vectors<T>,
+    faces: &[[usize; 3]],
+    options: &ViewFactorOptions<T>,
+) -> DMatrix<T>
+where
+    T: RealField,
+{
+    let mesh = Mesh::new(vertices.clone(), faces.to_vec());
+    let size = mesh.number_faces();
+    let normals = mesh.face_normals();
+    let areas = mesh.face_areas();
+    let centroids = mesh.face_centroids();
+    let bvh = if options.occlusion {
+        Some(Bvh::from_mesh(&mesh))
+    } else {
+        None
+    };
+    let max_distance = T::one() - T::default_epsilon().sqrt();
+    let subdivisions = options.subdivisions.max(1);
+    let number_subfacets: T = na::convert((subdivisions * subdivisions) as f64);
+    let subfacets = faces
+        .iter()
+        .map(|face| {
+            let triangle = [
+                vertices.column(face[0]).into_owned(),
+                vertices.column(face[1]).into_owned(),
+                vertices.column(face[2]).into_owned(),
+            ];
+            subdivide(&triangle, subdivisions)
+        })
+        .collect::<Vec<_>>();
+
+    let mut factors = DMatrix::zeros(size, size);
+    for i in 0..size {
+        let centroid = centroids.column(i).into_owned();
+        let normal = normals.column(i).into_owned();
+        let count = size - i - 1;
+        let origins = Vectors::from_fn(count, |row, _| centroid[row]);
+        let following = centroids.columns(i + 1, count).into_owned();
+        let lengths = distances(&origins, &following);
+        let lines = directions(&origins, &following);
+        let cosines_i = dot_products(&Vectors::from_fn(count, |row, _| normal[row]), &lines);
+        let cosines_j = -dot_products(&normals.columns(i + 1, count).into_owned(), &lines);
+
+        for (k, j) in (i + 1..size).enumerate() {
+            let (length, cosine_i, cosine_j) = (lengths[k], cosines_i[k], cosines_j[k]);
+            if cosine_i <= T::zero() || cosine_j <= T::zero() {
+                continue;
+            }
+            if let Some(bvh) = &bvh {
+                let line = centroids.column(j) - centroid;
+                if bvh.any_hit(&centroid, &line, Some(max_distance)) {
+                    continue;
+                }
+            }
+
+            // Integral of the view factor over the two facets.
+            let integral = if length > options.distance_ratio * areas[i].max(areas[j]).sqrt() {
+                cosine_i * cosine_j * areas[i] * areas[j] / (T::pi() * length.powi(2))
+            } else {
+                let subareas = areas[i] * areas[j] / number_subfacets.powi(2);
+                let normal_j = normals.column(j);
+                let mut integral = T::zero();
+                for point_i in subfacets[i].iter() {
+                    for point_j in subfacets[j].iter() {
+                        let line = point_j - point_i;
+                        let cosine_i = normal.dot(&line);
+                        let cosine_j = -normal_j.dot(&line);
+                        if cosine_i > T::zero() && cosine_j > T::zero() {
+                            integral +=
+                                cosine_i * cosine_j / (T::pi() * line.norm_squared().powi(2));
+                        }
+                    }
+                }
+                integral * subareas
+            };
+            factors[(i, j)] = integral / areas[i];
+            factors[(j, i)] = integral / areas[j];
+        }
+    }
+    factors
+}
@@ -20,16 +20,16 @@ pub use crate::core::*;
 
 extern crate alga;
 extern crate itertools;
-extern crate log;
 extern crate nalgebra as na;
 extern crate num_traits;
+extern crate simba;
 extern crate simplelog;
 #[macro_use]
 extern crate approx;
 
 use na::{
-    Dynamic, Matrix, Matrix3xX, MatrixSlice3xX, RowDVector, SliceStorage, UnitVector3, Vector3,
-    VectorSlice3, U1, U3,
+    Dynamic, Matrix, Matrix3xX, MatrixSlice3xX, Rotation3, RowDVector, SliceStorage, UnitVector3,
+    Vector3, VectorSlice3, U1, U3,
 };
 
 /// Type alias for [`RowDVector`]. The matrix has 1 row and X columns.
@@ -58,3 +58,6 @@ pub type VectorsSlice<'a, T> = MatrixSlice3xX<'a, T>;
 
 /// Type alias for [`Matrix3xX`]. The matrix has 3 rows and X columns.
 pub type VectorsGeneric<T, S> = Matrix<T, U3, Dynamic, S>;
+
+/// Type alias for [`Rotation3`]. The rotation matrix has 3 rows and 3 columns.
+pub type Rotation<T> = Rotation3<T>;
@@ -0,0 +1,127 @@
+use std::f64::consts::PI;
+use tool::{Angle, AngleError, List, Vector, Vectors, DEG2RAD, MAX_SEXAGESIMAL_DECIMALS, TAU};
+
+#[test]
+fn normalization() {
+    assert!(relative_eq!(
+        Angle::from_degrees(-90.0).normalized().degrees(),
+        270.0,
+        epsilon = 1e-12
+    ));
+    assert!(relative_eq!(
+        Angle::from_degrees(725.0).normalized().degrees(),
+        5.0,
+        epsilon = 1e-12
+    ));
+    assert_eq!(Angle::new(-1e-18).normalized().radians(), 0.0);
+    assert_eq!(Angle::new(TAU).normalized().radians(), 0.0);
+    assert_eq!(Angle::new(PI).normalized_signed().radians(), PI);
+    assert_eq!(Angle::new(-PI).normalized_signed().radians(), PI);
+    assert!(relative_eq!(
+        Angle::from_degrees(270.0).normalized_signed().degrees(),
+        -90.0,
+        epsilon = 1e-12
+    ));
+}
+
+#[test]
+fn difference() {
+    let difference = Angle::from_degrees(10.0).difference(&Angle::from_degrees(350.0));
+    assert!(relative_eq!(difference.degrees(), 20.0, epsilon = 1e-12));
+
+    let difference = Angle::from_degrees(350.0).difference(&Angle::from_degrees(10.0));
+    assert!(relative_eq!(difference.degrees(), -20.0, epsilon = 1e-12));
+}
+
+#[test]
+fn unwrap() {
+    let continuous = List::from_fn(50, |_, index| 0.3 * index as f64 - 2.0);
+    let wrapped = continuous.map(|angle| Angle::new(angle).normalized_signed().radians());
+
+    let unwrapped = Angle::new(wrapped.clone()).unwrapped();
+
+    assert!(wrapped.iter().any(|&angle| angle < 0.0));
+    assert!(relative_eq!(
+        *unwrapped.value(),
+        continuous,
+        epsilon = 1e-12
+    ));
+    assert_eq!(Angle::new(List::zeros(0)).unwrapped().len(), 0);
+}
+
+#[test]
+fn sexagesimal() {
+    let angle = Angle::from_dms("+45°30'15.5\"").unwrap();
+    assert!(relative_eq!(
+        angle.degrees(),
+        45.0 + 30.0 / 60.0 + 15.5 / 3600.0,
+        epsilon = 1e-12
+    ));
+    assert_eq!(angle.to_dms(2), "45°30'15.50\"");
+    assert_eq!((-angle).to_dms(0), "-45°30'16\"");
+    assert_eq!(Angle::from_dms("-0 30").unwrap().to_dms(1), "-0°30'00.0\"");
+    assert_eq!(
+        Angle::from_degrees(29.999_999_9).to_dms(3),
+        "30°00'00.000\""
+    );
+
+    let right_ascension = Angle::from_hms("12h34m56.789s").unwrap();
+    assert_eq!(Angle::from_hms("12:34:56.789").unwrap(), right_ascension);
+    assert_eq!(right_ascension.to_hms(3), "12h34m56.789s");
+    assert_eq!(Angle::from_degrees(-15.0).to_hms(0), "23h00m00s");
+    assert_eq!(
+        right_ascension.to_hms(30),
+        right_ascension.to_hms(MAX_SEXAGESIMAL_DECIMALS)
+    );
+    assert_eq!(angle.to_dms(usize::MAX), "45°30'15.500000000\"");
+
+    assert_eq!(
+        Angle::from_dms("12°61'"),
+        Err(AngleError::OutOfRange("12°61'".to_string()))
+    );
+    for string in &["", "12x30", "1 2 3 4", "--12", "12h30m"] {
+        assert_eq!(
+            Angle::from_dms(string),
+            Err(AngleError::Format(string.to_string()))
+        );
+    }
+}
+
+#[test]
+fn geometry() {
+    let vector = Vector::new(0.0, 1.0, 1.0);
+    let (azimuth, elevation, radius) = tool::cart_to_sph_angles(&vector);
+
+    assert!(relative_eq!(azimuth.degrees(), 90.0, epsilon = 1e-12));
+    assert!(relative_eq!(elevation.degrees(), 45.0, epsilon = 1e-12));
+    assert!(relative_eq!(
+        tool::sph_angles_to_cart(azimuth, elevation, radius),
+        vector,
+        epsilon = 1e-12
+    ));
+
+    let vectors = Vectors::from_columns(&[vector, Vector::new(-1.0, 0.0, 0.0), Vector::zeros()]);
+    let (azimuths, elevations, radii) = tool::cart_to_sph_angle_lists(&vectors);
+    assert!(relative_eq!(
+        azimuths.at(1).degrees(),
+        180.0,
+        epsilon = 1e-12
+    ));
+    assert!(relative_eq!(
+        elevations.at(0).degrees(),
+        45.0,
+        epsilon = 1e-12
+    ));
+    assert!(relative_eq!(
+        tool::sph_angle_lists_to_cart(&azimuths, &elevations, &radii),
+        vectors,
+        epsilon = 1e-12
+    ));
+
+    let angle: Angle = Angle::direct(&Vector::x(), &Vector::y(), &-Vector::z());
+    assert!(relative_eq!(
+        angle.radians(),
+        270.0 * DEG2RAD,
+        epsilon = 1e-12
+    ));
+}
@@ -0,0 +1,81 @@
+use super::temp_path;
+use std::error::Error;
+use tool::{Body, BodyDatabase, BodyError, Vector, DAY, DEG2RAD, GRAVITATIONAL_CONSTANT, HOUR};
+
+#[test]
+fn builtin() {
+    let database = BodyDatabase::builtin();
+
+    assert_eq!(database.bodies().len(), 11);
+    let earth = database.get("earth").unwrap();
+    assert_eq!(earth.name, "Earth");
+    assert!(relative_eq!(earth.mass(), 5.972e24, max_relative = 1e-3));
+    assert!(relative_eq!(
+        earth.gm,
+        earth.mass() * GRAVITATIONAL_CONSTANT
+    ));
+    assert_eq!(earth.radii[0], 6_378_136.6);
+    assert!(relative_eq!(
+        earth.rotation_period,
+        23.0 * HOUR + 56.0 * 60.0 + 4.0905,
+        epsilon = 1e-2
+    ));
+    assert!(database.get("Venus").unwrap().rotation_period < 0.0);
+    assert!(relative_eq!(
+        database.get("JUPITER").unwrap().rotation_period,
+        9.925 * HOUR
+    ));
+    assert!(database.get("Vulcan").is_none());
+}
+
+#[test]
+fn rotation_model() {
+    let earth = BodyDatabase::builtin().get("Earth").unwrap().clone();
+    let model = earth.rotation_model();
+
+    assert!(relative_eq!(
+        model.prime_meridian_at(0.0),
+        190.147 * DEG2RAD
+    ));
+    assert!(relative_eq!(
+        model.prime_meridian_at(DAY) - model.prime_meridian_at(0.0),
+        360.985_623_5 * DEG2RAD,
+        epsilon = 1e-6
+    ));
+}
+
+#[test]
+fn json() {
+    let path = temp_path("bodies.json");
+    let mut database = BodyDatabase::builtin();
+    let bennu = Body {
+        name: "Bennu".to_string(),
+        gm: 4.892,
+        radii: Vector::new(282.5, 267.5, 254.0),
+        mean_radius: 244.89,
+        rotation_period: 4.296_057 * HOUR,
+        pole_right_ascension: 85.45 * DEG2RAD,
+        pole_declination: -60.36 * DEG2RAD,
+        prime_meridian: 150.57 * DEG2RAD,
+    };
+    let mut moon = database.get("Moon").unwrap().clone();
+    moon.gm *= 2.0;
+
+    let mut user = BodyDatabase::new();
+    user.insert(bennu.clone());
+    user.insert(moon.clone());
+    user.write_json_file(&path).unwrap();
+    database.load_json_file(&path).unwrap();
+
+    assert_eq!(database.bodies().len(), 12);
+    assert_eq!(database.get("bennu"), Some(&bennu));
+    assert_eq!(database.get("moon"), Some(&moon));
+    assert_eq!(BodyDatabase::from_json_file(&path).unwrap(), user);
+    assert!(matches!(
+        database.load_json_str("[{\"name\": \"Bennu\"}]"),
+        Err(BodyError::Json(_))
+    ));
+    let error = BodyDatabase::from_json_file(temp_path("missing.json")).unwrap_err();
+    assert!(matches!(error, BodyError::Io(_)));
+    assert!(error.source().is_some());
+}
@@ -0,0 +1,94 @@
+use na::{ComplexField, RealField};
+use tool::{Dual, Vector};
+
+/// Derivative of a function at a value by automatic differentiation.
+fn derivative(function: impl Fn(Dual<f64>) -> Dual<f64>, value: f64) -> f64 {
+    function(Dual::variable(value)).dual
+}
+
+#[test]
+fn arithmetic() {
+    let x = Dual::new(3.0, 1.0);
+    let y = Dual::new(2.0, -0.5);
+    assert_eq!((x + y).dual, 0.5);
+    assert_eq!((x - y).dual, 1.5);
+    assert_eq!((x * y).dual, 2.0 - 1.5);
+    assert!(relative_eq!((x / y).dual, (2.0 + 1.5) / 4.0));
+    assert_eq!(-x, Dual::new(-3.0, -1.0));
+    assert_eq!((x % y).real, 1.0);
+    assert_eq!((x % y).dual, 1.5);
+
+    let mut z = x;
+    z *= y;
+    z += Dual::constant(1.0);
+    assert_eq!(z.real, 7.0);
+    assert_eq!(z.dual, 0.5);
+
+    // Comparisons only involve the real parts.
+    assert_eq!(Dual::new(1.0, 2.0), Dual::constant(1.0));
+    assert!(x > y);
+    assert_eq!(format!("{}", y), "2 + -0.5ε");
+}
+
+/// Function and its expected derivative.
+type Case = (fn(Dual<f64>) -> Dual<f64>, f64);
+
+#[test]
+fn elementary_functions() {
+    let x: f64 = 0.3;
+    let cases: Vec<Case> = vec![
+        (|y| y.sin(), x.cos()),
+        (|y| y.cos(), -x.sin()),
+        (|y| y.tan(), 1.0 / x.cos().powi(2)),
+        (|y| y.asin(), 1.0 / (1.0 - x * x).sqrt()),
+        (|y| y.acos(), -1.0 / (1.0 - x * x).sqrt()),
+        (|y| y.atan(), 1.0 / (1.0 + x * x)),
+        (|y| y.sinh(), x.cosh()),
+        (|y| y.tanh(), 1.0 - x.tanh().powi(2)),
+        (|y| y.atanh(), 1.0 / (1.0 - x * x)),
+        (|y| y.exp(), x.exp()),
+        (|y| y.exp2(), x.exp2() * 2f64.ln()),
+        (|y| y.ln(), 1.0 / x),
+        (|y| y.log10(), 1.0 / (x * 10f64.ln())),
+        (|y| y.sqrt(), 0.5 / x.sqrt()),
+        (|y| y.cbrt(), 1.0 / (3.0 * x.cbrt().powi(2))),
+        (|y| y.powi(4), 4.0 * x.powi(3)),
+        (|y| y.powf(Dual::constant(2.5)), 2.5 * x.powf(1.5)),
+        (|y| y.powf(y), x.powf(x) * (x.ln() + 1.0)),
+        (|y| y.recip(), -1.0 / (x * x)),
+        (|y| (-y).abs(), 1.0),
+        (|y| y.atan2(Dual::constant(2.0)), 2.0 / (x * x + 4.0)),
+        (|y| y.hypot(Dual::constant(0.4)), x / x.hypot(0.4)),
+    ];
+    for (function, expected) in cases {
+        assert!(relative_eq!(
+            derivative(function, x),
+            expected,
+            max_relative = 1e-12
+        ));
+    }
+}
+
+#[test]
+fn real_field() {
+    // A generic function of nalgebra is differentiated: the derivative of the norm along a
+    // direction is the projection of the direction on the unit vector.
+    let point = Vector::new(1.0, 2.0, 2.0);
+    let direction = Vector::new(0.0, 1.0, 0.0);
+    let vector = point.zip_map(&direction, Dual::new);
+    assert_eq!(vector.norm().real, 3.0);
+    assert!(relative_eq!(vector.norm().dual, 2.0 / 3.0));
+
+    let constant: Dual<f64> = na::convert(2.5);
+    assert_eq!(constant, Dual::constant(2.5));
+    assert_eq!(constant.dual, 0.0);
+    assert_eq!(na::try_convert::<Dual<f64>, f64>(constant), Some(2.5));
+    assert_eq!(na::try_convert::<Dual<f64>, f64>(Dual::variable(2.5)), None);
+    assert!(!Dual::new(1.0, f64::NAN).is_finite());
+    assert!(relative_eq!(
+        Dual::new(1.0, 2.0),
+        Dual::new(1.0, 2.0 + 1e-14),
+        max_relative = Dual::constant(1e-12)
+    ));
+    assert!(!relative_eq!(Dual::new(1.0, 2.0), Dual::new(1.0, 2.1)));
+}
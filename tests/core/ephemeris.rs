@@ -0,0 +1,77 @@
+use tool::{Epoch, Planet, TimeScale, Vector, ASTRONAUMICAL_UNIT, DAY, DEG2RAD};
+
+/// Heliocentric ecliptic longitude in degrees, latitude in degrees and distance in au.
+fn spherical(position: &Vector<f64>) -> (f64, f64, f64) {
+    let spherical = tool::cart_to_sph_vector(position);
+    (
+        spherical[0].rem_euclid(std::f64::consts::TAU) / DEG2RAD,
+        spherical[1] / DEG2RAD,
+        spherical[2] / ASTRONAUMICAL_UNIT,
+    )
+}
+
+#[test]
+fn meeus() {
+    // Meeus, Astronomical Algorithms, example 33.a, VSOP87 positions at 1992-12-20T00:00 TDB
+    // referred to the equinox of date, with the precession in longitude of -353.6 arcseconds to
+    // J2000 added back.
+    let epoch = Epoch::from_iso8601("1992-12-20", TimeScale::TDB).unwrap();
+    let precession = 353.6 / 3600.0;
+
+    let (longitude, latitude, distance) = spherical(&Planet::Venus.state(&epoch).0);
+    assert!(relative_eq!(
+        longitude,
+        26.114_28 + precession,
+        epsilon = 1e-2
+    ));
+    assert!(relative_eq!(latitude, -2.620_70, epsilon = 1e-2));
+    assert!(relative_eq!(distance, 0.724_603, epsilon = 1e-4));
+
+    let (longitude, latitude, distance) = spherical(&Planet::EarthMoonBarycenter.state(&epoch).0);
+    assert!(relative_eq!(
+        longitude,
+        88.357_04 + precession,
+        epsilon = 1e-2
+    ));
+    assert!(relative_eq!(latitude, 0.0, epsilon = 1e-2));
+    assert!(relative_eq!(distance, 0.983_824, epsilon = 1e-4));
+}
+
+#[test]
+fn earth_j2000() {
+    // Heliocentric position of the Earth at J2000 in the ecliptic frame, from DE440.
+    let epoch = Epoch::from_julian_date(2_451_545.0, TimeScale::TDB);
+
+    let (position, velocity) = Planet::EarthMoonBarycenter.state(&epoch);
+
+    assert!(relative_eq!(
+        position / ASTRONAUMICAL_UNIT,
+        Vector::new(-0.177_1, 0.967_3, 0.0),
+        epsilon = 5e-4
+    ));
+    assert!(relative_eq!(
+        velocity.norm() / ASTRONAUMICAL_UNIT * DAY,
+        0.017_49,
+        epsilon = 1e-4
+    ));
+    assert!(position.dot(&velocity).abs() < 1e-2 * position.norm() * velocity.norm());
+}
+
+#[test]
+fn velocity_consistent() {
+    let epoch = Epoch::from_iso8601("2030-06-01", TimeScale::TDB).unwrap();
+    let step = 60.0;
+
+    for planet in Planet::ALL.iter() {
+        let (position, velocity) = planet.state(&epoch);
+        let elements = planet.elements(&epoch);
+        let before = planet.state(&(epoch - step)).0;
+        let after = planet.state(&(epoch + step)).0;
+
+        let difference = (after - before) / (2.0 * step) - velocity;
+        assert!(difference.norm() < 1e-4 * velocity.norm());
+        let distance = position.norm();
+        assert!(distance > elements.semi_major_axis * (1.0 - elements.eccentricity) * 0.999_999);
+        assert!(distance < elements.semi_major_axis * (1.0 + elements.eccentricity) * 1.000_001);
+    }
+}
@@ -0,0 +1,240 @@
+use tool::{Angle, EphemerisIoError, Epoch, TimeScale, Vector, ASTRONAUMICAL_UNIT, DAY, TAU};
+
+const HEADER: &str = "\
+*******************************************************************************
+Target body name: Earth (399)                     {source: DE441}
+Center body name: Sun (10)                        {source: DE441}
+*******************************************************************************
+";
+
+/// Circular motion of radius 1 au and period 1 year in km and km/s, at a Julian date.
+fn circular(julian_date: f64) -> (Vector<f64>, Vector<f64>) {
+    let radius = ASTRONAUMICAL_UNIT * 1e-3;
+    let rate = TAU / (365.25 * DAY);
+    let angle = rate * (julian_date - 2_459_215.5) * DAY;
+    (
+        Vector::new(angle.cos(), angle.sin(), 0.0) * radius,
+        Vector::new(-angle.sin(), angle.cos(), 0.0) * radius * rate,
+    )
+}
+
+fn julian_dates() -> Vec<f64> {
+    (0..5).map(|day| 2_459_215.5 + day as f64).collect()
+}
+
+/// Vector table of the circular motion in the default text format.
+fn vectors_text() -> String {
+    let mut content = format!(
+        "{}Output units    : KM-S\n{}            JDTDB\n   X     Y     Z\n   VX    VY    VZ\n\
+         *******************************************************************************\n$$SOE\n",
+        HEADER, HEADER
+    );
+    for julian_date in julian_dates() {
+        let (position, velocity) = circular(julian_date);
+        content += &format!(
+            "{:.9} = A.D. 2021-Jan-01 00:00:00.0000 TDB \n X ={:.16E} Y ={:.16E} Z ={:.16E}\n \
+             VX={:.16E} VY={:.16E} VZ={:.16E}\n LT= 4.99E+02 RG= 1.49E+08 RR= 0.0E+00\n",
+            julian_date,
+            position[0],
+            position[1],
+            position[2],
+            velocity[0],
+            velocity[1],
+            velocity[2]
+        );
+    }
+    content
+        + "$$EOE\n*******************************************************************************\n"
+}
+
+fn assert_parse_error(error: EphemerisIoError, expected_line: usize) {
+    match error {
+        EphemerisIoError::Parse { line, .. } => assert_eq!(line, expected_line),
+        _ => panic!("expected a parse error, got {}", error),
+    }
+}
+
+#[test]
+fn horizons_vectors_text() {
+    let table = tool::parse_horizons_vectors(&vectors_text()).unwrap();
+
+    assert_eq!(table.records().len(), 5);
+    assert_eq!(table.julian_dates()[4], 2_459_219.5);
+    let (position, velocity) = circular(2_459_217.5);
+    assert!(relative_eq!(
+        Vector::from(table.positions().column(2)),
+        position * 1e3,
+        max_relative = 1e-14
+    ));
+    assert!(relative_eq!(
+        Vector::from(table.velocities().unwrap().column(2)),
+        velocity * 1e3,
+        max_relative = 1e-14
+    ));
+
+    // Cubic Hermite interpolation of daily states.
+    let julian_date = 2_459_216.8;
+    let epoch = Epoch::from_julian_date(julian_date, TimeScale::TDB);
+    let (expected_position, expected_velocity) = circular(julian_date);
+    let (position, velocity) = table.interpolate(&epoch).unwrap();
+    assert!(relative_eq!(
+        position,
+        expected_position * 1e3,
+        epsilon = 1e2
+    ));
+    assert!(relative_eq!(
+        velocity.unwrap(),
+        expected_velocity * 1e3,
+        epsilon = 1e-2
+    ));
+
+    let epoch = Epoch::from_julian_date(2_459_220.0, TimeScale::TDB);
+    assert!(matches!(
+        table.interpolate(&epoch),
+        Err(EphemerisIoError::OutOfRange { .. })
+    ));
+}
+
+#[test]
+fn horizons_vectors_csv() {
+    let content = format!(
+        "{}Output units    : AU-D\n\
+         *******************************************************************************\n\
+         \x20           JDTDB,            Calendar Date (TDB),                      X,                      Y,                      Z,\n\
+         *******************************************************************************\n\
+         $$SOE\n\
+         2459215.500000000, A.D. 2021-Jan-01 00:00:00.0000,  1.0E+00,  2.0E+00,  0.0E+00,\n\
+         2459216.500000000, A.D. 2021-Jan-02 00:00:00.0000,  2.0E+00,  2.0E+00, -1.0E+00,\n\
+         $$EOE\n",
+        HEADER
+    );
+    let table = tool::parse_horizons_vectors(&content).unwrap();
+    assert!(table.velocities().is_none());
+    assert_eq!(
+        table.records()[1].position,
+        Vector::new(2.0, 2.0, -1.0) * ASTRONAUMICAL_UNIT
+    );
+
+    // Linear interpolation without velocities.
+    let epoch = Epoch::from_julian_date(2_459_215.75, TimeScale::TDB);
+    let (position, velocity) = table.interpolate(&epoch).unwrap();
+    assert!(relative_eq!(
+        position,
+        Vector::new(1.25, 2.0, -0.25) * ASTRONAUMICAL_UNIT,
+        max_relative = 1e-9
+    ));
+    assert!(velocity.is_none());
+}
+
+#[test]
+fn horizons_vectors_malformed() {
+    let content = vectors_text();
+    assert!(matches!(
+        tool::parse_horizons_vectors(&content.replace("$$SOE", "")),
+        Err(EphemerisIoError::Format(_))
+    ));
+
+    // The first record spans the lines 15 to 18. Invalid values are reported at their line, and
+    // missing values at the line of the Julian date.
+    let malformed = content.replacen("VX=", "VX=1.0.0", 1);
+    assert_parse_error(tool::parse_horizons_vectors(&malformed).unwrap_err(), 17);
+    let malformed = content.replacen(" VX=", " VW=", 1);
+    assert_parse_error(tool::parse_horizons_vectors(&malformed).unwrap_err(), 15);
+}
+
+#[test]
+fn horizons_observer() {
+    let content = format!(
+        "{}*******************************************************************************\n\
+         \x20Date__(UT)__HR:MN, , , R.A._____(ICRF), DEC______(ICRF), delta, deldot,\n\
+         *******************************************************************************\n\
+         $$SOE\n\
+         \x202021-Jan-01 00:00, , , 23 59 00.00, +10 30 00.0, 1.50000000000000, -1.2000000,\n\
+         \x202021-Jan-01 12:00,*,m, 00 01 00.00, +10 00 00.0, 1.60000000000000,  1.0000000,\n\
+         $$EOE\n",
+        HEADER
+    );
+    let table = tool::parse_horizons_observer(&content).unwrap();
+    assert_eq!(table.time_scale(), TimeScale::UTC);
+    let record = &table.records()[1];
+    assert!(relative_eq!(
+        record.julian_date,
+        2_459_216.0,
+        epsilon = 1e-9
+    ));
+    assert!(relative_eq!(
+        record.right_ascension.degrees(),
+        0.25,
+        epsilon = 1e-12
+    ));
+    assert!(relative_eq!(
+        record.declination.degrees(),
+        10.0,
+        epsilon = 1e-12
+    ));
+    assert_eq!(record.distance, Some(1.6 * ASTRONAUMICAL_UNIT));
+    assert_eq!(record.range_rate, Some(1e3));
+
+    // The interpolation crosses the origin of the right ascension.
+    let epoch = Epoch::from_iso8601("2021-01-01T09:00:00", TimeScale::UTC).unwrap();
+    let (right_ascension, declination) = table.interpolate(&epoch).unwrap();
+    assert!(relative_eq!(
+        right_ascension.degrees(),
+        0.125,
+        epsilon = 1e-9
+    ));
+    assert!(relative_eq!(declination.degrees(), 10.125, epsilon = 1e-9));
+
+    let malformed = content.replace("+10 00 00.0", "+10 00 61.0");
+    assert_parse_error(tool::parse_horizons_observer(&malformed).unwrap_err(), 10);
+    let malformed = content.replace("2021-Jan-01 12:00", "2021-Jxn-01 12:00");
+    assert_parse_error(tool::parse_horizons_observer(&malformed).unwrap_err(), 10);
+}
+
+#[test]
+fn mpc_observations() {
+    let content = "\
+00433       * C2021 01 02.50000 03 10 20.50 -05 06 07.2          12.5 V      568
+     K21A00A  C2021 01 03.25000 23 59 59.00 +89 00 00.0                      G96
+
+     K21A00A  S2021 01 03.25000 01 00 00.00 +10 00 00.0          18.2 G      C51
+     K21A00A  s2021 01 03.25000 1 - 3456.7890 +  123.4567 -  234.5678        C51
+";
+    let observations = tool::parse_mpc_observations(content).unwrap();
+
+    assert_eq!(observations.len(), 3);
+    let first = &observations[0];
+    assert_eq!(first.designation, "00433");
+    assert!(first.discovery);
+    assert_eq!(first.observation_type, 'C');
+    assert_eq!(first.julian_date, 2_459_217.0);
+    assert!(relative_eq!(
+        first.right_ascension.radians(),
+        Angle::from_hms("03 10 20.50").unwrap().radians()
+    ));
+    assert!(relative_eq!(
+        first.declination.degrees(),
+        -(5.0 + 6.0 / 60.0 + 7.2 / 3600.0),
+        epsilon = 1e-12
+    ));
+    assert_eq!(first.magnitude, Some(12.5));
+    assert_eq!(first.band, Some('V'));
+    assert_eq!(first.observatory, "568");
+
+    let second = &observations[1];
+    assert_eq!(second.designation, "K21A00A");
+    assert!(!second.discovery);
+    assert_eq!(second.julian_date, 2_459_217.75);
+    assert_eq!(second.magnitude, None);
+    assert_eq!(second.band, None);
+    assert_eq!(observations[2].observatory, "C51");
+
+    let malformed = content.replace("2021 01 03.25000 23", "2021 13 03.25000 23");
+    assert_parse_error(tool::parse_mpc_observations(&malformed).unwrap_err(), 2);
+    let malformed = content.replace("12.5 V", "12.x V");
+    assert_parse_error(tool::parse_mpc_observations(&malformed).unwrap_err(), 1);
+    assert_parse_error(
+        tool::parse_mpc_observations("00433 too short").unwrap_err(),
+        1,
+    );
+}
@@ -0,0 +1,144 @@
+use tool::{Epoch, EpochError, TimeScale, DAY};
+
+#[test]
+fn j2000() {
+    let epoch = Epoch::from_iso8601("2000-01-01T12:00:00", TimeScale::TT).unwrap();
+
+    assert_eq!(epoch.seconds_past_j2000(TimeScale::TT), 0.0);
+    assert_eq!(epoch.julian_date(TimeScale::TT), 2_451_545.0);
+    assert_eq!(epoch.modified_julian_date(TimeScale::TT), 51_544.5);
+    assert_eq!(epoch.seconds_past_j2000(TimeScale::TAI), -32.184);
+    assert_eq!(epoch.to_iso8601(TimeScale::UTC), "2000-01-01T11:58:55.816");
+    assert_eq!(
+        Epoch::from_julian_date(2_451_545.0, TimeScale::TT),
+        Epoch::from_modified_julian_date(51_544.5, TimeScale::TT)
+    );
+}
+
+#[test]
+fn time_scales() {
+    // SOFA, Time scale and calendar tools, example of section 4.
+    let epoch = Epoch::from_iso8601("2006-01-15T21:24:37.5Z", TimeScale::UTC).unwrap();
+
+    assert_eq!(epoch.to_iso8601(TimeScale::TAI), "2006-01-15T21:25:10.500");
+    assert_eq!(epoch.to_iso8601(TimeScale::TT), "2006-01-15T21:25:42.684");
+    assert!(relative_eq!(
+        epoch.seconds_past_j2000(TimeScale::TDB) - epoch.seconds_past_j2000(TimeScale::TT),
+        0.000_373_7,
+        epsilon = 3e-5
+    ));
+    assert!(relative_eq!(
+        epoch.modified_julian_date(TimeScale::UTC),
+        53_750.892_100_694_4,
+        epsilon = 1e-9
+    ));
+    for &scale in &[
+        TimeScale::UTC,
+        TimeScale::TAI,
+        TimeScale::TT,
+        TimeScale::TDB,
+    ] {
+        let round_trip =
+            Epoch::from_seconds_past_j2000(epoch.seconds_past_j2000(scale), scale) - epoch;
+        assert!(round_trip.abs() < 1e-7);
+    }
+}
+
+#[test]
+fn leap_second() {
+    let before = Epoch::from_iso8601("2016-12-31T23:59:59", TimeScale::UTC).unwrap();
+    let leap = Epoch::from_iso8601("2016-12-31T23:59:60.5", TimeScale::UTC).unwrap();
+    let after = Epoch::from_iso8601("2017-01-01T00:00:00", TimeScale::UTC).unwrap();
+
+    assert_eq!(after - before, 2.0);
+    assert_eq!(leap - before, 1.5);
+    assert_eq!(leap.to_iso8601(TimeScale::UTC), "2016-12-31T23:59:60.500");
+    assert_eq!(
+        leap.seconds_past_j2000(TimeScale::UTC),
+        after.seconds_past_j2000(TimeScale::UTC)
+    );
+    assert_eq!(
+        after.seconds_past_j2000(TimeScale::TAI) - after.seconds_past_j2000(TimeScale::UTC),
+        37.0
+    );
+    assert_eq!(
+        Epoch::from_iso8601("2017-12-31T23:59:60", TimeScale::UTC),
+        Err(EpochError::OutOfRange("second 60".to_string()))
+    );
+}
+
+#[test]
+fn arithmetic() {
+    let mut epoch = Epoch::from_iso8601("2020-02-28", TimeScale::TT).unwrap();
+
+    epoch += DAY;
+    assert_eq!(epoch.to_iso8601(TimeScale::TT), "2020-02-29T00:00:00.000");
+    let mut epoch = epoch + 1.25 * DAY;
+    assert_eq!(epoch.to_iso8601(TimeScale::TT), "2020-03-01T06:00:00.000");
+    epoch -= 6.0 * 3600.0;
+    assert_eq!(
+        (epoch - 2.0 * DAY).to_iso8601(TimeScale::TT),
+        "2020-02-28T00:00:00.000"
+    );
+    assert!(epoch > epoch - 1e-3);
+}
+
+#[test]
+fn parse_and_format() {
+    let epoch: Epoch = "1999-12-31 23:59 TAI".parse().unwrap();
+
+    assert_eq!(epoch.to_iso8601(TimeScale::TAI), "1999-12-31T23:59:00.000");
+    assert_eq!(epoch.to_string(), "1999-12-31T23:58:28.000 UTC");
+    assert_eq!(epoch.to_string().parse::<Epoch>().unwrap(), epoch);
+    assert_eq!(
+        "1969-07-20T20:17:40Z".parse::<Epoch>().unwrap().to_string(),
+        "1969-07-20T20:17:40.000 UTC"
+    );
+    assert_eq!(
+        "-0044-03-15".parse::<Epoch>().unwrap_err(),
+        EpochError::Format("-0044-03-15".to_string())
+    );
+    for string in &[
+        "2021-13-01",
+        "2021-02-29",
+        "2021-01-01T24:00",
+        "2021-01-01T12:60",
+    ] {
+        assert!(matches!(
+            string.parse::<Epoch>(),
+            Err(EpochError::OutOfRange(_))
+        ));
+    }
+    for string in &[
+        "2021-1-01",
+        "2021-01-01T12",
+        "2021-01-01T12:00:0",
+        "2021-01-01 TXY",
+        "2021-01-01T12:00:00Z TT",
+    ] {
+        assert!(matches!(
+            string.parse::<Epoch>(),
+            Err(EpochError::Format(_))
+        ));
+    }
+}
+
+#[test]
+fn serde() {
+    let epoch = Epoch::from_iso8601("2021-06-01T08:30:00.125", TimeScale::UTC).unwrap();
+
+    let json = serde_json::to_string(&epoch).unwrap();
+    let round_trip: Epoch = serde_json::from_str(&json).unwrap();
+    assert_eq!(json, "\"2021-06-01T08:30:00.125 UTC\"");
+    assert!(relative_eq!(round_trip - epoch, 0.0, epsilon = 1e-6));
+    assert_eq!(
+        serde_json::from_str::<Epoch>("\"2021-06-01T08:31:09.309 TT\"").unwrap(),
+        Epoch::from_iso8601("2021-06-01T08:31:09.309", TimeScale::TT).unwrap()
+    );
+    assert!(serde_json::from_str::<Epoch>("\"2021-13-01T00:00:00\"").is_err());
+    assert!(serde_json::from_str::<Epoch>("{\"tt_seconds_past_j2000\": 0.0}").is_err());
+    assert_eq!(
+        serde_json::to_string(&TimeScale::TDB).unwrap(),
+        "\"TDB\"".to_string()
+    );
+}
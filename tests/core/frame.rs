@@ -0,0 +1,166 @@
+use tool::{
+    BodyFixed, Ecliptic, Equatorial, Frame, FramedVector, FramedVectors, RotationModel, Vector,
+    Vectors, DAY, DEG2RAD, OBLIQUITY_J2000,
+};
+
+#[test]
+fn equatorial_to_ecliptic() {
+    let pole = FramedVector::<f64, Equatorial>::new(Vector::z());
+    let expected_pole = Vector::new(0.0, OBLIQUITY_J2000.sin(), OBLIQUITY_J2000.cos());
+
+    let pole_ecliptic = tool::equatorial_to_ecliptic().apply(&pole);
+
+    assert_eq!(pole_ecliptic.frame_name(), Ecliptic::NAME);
+    assert!(relative_eq!(
+        *pole_ecliptic.vector(),
+        expected_pole,
+        epsilon = 1e-15
+    ));
+}
+
+#[test]
+fn ecliptic_to_equatorial() {
+    let vectors = FramedVectors::<f64, Ecliptic>::new(Vectors::from_column_slice(&[
+        1.0, 2.0, 3.0, 0.0, 0.0, 1.0,
+    ]));
+
+    let equatorials = tool::ecliptic_to_equatorial().apply_all(&vectors);
+    let round_trip = tool::equatorial_to_ecliptic().apply_all(&equatorials);
+
+    assert!(relative_eq!(
+        equatorials.vector(1).into_vector(),
+        Vector::new(0.0, -OBLIQUITY_J2000.sin(), OBLIQUITY_J2000.cos()),
+        epsilon = 1e-15
+    ));
+    assert!(relative_eq!(
+        *round_trip.vectors(),
+        *vectors.vectors(),
+        epsilon = 1e-15
+    ));
+}
+
+#[test]
+fn body_fixed() {
+    let (right_ascension, declination): (f64, f64) = (40.0 * DEG2RAD, 60.0 * DEG2RAD);
+    let model = RotationModel {
+        right_ascension,
+        declination,
+        prime_meridian: 0.0,
+        period: DAY,
+        epoch: 10.0,
+    };
+    let pole = FramedVector::<f64, Equatorial>::new(Vector::new(
+        declination.cos() * right_ascension.cos(),
+        declination.cos() * right_ascension.sin(),
+        declination.sin(),
+    ));
+    let node = FramedVector::<f64, Equatorial>::new(Vector::new(
+        -right_ascension.sin(),
+        right_ascension.cos(),
+        0.0,
+    ));
+
+    let transform_epoch = model.transform::<BodyFixed>(10.0);
+    let transform_quarter = model.transform::<BodyFixed>(10.0 + DAY / 4.0);
+
+    assert!(relative_eq!(
+        transform_epoch.apply(&pole).into_vector(),
+        Vector::z(),
+        epsilon = 1e-15
+    ));
+    assert!(relative_eq!(
+        transform_quarter.apply(&pole).into_vector(),
+        Vector::z(),
+        epsilon = 1e-15
+    ));
+    assert!(relative_eq!(
+        transform_epoch.apply(&node).into_vector(),
+        Vector::x(),
+        epsilon = 1e-15
+    ));
+    assert!(relative_eq!(
+        transform_quarter.apply(&node).into_vector(),
+        -Vector::y(),
+        epsilon = 1e-15
+    ));
+}
+
+#[test]
+fn frame_transform_then() {
+    let model = RotationModel {
+        right_ascension: 0.0,
+        declination: 90.0 * DEG2RAD,
+        prime_meridian: 0.0,
+        period: DAY,
+        epoch: 0.0,
+    };
+    let vector = FramedVector::<f64, Ecliptic>::new(Vector::new(1.0, -2.0, 0.5));
+
+    let transform = tool::ecliptic_to_equatorial().then(&model.transform(0.3 * DAY));
+    let body_fixed: FramedVector<f64, BodyFixed> = transform.apply(&vector);
+    let expected_body_fixed = model
+        .transform::<BodyFixed>(0.3 * DAY)
+        .apply(&tool::ecliptic_to_equatorial().apply(&vector));
+
+    assert!(relative_eq!(
+        body_fixed.into_vector(),
+        expected_body_fixed.into_vector(),
+        epsilon = 1e-15
+    ));
+}
+
+#[test]
+fn framed_vector_operations() {
+    let vector_1 = FramedVector::<f64, Equatorial>::new(Vector::new(1.0, 2.0, 3.0));
+    let vector_2 = FramedVector::<f64, Equatorial>::new(Vector::new(-1.0, 0.5, 1.0));
+
+    let vector = (vector_1 - vector_2) * 2.0 + -vector_1;
+
+    assert_eq!(vector.into_vector(), Vector::new(3.0, 1.0, 1.0));
+}
+
+/// Frame fixed to a first body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Primary;
+
+impl Frame for Primary {
+    const NAME: &'static str = "primary body-fixed";
+}
+
+/// Frame fixed to a second body.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Secondary;
+
+impl Frame for Secondary {
+    const NAME: &'static str = "secondary body-fixed";
+}
+
+#[test]
+fn body_fixed_markers() {
+    let model = |period: f64| RotationModel {
+        right_ascension: 0.0,
+        declination: 90.0 * DEG2RAD,
+        prime_meridian: 0.0,
+        period,
+        epoch: 0.0,
+    };
+    let vector = FramedVector::<f64, Equatorial>::new(Vector::new(1.0, -2.0, 0.5));
+    let to_primary = model(DAY).transform::<Primary>(DAY / 8.0);
+    let to_secondary = model(DAY / 2.0).transform::<Secondary>(DAY / 8.0);
+
+    // Each body has its own frame, and going from one to the other is explicit.
+    let primary = to_primary.apply(&vector);
+    let secondary = to_primary.inverse().then(&to_secondary).apply(&primary);
+
+    assert_eq!(Primary::NAME, "primary body-fixed");
+    assert!(relative_eq!(
+        secondary.into_vector(),
+        to_secondary.apply(&vector).into_vector(),
+        epsilon = 1e-15
+    ));
+    assert!(!relative_eq!(
+        primary.into_vector(),
+        secondary.into_vector(),
+        epsilon = 1e-3
+    ));
+}
@@ -1,5 +1,5 @@
 use itertools::multizip;
-use tool::{direct_angle, List, Vector, Vectors};
+use tool::{direct_angle, List, Vector, Vectors, DEG2RAD};
 
 #[test]
 fn compute_distances() {
@@ -107,3 +107,226 @@ fn test_direct_angle_negative() {
     let ang = direct_angle(&v1, &v2, &up);
     assert!(relative_eq!(ang, 5.497787143782138, epsilon = f64::EPSILON));
 }
+
+#[test]
+fn sph_to_cart() {
+    let vectors = Vectors::from_column_slice(&[
+        0.0,
+        0.0,
+        2.0,
+        90.0 * DEG2RAD,
+        0.0,
+        1.0,
+        45.0 * DEG2RAD,
+        45.0 * DEG2RAD,
+        2.0f64.sqrt(),
+    ]);
+    let expected_vectors = Vectors::from_column_slice(&[
+        2.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+        0.5f64.sqrt(),
+        0.5f64.sqrt(),
+        1.0,
+    ]);
+
+    let cartesians = tool::sph_to_cart(&vectors);
+
+    for (component, expected_component) in multizip((cartesians.iter(), expected_vectors.iter())) {
+        assert!(relative_eq!(component, expected_component, epsilon = 1e-14));
+    }
+}
+
+#[test]
+fn cart_to_sph_round_trip() {
+    let vectors = Vectors::from_column_slice(&[
+        1.0, 2.0, 3.0, -1.0, 0.5, -2.0, -3.0, -4.0, 0.1, 0.0, 0.0, 5.0, 0.0, 0.0, -5.0,
+    ]);
+
+    let round_trip = tool::sph_to_cart(&tool::cart_to_sph(&vectors));
+
+    for (component, expected_component) in multizip((round_trip.iter(), vectors.iter())) {
+        assert!(relative_eq!(component, expected_component, epsilon = 1e-14));
+    }
+}
+
+#[test]
+fn cart_to_sph_poles() {
+    let north = tool::cart_to_sph_vector(&Vector::new(0.0, 0.0, 3.0));
+    let south = tool::cart_to_sph_vector(&Vector::new(0.0, 0.0, -3.0));
+
+    assert!(relative_eq!(
+        north[1],
+        90.0 * DEG2RAD,
+        epsilon = f64::EPSILON
+    ));
+    assert!(relative_eq!(north[2], 3.0, epsilon = f64::EPSILON));
+    assert!(relative_eq!(
+        south[1],
+        -90.0 * DEG2RAD,
+        epsilon = f64::EPSILON
+    ));
+
+    let round_trip = tool::sph_to_cart_vector(&south);
+    assert!(relative_eq!(
+        round_trip,
+        Vector::new(0.0, 0.0, -3.0),
+        epsilon = 1e-14
+    ));
+}
+
+#[test]
+fn cart_to_sph_origin() {
+    let origin = Vector::<f64>::zeros();
+
+    let spherical = tool::cart_to_sph_vector(&origin);
+    let cartesian = tool::sph_to_cart_vector(&spherical);
+
+    assert_eq!(spherical, Vector::zeros());
+    assert_eq!(cartesian, origin);
+}
+
+#[test]
+fn sph_to_cart_slice() {
+    let vectors = Vectors::from_column_slice(&[1.0, 2.0, 3.0, -1.0, 0.5, -2.0]);
+    let sphericals = tool::cart_to_sph(&vectors.columns(1, 1));
+
+    let cartesians = tool::sph_to_cart(&sphericals.columns(0, 1));
+
+    assert!(relative_eq!(
+        cartesians.column(0).into_owned(),
+        vectors.column(1).into_owned(),
+        epsilon = 1e-14
+    ));
+}
+
+#[test]
+fn cart_to_cyl_round_trip() {
+    let vectors = Vectors::from_column_slice(&[1.0, 2.0, 3.0, -1.0, 0.5, -2.0, 0.0, 0.0, 4.0]);
+    let expected_cylindricals = Vectors::from_column_slice(&[
+        2.0f64.atan2(1.0),
+        3.0,
+        5.0f64.sqrt(),
+        0.5f64.atan2(-1.0),
+        -2.0,
+        1.25f64.sqrt(),
+        0.0,
+        4.0,
+        0.0,
+    ]);
+
+    let cylindricals = tool::cart_to_cyl(&vectors);
+    let round_trip = tool::cyl_to_cart(&cylindricals);
+
+    for (component, expected_component) in
+        multizip((cylindricals.iter(), expected_cylindricals.iter()))
+    {
+        assert!(relative_eq!(component, expected_component, epsilon = 1e-14));
+    }
+    for (component, expected_component) in multizip((round_trip.iter(), vectors.iter())) {
+        assert!(relative_eq!(component, expected_component, epsilon = 1e-14));
+    }
+}
+
+#[test]
+fn geod_to_cart_spheroid() {
+    let (a, c) = (6378137.0f64, 6356752.314245);
+    let radii = Vector::new(a, a, c);
+    let eccentricity_square = 1.0 - (c / a).powi(2);
+    let (longitude, latitude, altitude): (f64, f64, f64) = (30.0 * DEG2RAD, 45.0 * DEG2RAD, 1000.0);
+    let normal_radius = a / (1.0 - eccentricity_square * latitude.sin().powi(2)).sqrt();
+    let expected_vector = Vector::new(
+        (normal_radius + altitude) * latitude.cos() * longitude.cos(),
+        (normal_radius + altitude) * latitude.cos() * longitude.sin(),
+        (normal_radius * (1.0 - eccentricity_square) + altitude) * latitude.sin(),
+    );
+
+    let vector = tool::geod_to_cart_vector(&Vector::new(longitude, latitude, altitude), &radii);
+
+    assert!(relative_eq!(vector, expected_vector, epsilon = 1e-7));
+}
+
+#[test]
+fn cart_to_geod_sphere() {
+    let radii = Vector::new(2.0, 2.0, 2.0);
+    let vectors = Vectors::from_column_slice(&[1.0, 2.0, 3.0, -1.0, 0.5, -0.2]);
+
+    let geodetics = tool::cart_to_geod(&vectors, &radii).unwrap();
+    let sphericals = tool::cart_to_sph(&vectors);
+
+    for (geodetic, spherical) in multizip((geodetics.column_iter(), sphericals.column_iter())) {
+        assert!(relative_eq!(geodetic[0], spherical[0], epsilon = 1e-12));
+        assert!(relative_eq!(geodetic[1], spherical[1], epsilon = 1e-12));
+        assert!(relative_eq!(
+            geodetic[2],
+            spherical[2] - 2.0,
+            epsilon = 1e-12
+        ));
+    }
+}
+
+#[test]
+fn cart_to_geod_round_trip() {
+    let radii = Vector::new(3.0, 2.0, 1.0);
+    let geodetics = Vectors::from_column_slice(&[
+        10.0 * DEG2RAD,
+        20.0 * DEG2RAD,
+        0.0,
+        -120.0 * DEG2RAD,
+        -60.0 * DEG2RAD,
+        5.0,
+        170.0 * DEG2RAD,
+        80.0 * DEG2RAD,
+        0.3,
+        45.0 * DEG2RAD,
+        -5.0 * DEG2RAD,
+        -0.2,
+    ]);
+
+    let round_trip = tool::cart_to_geod(&tool::geod_to_cart(&geodetics, &radii), &radii).unwrap();
+
+    for (component, expected_component) in multizip((round_trip.iter(), geodetics.iter())) {
+        assert!(relative_eq!(component, expected_component, epsilon = 1e-10));
+    }
+}
+
+#[test]
+fn cart_to_geod_poles_and_origin() {
+    let radii = Vector::new(6378137.0, 6378137.0, 6356752.314245);
+    let vectors = Vectors::from_column_slice(&[0.0, 0.0, 6357752.314245, 0.0, 0.0, 0.0]);
+
+    let geodetics = tool::cart_to_geod(&vectors, &radii).unwrap();
+
+    assert!(relative_eq!(
+        geodetics[(1, 0)],
+        90.0 * DEG2RAD,
+        epsilon = 1e-12
+    ));
+    assert!(relative_eq!(geodetics[(2, 0)], 1000.0, epsilon = 1e-6));
+    assert!(relative_eq!(
+        geodetics[(1, 1)],
+        90.0 * DEG2RAD,
+        epsilon = 1e-12
+    ));
+    assert!(relative_eq!(
+        geodetics[(2, 1)],
+        -6356752.314245,
+        epsilon = 1e-6
+    ));
+}
+
+#[test]
+fn cart_to_geod_not_finite() {
+    let radii = Vector::new(3.0, 2.0, 1.0);
+    let vectors = Vectors::from_column_slice(&[1.0, 2.0, 3.0, f64::NAN, 0.0, 1.0]);
+
+    match tool::cart_to_geod(&vectors, &radii) {
+        Err(tool::NewtonMethodError::NonFinite { components, .. }) => {
+            assert_eq!(components, vec![1])
+        }
+        result => panic!("expected a non-finite error, got {:?}", result),
+    }
+}
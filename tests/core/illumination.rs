@@ -0,0 +1,54 @@
+use tool::{Vector, Vectors, DEG2RAD};
+
+#[test]
+fn illumination() {
+    let centroids = Vectors::zeros(3);
+    let normals = Vectors::from_column_slice(&[0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 1.0, 0.0, 0.0]);
+    let sun = Vector::new(0.0, 0.0, 10.0);
+    let observer = Vector::new(5.0, 0.0, 5.0);
+
+    let illumination = tool::illumination(&centroids, &normals, &sun, &observer);
+
+    let expected_cos_incidence = [1.0, 0.0, 0.0];
+    let expected_cos_emission = [0.5f64.sqrt(), 0.0, 0.5f64.sqrt()];
+    for index in 0..3 {
+        assert!(relative_eq!(
+            illumination.cos_incidence[index],
+            expected_cos_incidence[index],
+            epsilon = 1e-15
+        ));
+        assert!(relative_eq!(
+            illumination.cos_emission[index],
+            expected_cos_emission[index],
+            epsilon = 1e-15
+        ));
+        assert!(relative_eq!(
+            illumination.phase[index],
+            45.0 * DEG2RAD,
+            epsilon = 1e-15
+        ));
+        assert_eq!(illumination.azimuth[index], 0.0);
+    }
+}
+
+#[test]
+fn illumination_azimuth() {
+    let centroids = Vectors::from_column_slice(&[1.0, 1.0, 0.0, 1.0, 1.0, 0.0]);
+    let normals = Vectors::from_column_slice(&[0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+    let sun = Vector::new(2.0, 1.0, 1.0);
+
+    let left = tool::illumination(&centroids, &normals, &sun, &Vector::new(1.0, 2.0, 1.0));
+    let right = tool::illumination(&centroids, &normals, &sun, &Vector::new(1.0, 0.0, 1.0));
+
+    assert!(relative_eq!(
+        left.azimuth[0],
+        90.0 * DEG2RAD,
+        epsilon = 1e-15
+    ));
+    assert!(relative_eq!(
+        right.azimuth[1],
+        270.0 * DEG2RAD,
+        epsilon = 1e-14
+    ));
+    assert!(relative_eq!(left.phase[0], 60.0 * DEG2RAD, epsilon = 1e-15));
+}
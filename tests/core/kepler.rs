@@ -0,0 +1,76 @@
+use tool::{KeplerError, List, DEG2RAD};
+
+/// Residual of Kepler's equation.
+fn residual(anomaly: f64, mean_anomaly: f64, eccentricity: f64) -> f64 {
+    if eccentricity < 1.0 {
+        anomaly - eccentricity * anomaly.sin() - mean_anomaly
+    } else if eccentricity == 1.0 {
+        anomaly + anomaly.powi(3) / 3.0 - mean_anomaly
+    } else {
+        eccentricity * anomaly.sinh() - anomaly - mean_anomaly
+    }
+}
+
+#[test]
+fn solve_kepler_vallado() {
+    // Vallado, Fundamentals of Astrodynamics and Applications, example 2-1.
+    let anomaly = tool::solve_kepler(235.4 * DEG2RAD - 360.0 * DEG2RAD, 0.4).unwrap();
+
+    assert!(relative_eq!(
+        anomaly,
+        3.848_661_745_1 - 360.0 * DEG2RAD,
+        epsilon = 1e-10
+    ));
+}
+
+#[test]
+fn solve_kepler_all_regimes() {
+    let mean_anomalies = List::from_column_slice(&[
+        -50.0, -3.0, -1e-3, 0.0, 1e-9, 1e-4, 0.1, 1.0, 3.1, 3.2, 6.0, 40.0, 1e4,
+    ]);
+    let eccentricities = [
+        0.0, 0.3, 0.9, 0.99, 0.999_999, 1.0, 1.000_001, 1.01, 2.0, 10.0, 1e3,
+    ];
+
+    for eccentricity in eccentricities.iter() {
+        let anomalies = tool::solve_kepler_list(&mean_anomalies, *eccentricity).unwrap();
+        for (anomaly, mean_anomaly) in anomalies.iter().zip(mean_anomalies.iter()) {
+            let tolerance = 1e-12 * mean_anomaly.abs().max(1.0);
+            assert!(
+                residual(*anomaly, *mean_anomaly, *eccentricity).abs() <= tolerance,
+                "e = {}, M = {}",
+                eccentricity,
+                mean_anomaly
+            );
+        }
+    }
+}
+
+#[test]
+fn solve_barker() {
+    assert!(relative_eq!(
+        tool::solve_barker(4.0 / 3.0),
+        1.0,
+        epsilon = 1e-15
+    ));
+    assert!(relative_eq!(
+        tool::solve_barker(-4.0 / 3.0),
+        -1.0,
+        epsilon = 1e-15
+    ));
+    assert_eq!(tool::solve_barker(0.0), 0.0);
+}
+
+#[test]
+fn solve_kepler_errors() {
+    let mean_anomalies = List::from_column_slice(&[0.1, 0.2, f64::NAN]);
+
+    let invalid_eccentricity = tool::solve_kepler(0.1, -0.1).unwrap_err();
+    let invalid_mean_anomaly = tool::solve_kepler_list(&mean_anomalies, 0.5).unwrap_err();
+
+    assert_eq!(invalid_eccentricity, KeplerError::InvalidEccentricity(-0.1));
+    match invalid_mean_anomaly {
+        KeplerError::InvalidMeanAnomaly { index, .. } => assert_eq!(index, 2),
+        _ => panic!("expected an invalid mean anomaly"),
+    }
+}
@@ -0,0 +1,155 @@
+use itertools::multizip;
+use tool::{List, Mesh, MeshError, Vectors};
+
+fn tetrahedron() -> Mesh<f64> {
+    Mesh::new(
+        Vectors::from_column_slice(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0]),
+        vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]],
+    )
+}
+
+#[test]
+fn face_normals() {
+    let mesh = tetrahedron();
+    let a = 1.0 / 3.0f64.sqrt();
+    let expected_normals =
+        Vectors::from_column_slice(&[0.0, 0.0, -1.0, 0.0, -1.0, 0.0, -1.0, 0.0, 0.0, a, a, a]);
+
+    let normals = mesh.face_normals();
+
+    for (component, expected_component) in multizip((normals.iter(), expected_normals.iter())) {
+        assert!(relative_eq!(
+            component,
+            expected_component,
+            epsilon = f64::EPSILON
+        ));
+    }
+}
+
+#[test]
+fn face_areas() {
+    let mesh = tetrahedron();
+    let expected_areas = List::from_column_slice(&[0.5, 0.5, 0.5, 3.0f64.sqrt() / 2.0]);
+
+    let areas = mesh.face_areas();
+
+    for (area, expected_area) in multizip((areas.iter(), expected_areas.iter())) {
+        assert!(relative_eq!(area, expected_area, epsilon = f64::EPSILON));
+    }
+}
+
+#[test]
+fn face_centroids() {
+    let mesh = tetrahedron();
+    let a = 1.0 / 3.0;
+    let expected_centroids =
+        Vectors::from_column_slice(&[a, a, 0.0, a, 0.0, a, 0.0, a, a, a, a, a]);
+
+    let centroids = mesh.face_centroids();
+
+    for (component, expected_component) in multizip((centroids.iter(), expected_centroids.iter())) {
+        assert!(relative_eq!(
+            component,
+            expected_component,
+            epsilon = f64::EPSILON
+        ));
+    }
+}
+
+#[test]
+fn vertex_normals() {
+    let mesh = tetrahedron();
+    let a = 1.0 / 3.0f64.sqrt();
+
+    let normals = mesh.vertex_normals();
+
+    assert!(relative_eq!(normals[(0, 0)], -a, epsilon = f64::EPSILON));
+    assert!(relative_eq!(normals[(1, 0)], -a, epsilon = f64::EPSILON));
+    assert!(relative_eq!(normals[(2, 0)], -a, epsilon = f64::EPSILON));
+    for normal in normals.column_iter() {
+        assert!(relative_eq!(normal.norm(), 1.0, epsilon = f64::EPSILON));
+    }
+}
+
+#[test]
+fn unreferenced_vertex_normal() {
+    let mesh = Mesh::new(
+        Vectors::from_column_slice(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 5.0, 5.0, 5.0]),
+        vec![[0, 1, 2]],
+    );
+
+    let normals = mesh.vertex_normals();
+
+    assert_eq!(normals.column(3).norm(), 0.0);
+    assert!(relative_eq!(normals[(2, 0)], 1.0, epsilon = f64::EPSILON));
+}
+
+#[test]
+fn degenerate_face() {
+    let mesh = Mesh::new(
+        Vectors::from_column_slice(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 2.0, 0.0, 0.0]),
+        vec![[0, 1, 2]],
+    );
+
+    assert_eq!(mesh.face_normals(), Vectors::zeros(1));
+    assert_eq!(mesh.face_areas()[0], 0.0);
+    assert_eq!(mesh.vertex_normals(), Vectors::zeros(3));
+}
+
+#[test]
+fn empty_mesh() {
+    let mesh = Mesh::new(Vectors::<f64>::zeros(0), vec![]);
+
+    assert!(!mesh.is_closed());
+    assert!(mesh.is_consistently_oriented());
+}
+
+#[test]
+fn closed_and_oriented() {
+    let mesh = tetrahedron();
+
+    assert!(mesh.is_closed());
+    assert!(mesh.is_consistently_oriented());
+}
+
+#[test]
+fn not_consistently_oriented() {
+    let mut faces = tetrahedron().faces().to_vec();
+    faces[3] = [1, 3, 2];
+    let mesh = Mesh::new(tetrahedron().vertices().clone(), faces);
+
+    assert!(mesh.is_closed());
+    assert!(!mesh.is_consistently_oriented());
+}
+
+#[test]
+fn not_closed() {
+    let tetrahedron = tetrahedron();
+    let mesh = Mesh::new(
+        tetrahedron.vertices().clone(),
+        tetrahedron.faces()[..3].to_vec(),
+    );
+
+    assert!(!mesh.is_closed());
+    assert!(mesh.is_consistently_oriented());
+}
+
+#[test]
+#[should_panic]
+fn invalid_face() {
+    Mesh::new(Vectors::<f64>::zeros(3), vec![[0, 1, 3]]);
+}
+
+#[test]
+fn try_new_invalid_face() {
+    let error = Mesh::try_new(Vectors::<f64>::zeros(3), vec![[0, 1, 2], [0, 1, 3]]).unwrap_err();
+
+    assert_eq!(
+        error,
+        MeshError::VertexOutOfRange {
+            face: 1,
+            vertex: 3,
+            vertices: 3,
+        }
+    );
+}
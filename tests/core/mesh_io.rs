@@ -0,0 +1,243 @@
+use super::temp_path;
+use tool::{List, Mesh, MeshAttribute, MeshIoError, Vectors};
+
+fn tetrahedron() -> Mesh<f64> {
+    Mesh::new(
+        Vectors::from_column_slice(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.5, 0.0, 0.0, 0.0, -0.25]),
+        vec![[0, 2, 1], [0, 1, 3], [0, 3, 2], [1, 2, 3]],
+    )
+}
+
+fn assert_parse_error(error: MeshIoError, expected_line: usize) {
+    match error {
+        MeshIoError::Parse { line, .. } => assert_eq!(line, expected_line),
+        _ => panic!("expected a parse error, got {}", error),
+    }
+}
+
+#[test]
+fn obj_round_trip() {
+    let path = temp_path("tetrahedron.obj");
+    let mesh = tetrahedron();
+
+    tool::write_obj(&path, &mesh).unwrap();
+    let read_mesh = tool::read_obj(&path).unwrap();
+
+    assert_eq!(read_mesh, mesh);
+}
+
+#[test]
+fn read_obj_polygons() {
+    let path = temp_path("square.obj");
+    let content =
+        "# square\nv 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//1 -1//1\n";
+    std::fs::write(&path, content).unwrap();
+
+    let mesh = tool::read_obj(&path).unwrap();
+
+    assert_eq!(mesh.number_vertices(), 4);
+    assert_eq!(mesh.faces(), vec![[0, 1, 2], [0, 2, 3]]);
+}
+
+#[test]
+fn read_obj_malformed() {
+    let malformed_path = temp_path("malformed.obj");
+    let out_of_range_path = temp_path("out_of_range.obj");
+    std::fs::write(&malformed_path, "v 0 0 0\nv 1 0 0\nv 0 a 0\n").unwrap();
+    assert_parse_error(tool::read_obj(&malformed_path).unwrap_err(), 3);
+
+    std::fs::write(
+        &out_of_range_path,
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n\nf 1 2 4\n",
+    )
+    .unwrap();
+    let error = tool::read_obj(&out_of_range_path).unwrap_err();
+    assert!(error.to_string().starts_with("line 6:"));
+    assert_parse_error(error, 6);
+}
+
+#[test]
+fn ply_round_trip_with_attributes() {
+    let path = temp_path("tetrahedron.ply");
+    let mesh = tetrahedron();
+    let temperatures = List::from_column_slice(&[100.0, 200.0, 300.0, 400.5]);
+    let albedos = List::from_column_slice(&[0.1, 0.2, 0.3, 0.4]);
+
+    tool::write_ply(
+        &path,
+        &mesh,
+        &[
+            MeshAttribute::Face("temperature", &temperatures),
+            MeshAttribute::Vertex("albedo", &albedos),
+        ],
+    )
+    .unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    let read_mesh = tool::read_ply(&path).unwrap();
+
+    assert!(content.contains("property double albedo\nelement face 4\n"));
+    assert!(content.contains("3 1 2 3 400.5\n"));
+    assert_eq!(read_mesh, mesh);
+}
+
+#[test]
+fn write_ply_invalid_attribute() {
+    let path = temp_path("invalid.ply");
+    let mesh = tetrahedron();
+    let temperatures = List::from_column_slice(&[100.0, 200.0]);
+
+    let error = tool::write_ply(
+        &path,
+        &mesh,
+        &[MeshAttribute::Face("temperature", &temperatures)],
+    )
+    .unwrap_err();
+
+    assert!(matches!(error, MeshIoError::Format(_)));
+}
+
+#[test]
+fn read_ply_binary() {
+    let path = temp_path("square.ply");
+    let mut content = b"ply\nformat binary_little_endian 1.0\ncomment test\nelement vertex 4\nproperty float x\nproperty float y\nproperty float z\nproperty uchar red\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n".to_vec();
+    for vertex in [
+        [0.0f32, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [1.0, 1.0, 0.0],
+        [0.0, 1.0, 0.0],
+    ]
+    .iter()
+    {
+        for component in vertex.iter() {
+            content.extend_from_slice(&component.to_le_bytes());
+        }
+        content.push(255);
+    }
+    content.push(4);
+    for index in 0..4i32 {
+        content.extend_from_slice(&index.to_le_bytes());
+    }
+    std::fs::write(&path, content).unwrap();
+
+    let mesh = tool::read_ply(&path).unwrap();
+
+    assert_eq!(
+        *mesh.vertices(),
+        Vectors::from_column_slice(&[0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0])
+    );
+    assert_eq!(mesh.faces(), vec![[0, 1, 2], [0, 2, 3]]);
+}
+
+#[test]
+fn read_ply_malformed() {
+    let path = temp_path("malformed.ply");
+    std::fs::write(
+        &path,
+        "ply\nformat ascii 1.0\nelement vertex 2\nproperty double x\nproperty double y\nproperty double z\nend_header\n0 0 0\n1 0\n",
+    )
+    .unwrap();
+
+    assert_parse_error(tool::read_ply(&path).unwrap_err(), 9);
+}
+
+#[test]
+fn write_ply_invalid_attribute_name() {
+    let path = temp_path("invalid_name.ply");
+    let mesh = tetrahedron();
+    let temperatures = List::from_column_slice(&[100.0, 200.0, 300.0, 400.0]);
+
+    for name in ["", "surface temperature", "temperature\nend_header"].iter() {
+        let error =
+            tool::write_ply(&path, &mesh, &[MeshAttribute::Face(name, &temperatures)]).unwrap_err();
+
+        assert!(matches!(error, MeshIoError::Format(_)));
+    }
+    assert!(!path.exists());
+}
+
+#[test]
+fn read_ply_invalid_index() {
+    let path = temp_path("invalid_index.ply");
+    let header = "ply\nformat ascii 1.0\nelement vertex 3\nproperty double x\nproperty double y\nproperty double z\nelement face 1\nproperty list uchar double vertex_indices\nend_header\n0 0 0\n1 0 0\n0 1 0\n";
+
+    for face in ["3 0 1 -1\n", "3 0 1 1.5\n", "3 0 1 nan\n"].iter() {
+        std::fs::write(&path, format!("{}{}", header, face)).unwrap();
+
+        assert_parse_error(tool::read_ply(&path).unwrap_err(), 13);
+    }
+}
+
+#[test]
+fn read_ply_list_coordinates() {
+    let path = temp_path("list_coordinates.ply");
+    std::fs::write(
+        &path,
+        "ply\nformat ascii 1.0\nelement vertex 1\nproperty list uchar float x\nproperty float y\nproperty float z\nend_header\n0 0 0\n",
+    )
+    .unwrap();
+
+    assert!(matches!(
+        tool::read_ply(&path).unwrap_err(),
+        MeshIoError::Format(_)
+    ));
+}
+
+#[test]
+fn stl_round_trip() {
+    let path = temp_path("tetrahedron.stl");
+    let mesh = tetrahedron();
+
+    tool::write_stl(&path, &mesh).unwrap();
+    let read_mesh = tool::read_stl(&path).unwrap();
+
+    assert_eq!(read_mesh.number_vertices(), 4);
+    assert!(read_mesh.is_closed());
+    assert!(read_mesh.is_consistently_oriented());
+    assert_eq!(read_mesh.face_centroids(), mesh.face_centroids());
+    assert_eq!(read_mesh.face_normals(), mesh.face_normals());
+}
+
+#[test]
+fn read_stl_ascii() {
+    let path = temp_path("square.stl");
+    let content = "solid square
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+      vertex 1 1 0
+    endloop
+  endfacet
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 1 0
+      vertex 0 1 -0
+    endloop
+  endfacet
+endsolid square
+";
+    std::fs::write(&path, content).unwrap();
+
+    let mesh = tool::read_stl(&path).unwrap();
+
+    assert_eq!(mesh.number_vertices(), 4);
+    assert_eq!(mesh.faces(), vec![[0, 1, 2], [0, 2, 3]]);
+}
+
+#[test]
+fn read_stl_ascii_malformed() {
+    let path = temp_path("malformed.stl");
+    let content = "solid square
+  facet normal 0 0 1
+    outer loop
+      vertex 0 0 0
+      vertex 1 0 0
+    endloop
+  endfacet
+endsolid square
+";
+    std::fs::write(&path, content).unwrap();
+
+    assert_parse_error(tool::read_stl(&path).unwrap_err(), 7);
+}
@@ -1,6 +1,77 @@
+mod angle;
+mod body;
 mod geometry;
+mod illumination;
 #[macro_use]
 mod log;
 mod matrix;
 #[macro_use]
 mod json;
+mod dual;
+mod ephemeris;
+mod ephemeris_io;
+mod epoch;
+mod frame;
+mod kepler;
+mod mesh;
+mod mesh_io;
+mod numerical_algorithms;
+mod orbit;
+mod quantity;
+mod ray;
+mod rotation;
+mod spk;
+mod thermal;
+mod two_body;
+mod view_factor;
+
+use std::path::PathBuf;
+use tool::{Mesh, Vectors, TAU};
+
+/// Path of a file written by the tests, in the temporary directory.
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rustool_{}", name))
+}
+
+/// Sphere of radius 1 made of latitude and longitude bands.
+pub fn sphere(number_latitudes: usize, number_longitudes: usize) -> Mesh<f64> {
+    let mut coordinates = vec![0.0, 0.0, 1.0];
+    for latitude in 1..number_latitudes {
+        let polar = latitude as f64 / number_latitudes as f64 * TAU / 2.0;
+        for longitude in 0..number_longitudes {
+            let azimuth = longitude as f64 / number_longitudes as f64 * TAU;
+            coordinates.extend_from_slice(&[
+                polar.sin() * azimuth.cos(),
+                polar.sin() * azimuth.sin(),
+                polar.cos(),
+            ]);
+        }
+    }
+    coordinates.extend_from_slice(&[0.0, 0.0, -1.0]);
+    let south = coordinates.len() / 3 - 1;
+    let vertex = |latitude: usize, longitude: usize| {
+        1 + (latitude - 1) * number_longitudes + longitude % number_longitudes
+    };
+    let mut faces = vec![];
+    for longitude in 0..number_longitudes {
+        faces.push([0, vertex(1, longitude), vertex(1, longitude + 1)]);
+        for latitude in 1..number_latitudes - 1 {
+            faces.push([
+                vertex(latitude, longitude),
+                vertex(latitude + 1, longitude),
+                vertex(latitude + 1, longitude + 1),
+            ]);
+            faces.push([
+                vertex(latitude, longitude),
+                vertex(latitude + 1, longitude + 1),
+                vertex(latitude, longitude + 1),
+            ]);
+        }
+        faces.push([
+            vertex(number_latitudes - 1, longitude),
+            south,
+            vertex(number_latitudes - 1, longitude + 1),
+        ]);
+    }
+    Mesh::new(Vectors::from_vec(coordinates), faces)
+}
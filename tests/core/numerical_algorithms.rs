@@ -0,0 +1,547 @@
+use na::{DMatrix, DVector, RealField};
+use std::cell::RefCell;
+use tool::{
+    newton_method, newton_method_dual, newton_method_finite_differences,
+    newton_method_with_options, newton_raphson, newton_raphson_finite_differences, pows, List,
+    NewtonMethodArguments, NewtonMethodError, NewtonMethodOptions, NewtonMethodSolution,
+    NewtonRaphsonError, FINITE_DIFFERENCE_STEP, STEFAN_BOLTZMANN,
+};
+
+/// Target values of the functions.
+struct Targets {
+    values: List<f64>,
+}
+
+impl NewtonMethodArguments for Targets {}
+
+#[test]
+fn square_roots() {
+    let targets = Targets {
+        values: List::from_row_slice(&[2.0, 9.0, 0.25]),
+    };
+    let solution = newton_method(
+        List::from_element(3, 1.0),
+        |values, args: &Targets| values.component_mul(values) - &args.values,
+        |values, _| values * 2.0,
+        targets,
+    )
+    .unwrap();
+
+    assert!(relative_eq!(
+        solution.values,
+        List::from_row_slice(&[2f64.sqrt(), 3.0, 0.5]),
+        epsilon = 1e-10
+    ));
+    assert!(solution.iterations > 1 && solution.iterations < 10);
+    assert!(solution
+        .residuals
+        .iter()
+        .all(|residual| residual.abs() < 1e-5));
+}
+
+#[test]
+fn division_by_zero() {
+    let targets = Targets {
+        values: List::from_row_slice(&[1.0, 1.0, 1.0]),
+    };
+    let error = newton_method(
+        List::from_row_slice(&[0.0, 2.0, 0.0]),
+        |values, args: &Targets| values.component_mul(values) - &args.values,
+        |values, _| values * 2.0,
+        targets,
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error,
+        NewtonMethodError::DivisionByZero {
+            iteration: 1,
+            components: vec![0, 2],
+            values: vec![0.0, 0.0],
+        }
+    );
+}
+
+#[test]
+fn not_finite() {
+    let targets = Targets {
+        values: List::from_row_slice(&[1.0, 1.0]),
+    };
+    let error = newton_method(
+        List::from_row_slice(&[4.0, -1.0]),
+        |values, args: &Targets| values.map(f64::sqrt) - &args.values,
+        |values, _| values.map(|value| 0.5 / value.sqrt()),
+        targets,
+    )
+    .unwrap_err();
+
+    assert_eq!(
+        error,
+        NewtonMethodError::NonFinite {
+            iteration: 1,
+            components: vec![1],
+            values: vec![-1.0],
+        }
+    );
+}
+
+#[test]
+fn not_converged() {
+    // The first function has no real root, and its Newton's iterations never settle.
+    let targets = Targets {
+        values: List::from_row_slice(&[-1.0, 4.0]),
+    };
+    let error = newton_method(
+        List::from_row_slice(&[0.5, 1.0]),
+        |values, args: &Targets| values.component_mul(values) - &args.values,
+        |values, _| values * 2.0,
+        targets,
+    )
+    .unwrap_err();
+
+    match error {
+        NewtonMethodError::NotConverged {
+            components,
+            values,
+            residuals,
+        } => {
+            assert_eq!(components, vec![0]);
+            assert!(values[0].is_finite());
+            assert!(residuals[0].abs() >= 1e-5);
+        }
+        error => panic!("expected a convergence error, got {}", error),
+    }
+}
+
+/// Newton's method on the function of the arguments from a start value, with options.
+fn solve(
+    start: f64,
+    function: fn(f64) -> f64,
+    derivative: fn(f64) -> f64,
+    options: &NewtonMethodOptions<f64>,
+) -> Result<NewtonMethodSolution<f64>, NewtonMethodError<f64>> {
+    newton_method_with_options(
+        List::from_element(1, start),
+        |values, _: &Targets| values.map(function),
+        |values, _| values.map(derivative),
+        Targets {
+            values: List::zeros(1),
+        },
+        options,
+    )
+}
+
+#[test]
+fn tolerances() {
+    // The residuals are relative to a root of order 1e8.
+    let function = |value: f64| value * value - 1e16;
+    let derivative = |value: f64| 2.0 * value;
+    let mut options = NewtonMethodOptions {
+        absolute_tolerance: 0.0,
+        relative_tolerance: 1e-12,
+        ..NewtonMethodOptions::default()
+    };
+    let solution = solve(1.0, function, derivative, &options).unwrap();
+    assert!(relative_eq!(solution.values[0], 1e8, max_relative = 1e-12));
+
+    // The criterion on the function adds iterations.
+    let function = |value: f64| value * value - 4.0;
+    let without = solve(3.0, function, derivative, &options).unwrap();
+    options.residual_tolerance = Some(1e-14);
+    let solution = solve(3.0, function, derivative, &options).unwrap();
+    assert!(function(solution.values[0]).abs() <= 1e-14);
+    assert!(solution.iterations >= without.iterations);
+
+    options.max_iterations = 2;
+    assert!(matches!(
+        solve(3.0, function, derivative, &options),
+        Err(NewtonMethodError::NotConverged { .. })
+    ));
+}
+
+#[test]
+fn damping_line_search() {
+    let function = |value: f64| value * value - 4.0;
+    let derivative = |value: f64| 2.0 * value;
+    let undamped = solve(3.0, function, derivative, &NewtonMethodOptions::default()).unwrap();
+    let options = NewtonMethodOptions {
+        damping: 0.5,
+        ..NewtonMethodOptions::default()
+    };
+    let damped = solve(3.0, function, derivative, &options).unwrap();
+    assert!(relative_eq!(damped.values[0], 2.0, epsilon = 1e-4));
+    assert!(damped.iterations > undamped.iterations);
+
+    // The full Newton steps of the arctangent diverge from 2, the halved steps do not.
+    let derivative = |value: f64| 1.0 / (1.0 + value * value);
+    assert!(solve(2.0, f64::atan, derivative, &NewtonMethodOptions::default()).is_err());
+    let options = NewtonMethodOptions {
+        line_search: true,
+        ..NewtonMethodOptions::default()
+    };
+    let solution = solve(2.0, f64::atan, derivative, &options).unwrap();
+    assert!(relative_eq!(solution.values[0], 0.0, epsilon = 1e-10));
+}
+
+#[test]
+fn bounds() {
+    let function = |value: f64| value * value - 4.0;
+    let derivative = |value: f64| 2.0 * value;
+    let solution = solve(-1.0, function, derivative, &NewtonMethodOptions::default()).unwrap();
+    assert!(relative_eq!(solution.values[0], -2.0, epsilon = 1e-10));
+
+    // The start value is clipped to the lower bound, and the upper bound is never crossed.
+    let options = NewtonMethodOptions {
+        lower_bound: Some(0.5),
+        upper_bound: Some(2.5),
+        ..NewtonMethodOptions::default()
+    };
+    let solution = solve(-1.0, function, derivative, &options).unwrap();
+    assert!(relative_eq!(solution.values[0], 2.0, epsilon = 1e-10));
+}
+
+#[test]
+fn invalid_options() {
+    let function = |value: f64| value * value - 4.0;
+    let derivative = |value: f64| 2.0 * value;
+    for options in [
+        NewtonMethodOptions {
+            damping: 0.0,
+            ..NewtonMethodOptions::default()
+        },
+        NewtonMethodOptions {
+            relative_tolerance: -1.0,
+            ..NewtonMethodOptions::default()
+        },
+        NewtonMethodOptions {
+            max_iterations: 0,
+            ..NewtonMethodOptions::default()
+        },
+        NewtonMethodOptions {
+            lower_bound: Some(1.0),
+            upper_bound: Some(0.0),
+            ..NewtonMethodOptions::default()
+        },
+    ] {
+        assert!(matches!(
+            solve(3.0, function, derivative, &options),
+            Err(NewtonMethodError::InvalidOptions(_))
+        ));
+    }
+}
+
+/// Target values of the functions, evaluated on the components that have not converged only.
+struct Subset {
+    values: List<f64>,
+}
+
+impl NewtonMethodArguments for Subset {
+    fn subset(&self, components: &[usize]) -> Option<Self> {
+        Some(Self {
+            values: List::from_iterator(
+                components.len(),
+                components.iter().map(|&index| self.values[index]),
+            ),
+        })
+    }
+}
+
+#[test]
+fn frozen_components() {
+    // The first function is linear and converges at the second iteration, the others are
+    // quadratic.
+    let targets = [1.0, 2.0, 9.0];
+    let sizes = RefCell::new(vec![]);
+    let function = |values: &List<f64>, targets: &List<f64>| {
+        sizes.borrow_mut().push(values.len());
+        List::from_iterator(
+            values.len(),
+            values.iter().zip(targets.iter()).map(|(value, target)| {
+                if *target == 1.0 {
+                    value - target
+                } else {
+                    value * value - target
+                }
+            }),
+        )
+    };
+    let derivative = |values: &List<f64>, targets: &List<f64>| {
+        List::from_iterator(
+            values.len(),
+            values
+                .iter()
+                .zip(targets.iter())
+                .map(|(value, target)| if *target == 1.0 { 1.0 } else { 2.0 * value }),
+        )
+    };
+
+    let solution = newton_method(
+        List::from_row_slice(&[0.0, 1.0, 1.0]),
+        |values, args: &Subset| function(values, &args.values),
+        |values, args| derivative(values, &args.values),
+        Subset {
+            values: List::from_row_slice(&targets),
+        },
+    )
+    .unwrap();
+    assert_eq!(solution.values[0], 1.0);
+    assert_eq!(solution.residuals[0], 0.0);
+    assert_eq!(solution.component_iterations[0], 2);
+    assert!(solution.component_iterations[1] < solution.component_iterations[2]);
+    assert_eq!(solution.iterations, solution.component_iterations[2]);
+    // Only the components that have not converged are evaluated.
+    let subset_sizes = sizes.borrow().clone();
+    assert_eq!(subset_sizes[..4], [3, 3, 3, 2]);
+    assert_eq!(*subset_sizes.last().unwrap(), 1);
+
+    // Without a subset of the arguments, all the components are evaluated, to the same
+    // solution.
+    sizes.borrow_mut().clear();
+    let full = newton_method(
+        List::from_row_slice(&[0.0, 1.0, 1.0]),
+        |values, args: &Targets| function(values, &args.values),
+        |values, args| derivative(values, &args.values),
+        Targets {
+            values: List::from_row_slice(&targets),
+        },
+    )
+    .unwrap();
+    assert!(sizes.borrow().iter().all(|&size| size == 3));
+    assert_eq!(full, solution);
+}
+
+/// Radiative equilibrium of a surface absorbing a flux, for any scalar.
+fn equilibrium<T: RealField>(values: &List<T>, args: &Targets) -> List<T> {
+    let emission: T = na::convert(0.9 * STEFAN_BOLTZMANN);
+    List::from_iterator(
+        values.len(),
+        values
+            .iter()
+            .zip(args.values.iter())
+            .map(|(&value, &flux)| value.powi(4) * emission - na::convert(flux)),
+    )
+}
+
+#[test]
+fn derivative_free() {
+    let fluxes = List::from_row_slice(&[1361.0, 100.0, 1.0]);
+    let expected = fluxes.map(|flux| (flux / (0.9 * STEFAN_BOLTZMANN)).powf(0.25));
+    let options = NewtonMethodOptions {
+        relative_tolerance: 1e-12,
+        absolute_tolerance: 0.0,
+        ..NewtonMethodOptions::default()
+    };
+    let analytic = newton_method_with_options(
+        List::from_element(3, 300.0),
+        equilibrium,
+        |values, _| pows(values, 3) * (4.0 * 0.9 * STEFAN_BOLTZMANN),
+        Targets {
+            values: fluxes.clone(),
+        },
+        &options,
+    )
+    .unwrap();
+
+    let finite_differences = newton_method_finite_differences(
+        List::from_element(3, 300.0),
+        equilibrium,
+        Targets {
+            values: fluxes.clone(),
+        },
+        FINITE_DIFFERENCE_STEP,
+        &options,
+    )
+    .unwrap();
+    assert!(relative_eq!(
+        finite_differences.values,
+        expected,
+        max_relative = 1e-12
+    ));
+
+    // The automatic differentiation follows the iterations with the analytic derivative.
+    let dual = newton_method_dual(
+        List::from_element(3, 300.0),
+        equilibrium,
+        Targets { values: fluxes },
+        &options,
+    )
+    .unwrap();
+    assert!(relative_eq!(dual.values, expected, max_relative = 1e-12));
+    assert_eq!(dual.component_iterations, analytic.component_iterations);
+
+    for step in [0.0, -1e-6, f64::INFINITY] {
+        assert!(matches!(
+            newton_method_finite_differences(
+                List::from_element(1, 300.0),
+                equilibrium,
+                Targets {
+                    values: List::from_element(1, 1.0),
+                },
+                step,
+                &options,
+            ),
+            Err(NewtonMethodError::InvalidOptions(_))
+        ));
+    }
+}
+
+/// Intersection of a circle of radius given by the arguments and of the line y = 2x.
+fn circle_line(values: &DVector<f64>, args: &Targets) -> DVector<f64> {
+    DVector::from_row_slice(&[
+        values[0] * values[0] + values[1] * values[1] - args.values[0] * args.values[0],
+        values[1] - 2.0 * values[0],
+    ])
+}
+
+/// Jacobian matrix of [`circle_line`].
+fn circle_line_jacobian(values: &DVector<f64>, _: &Targets) -> DMatrix<f64> {
+    DMatrix::from_row_slice(2, 2, &[2.0 * values[0], 2.0 * values[1], -2.0, 1.0])
+}
+
+#[test]
+fn coupled_system() {
+    let radius = Targets {
+        values: List::from_element(1, 5f64.sqrt()),
+    };
+    let expected = DVector::from_row_slice(&[1.0, 2.0]);
+    let options = NewtonMethodOptions {
+        residual_tolerance: Some(1e-12),
+        ..NewtonMethodOptions::default()
+    };
+    let solution = newton_raphson(
+        DVector::from_row_slice(&[3.0, 1.0]),
+        circle_line,
+        circle_line_jacobian,
+        radius,
+        &options,
+    )
+    .unwrap();
+    assert!(relative_eq!(solution.values, expected, epsilon = 1e-12));
+    assert!(solution.function_values.norm() <= 1e-12);
+    assert!(solution.iterations < 10);
+
+    let finite_differences = newton_raphson_finite_differences(
+        DVector::from_row_slice(&[3.0, 1.0]),
+        circle_line,
+        Targets {
+            values: List::from_element(1, 5f64.sqrt()),
+        },
+        FINITE_DIFFERENCE_STEP,
+        &options,
+    )
+    .unwrap();
+    assert!(relative_eq!(
+        finite_differences.values,
+        expected,
+        epsilon = 1e-12
+    ));
+
+    // The other intersection is excluded by the bounds.
+    let options = NewtonMethodOptions {
+        lower_bound: Some(-1.5),
+        upper_bound: Some(-0.5),
+        max_iterations: 100,
+        ..options
+    };
+    let solution = newton_raphson(
+        DVector::from_row_slice(&[3.0, 1.0]),
+        circle_line,
+        circle_line_jacobian,
+        Targets {
+            values: List::from_element(1, 5f64.sqrt()),
+        },
+        &options,
+    );
+    assert!(matches!(
+        solution,
+        Err(NewtonRaphsonError::NotConverged { .. })
+    ));
+}
+
+#[test]
+fn backtracking() {
+    // The full Newton steps of the arctangent of the sum diverge from far, the backtracked ones
+    // do not.
+    let function = |values: &DVector<f64>, _: &Targets| {
+        DVector::from_row_slice(&[(values[0] + values[1]).atan(), values[0] - 2.0 * values[1]])
+    };
+    let jacobian = |values: &DVector<f64>, _: &Targets| {
+        let derivative = 1.0 / (1.0 + (values[0] + values[1]).powi(2));
+        DMatrix::from_row_slice(2, 2, &[derivative, derivative, 1.0, -2.0])
+    };
+    let targets = || Targets {
+        values: List::zeros(0),
+    };
+    let start = DVector::from_row_slice(&[2.0, 1.0]);
+    assert!(newton_raphson(
+        start.clone(),
+        function,
+        jacobian,
+        targets(),
+        &NewtonMethodOptions::default()
+    )
+    .is_err());
+    let options = NewtonMethodOptions {
+        line_search: true,
+        ..NewtonMethodOptions::default()
+    };
+    let solution = newton_raphson(start, function, jacobian, targets(), &options).unwrap();
+    assert!(relative_eq!(
+        solution.values,
+        DVector::zeros(2),
+        epsilon = 1e-10
+    ));
+}
+
+#[test]
+fn coupled_system_errors() {
+    let targets = || Targets {
+        values: List::zeros(0),
+    };
+    let options = NewtonMethodOptions::default();
+    let start = DVector::from_row_slice(&[0.0, 0.0]);
+    let error = newton_raphson(
+        start.clone(),
+        |values, _| DVector::from_row_slice(&[values[0] + values[1] - 1.0, values.sum() * 2.0]),
+        |_, _| DMatrix::from_row_slice(2, 2, &[1.0, 1.0, 2.0, 2.0]),
+        targets(),
+        &options,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        NewtonRaphsonError::SingularJacobian {
+            iteration: 1,
+            values: start.clone(),
+        }
+    );
+
+    let error = newton_raphson(
+        start.clone(),
+        |values, _| values.clone(),
+        |_, _| DMatrix::identity(3, 3),
+        targets(),
+        &options,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        NewtonRaphsonError::DimensionMismatch {
+            expected: (2, 2),
+            found: (3, 3),
+        }
+    );
+
+    let error = newton_raphson(
+        start,
+        |values, _| values.map(f64::ln),
+        |values, _| DMatrix::from_diagonal(&values.map(|value| 1.0 / value)),
+        targets(),
+        &options,
+    )
+    .unwrap_err();
+    assert!(matches!(
+        error,
+        NewtonRaphsonError::NonFinite { iteration: 1, .. }
+    ));
+}
@@ -0,0 +1,252 @@
+use itertools::multizip;
+use tool::{
+    KeplerError, List, OrbitError, OrbitalElements, Vector, DEG2RAD, PARABOLIC_TOLERANCE, TAU,
+};
+
+const GM_SUN: f64 = 1.32712440018e20;
+const GM_EARTH: f64 = 3.986004418e14;
+
+fn assert_elements_eq(elements: &OrbitalElements, expected_elements: &OrbitalElements) {
+    assert!(relative_eq!(
+        elements.semi_major_axis,
+        expected_elements.semi_major_axis,
+        max_relative = 1e-10
+    ));
+    assert!(relative_eq!(
+        elements.eccentricity,
+        expected_elements.eccentricity,
+        epsilon = 1e-10
+    ));
+    assert!(relative_eq!(
+        elements.inclination,
+        expected_elements.inclination,
+        epsilon = 1e-10
+    ));
+    assert!(relative_eq!(
+        elements.longitude_ascending_node,
+        expected_elements.longitude_ascending_node,
+        epsilon = 1e-10
+    ));
+    assert!(relative_eq!(
+        elements.argument_periapsis,
+        expected_elements.argument_periapsis,
+        epsilon = 1e-10
+    ));
+    assert!(relative_eq!(
+        elements.mean_anomaly,
+        expected_elements.mean_anomaly,
+        epsilon = 1e-10
+    ));
+}
+
+#[test]
+fn mean_to_eccentric_anomaly() {
+    // Vallado, Fundamentals of Astrodynamics and Applications, example 2-1.
+    let mean_anomalies = List::from_column_slice(&[235.4 * DEG2RAD]);
+
+    let eccentric_anomalies = tool::mean_to_eccentric_anomaly(&mean_anomalies, 0.4).unwrap();
+
+    assert!(relative_eq!(
+        eccentric_anomalies[0],
+        3.848_661_745_1,
+        epsilon = 1e-10
+    ));
+}
+
+#[test]
+fn anomalies_round_trip() {
+    let mean_anomalies = List::from_column_slice(&[0.0, 0.5, 2.0, 3.1, 5.0, -1.0, 15.0]);
+
+    for eccentricity in [0.0, 0.1, 0.5, 0.9, 0.99, 1.0, 1.5, 4.0].iter() {
+        let true_anomalies = tool::mean_to_true_anomaly(&mean_anomalies, *eccentricity).unwrap();
+        let round_trip = tool::true_to_mean_anomaly(&true_anomalies, *eccentricity);
+        for (mean, expected_mean) in multizip((round_trip.iter(), mean_anomalies.iter())) {
+            assert!(relative_eq!(mean, expected_mean, epsilon = 1e-10));
+        }
+    }
+}
+
+#[test]
+fn nearly_parabolic_anomalies() {
+    // Within the tolerance, the conversions and Kepler's equation use the parabolic anomaly.
+    let mean_anomalies = List::from_column_slice(&[0.5, -2.0]);
+    let eccentricity = 1.0 + PARABOLIC_TOLERANCE / 2.0;
+
+    let parabolic_anomalies =
+        tool::mean_to_eccentric_anomaly(&mean_anomalies, eccentricity).unwrap();
+    let true_anomalies = tool::mean_to_true_anomaly(&mean_anomalies, eccentricity).unwrap();
+
+    assert_eq!(
+        parabolic_anomalies,
+        tool::mean_to_eccentric_anomaly(&mean_anomalies, 1.0).unwrap()
+    );
+    assert_eq!(
+        true_anomalies,
+        tool::mean_to_true_anomaly(&mean_anomalies, 1.0).unwrap()
+    );
+}
+
+#[test]
+fn parabolic_state() {
+    let elements = OrbitalElements {
+        semi_major_axis: f64::INFINITY,
+        eccentricity: 1.0,
+        inclination: 0.0,
+        longitude_ascending_node: 0.0,
+        argument_periapsis: 0.0,
+        mean_anomaly: 0.5,
+    };
+
+    assert_eq!(elements.to_state(GM_SUN), Err(OrbitError::ParabolicOrbit));
+}
+
+#[test]
+fn eccentric_to_true_anomaly() {
+    let eccentric_anomalies = List::from_column_slice(&[TAU / 4.0]);
+
+    let true_anomalies = tool::eccentric_to_true_anomaly(&eccentric_anomalies, 0.5);
+
+    // At E = 90°, cos(nu) = -e.
+    assert!(relative_eq!(true_anomalies[0].cos(), -0.5, epsilon = 1e-15));
+}
+
+#[test]
+fn circular_orbit_state() {
+    let radius = 7.0e6;
+    let elements = OrbitalElements {
+        semi_major_axis: radius,
+        eccentricity: 0.0,
+        inclination: 0.0,
+        longitude_ascending_node: 0.0,
+        argument_periapsis: 0.0,
+        mean_anomaly: TAU / 4.0,
+    };
+
+    let (position, velocity) = elements.to_state(GM_EARTH).unwrap();
+
+    let speed = (GM_EARTH / radius).sqrt();
+    assert!(relative_eq!(
+        position,
+        Vector::new(0.0, radius, 0.0),
+        epsilon = 1e-8
+    ));
+    assert!(relative_eq!(
+        velocity,
+        Vector::new(-speed, 0.0, 0.0),
+        epsilon = 1e-10
+    ));
+    assert!(relative_eq!(
+        elements.period(GM_EARTH),
+        TAU * (radius.powi(3) / GM_EARTH).sqrt(),
+        epsilon = 1e-10
+    ));
+}
+
+#[test]
+fn state_round_trip_elliptic() {
+    let elements = OrbitalElements {
+        semi_major_axis: 2.7 * tool::ASTRONAUMICAL_UNIT,
+        eccentricity: 0.25,
+        inclination: 12.0 * DEG2RAD,
+        longitude_ascending_node: 80.0 * DEG2RAD,
+        argument_periapsis: 300.0 * DEG2RAD,
+        mean_anomaly: 2.0,
+    };
+
+    let (position, velocity) = elements.to_state(GM_SUN).unwrap();
+    let round_trip = OrbitalElements::from_state(&position, &velocity, GM_SUN).unwrap();
+
+    assert_elements_eq(&round_trip, &elements);
+}
+
+#[test]
+fn state_round_trip_hyperbolic() {
+    let elements = OrbitalElements::from_true_anomaly(
+        -1.2 * tool::ASTRONAUMICAL_UNIT,
+        1.8,
+        120.0 * DEG2RAD,
+        10.0 * DEG2RAD,
+        45.0 * DEG2RAD,
+        -60.0 * DEG2RAD,
+    )
+    .unwrap();
+
+    let (position, velocity) = elements.to_state(GM_SUN).unwrap();
+    let round_trip = OrbitalElements::from_state(&position, &velocity, GM_SUN).unwrap();
+
+    assert!(relative_eq!(
+        elements.true_anomaly().unwrap(),
+        -60.0 * DEG2RAD,
+        epsilon = 1e-12
+    ));
+    assert_elements_eq(&round_trip, &elements);
+}
+
+#[test]
+fn state_from_vallado() {
+    // Vallado, Fundamentals of Astrodynamics and Applications, example 2-5.
+    let position = Vector::new(6524.834, 6862.875, 6448.296) * 1e3;
+    let velocity = Vector::new(4.901327, 5.533756, -1.976341) * 1e3;
+
+    let elements = OrbitalElements::from_state(&position, &velocity, GM_EARTH).unwrap();
+
+    assert!(relative_eq!(
+        elements.semi_major_axis,
+        36127.343e3,
+        max_relative = 1e-5
+    ));
+    assert!(relative_eq!(
+        elements.eccentricity,
+        0.832853,
+        epsilon = 1e-5
+    ));
+    assert!(relative_eq!(
+        elements.inclination,
+        87.870 * DEG2RAD,
+        epsilon = 1e-4
+    ));
+    assert!(relative_eq!(
+        elements.longitude_ascending_node,
+        227.898 * DEG2RAD,
+        epsilon = 1e-4
+    ));
+    assert!(relative_eq!(
+        elements.argument_periapsis,
+        53.38 * DEG2RAD,
+        epsilon = 1e-4
+    ));
+    assert!(relative_eq!(
+        elements.true_anomaly().unwrap(),
+        92.335 * DEG2RAD,
+        epsilon = 1e-4
+    ));
+}
+
+#[test]
+fn invalid_elements() {
+    let elements = OrbitalElements {
+        semi_major_axis: 1.5e11,
+        eccentricity: 0.2,
+        inclination: 0.0,
+        longitude_ascending_node: 0.0,
+        argument_periapsis: 0.0,
+        mean_anomaly: f64::NAN,
+    };
+
+    assert!(matches!(
+        elements.true_anomaly(),
+        Err(KeplerError::InvalidMeanAnomaly { index: 0, .. })
+    ));
+    assert!(matches!(
+        elements.to_state(GM_SUN),
+        Err(OrbitError::Kepler(KeplerError::InvalidMeanAnomaly { .. }))
+    ));
+    assert_eq!(
+        OrbitalElements::from_true_anomaly(1.5e11, -0.1, 0.0, 0.0, 0.0, 0.0),
+        Err(KeplerError::InvalidEccentricity(-0.1))
+    );
+    assert_eq!(
+        tool::mean_to_true_anomaly(&List::from_element(1, 0.5), f64::INFINITY),
+        Err(KeplerError::InvalidEccentricity(f64::INFINITY))
+    );
+}
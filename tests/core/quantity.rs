@@ -0,0 +1,123 @@
+use tool::{
+    Acceleration, Angle, AngularVelocity, Area, Energy, Irradiance, Length, List, Mass, Power,
+    Temperature, Time, Vector, Velocity, ASTRONAUMICAL_UNIT, DAY, DEG2RAD, HOUR,
+};
+
+#[test]
+fn conversions() {
+    assert_eq!(Length::from_kilometers(2.5).meters(), 2500.0);
+    assert_eq!(
+        Length::from_astronomical_units(2.0).meters(),
+        2.0 * ASTRONAUMICAL_UNIT
+    );
+    assert_eq!(Time::from_days(1.5).hours(), 36.0);
+    assert_eq!(Time::from_years(1.0).days(), 365.25);
+    assert!(relative_eq!(
+        Angle::from_degrees(90.0).radians(),
+        std::f64::consts::FRAC_PI_2
+    ));
+    assert_eq!(Mass::from_grams(1500.0).kilograms(), 1.5);
+    assert!(relative_eq!(
+        Temperature::from_celsius(-273.15).kelvins(),
+        0.0
+    ));
+    assert!(relative_eq!(
+        Temperature::from_kelvins(300.0).celsius(),
+        26.85,
+        epsilon = 1e-12
+    ));
+    assert_eq!(Power::from_kilowatts(1.361).watts(), 1361.0);
+}
+
+#[test]
+fn typed_constants() {
+    assert_eq!(*Time::HOUR.value(), HOUR);
+    assert_eq!(Time::DAY.into_value(), DAY);
+    assert_eq!(Time::DAY / Time::HOUR, 24.0);
+    assert_eq!(Length::ASTRONOMICAL_UNIT.astronomical_units(), 1.0);
+    assert_eq!(Angle::DEGREE.radians(), DEG2RAD);
+    assert!(relative_eq!(Angle::TURN.degrees(), 360.0));
+    assert!(relative_eq!(
+        (Angle::DEGREE * 30.0).sin(),
+        0.5,
+        epsilon = 1e-15
+    ));
+}
+
+#[test]
+fn arithmetic() {
+    let mut duration = Time::from_hours(1.0) + Time::from_minutes(30.0);
+    duration -= Time::from_seconds(1800.0);
+    duration += Time::MINUTE * 2.0;
+
+    assert_eq!(duration.minutes(), 62.0);
+    assert_eq!((-duration).minutes(), -62.0);
+    assert_eq!((duration / 2.0).minutes(), 31.0);
+    assert!(Time::HOUR > Time::MINUTE);
+    assert_eq!(Length::from_meters(1.5).to_string(), "1.5 m");
+}
+
+#[test]
+fn arrays() {
+    let lengths = Length::from_kilometers(List::from_column_slice(&[1.0, 2.0, 3.0]));
+    let doubled = lengths.clone() + lengths.clone();
+
+    assert_eq!(lengths.len(), 3);
+    assert_eq!(lengths.at(1).meters(), 2000.0);
+    assert_eq!(
+        doubled.kilometers(),
+        List::from_column_slice(&[2.0, 4.0, 6.0])
+    );
+    assert_eq!(
+        lengths.iter().map(|length| length.meters()).sum::<f64>(),
+        6000.0
+    );
+
+    let position = Length::ASTRONOMICAL_UNIT * Vector::new(3.0, 4.0, 0.0);
+    assert_eq!(position.norm().astronomical_units(), 5.0);
+    assert_eq!(position.at(0).astronomical_units(), 3.0);
+    assert_eq!(position.astronomical_units(), Vector::new(3.0, 4.0, 0.0));
+    let temperatures = Temperature::new(List::from_column_slice(&[100.0, 200.0]));
+    assert_eq!(
+        temperatures
+            .map(|values| values.map(|value| value * 2.0))
+            .kelvins(),
+        List::from_column_slice(&[200.0, 400.0])
+    );
+}
+
+#[test]
+fn derived_dimensions() {
+    let side = Length::from_meters(3.0);
+    let area: Area = side * Length::from_meters(2.0);
+    let velocity: Velocity = Length::from_kilometers(7.2) / Time::HOUR;
+    let acceleration: Acceleration = velocity / Time::from_seconds(4.0);
+    let rate: AngularVelocity = Angle::TURN / Time::DAY;
+    let energy: Energy = Power::from_kilowatts(2.0) * Time::MINUTE;
+    let irradiance: Irradiance = Power::from_watts(12.0) / area;
+
+    assert_eq!(area.square_meters(), 6.0);
+    assert_eq!(area / side, Length::from_meters(2.0));
+    assert!(relative_eq!(velocity.meters_per_second(), 2.0));
+    assert!(relative_eq!(acceleration.meters_per_second_squared(), 0.5));
+    assert!(relative_eq!(
+        (acceleration * Time::from_seconds(4.0)).meters_per_second(),
+        2.0
+    ));
+    assert!(relative_eq!(rate.degrees_per_day(), 360.0));
+    assert!(relative_eq!((Time::DAY * rate).degrees(), 360.0));
+    assert_eq!(energy.joules(), 120_000.0);
+    assert_eq!(energy / Time::MINUTE, Power::from_kilowatts(2.0));
+    assert_eq!(irradiance.watts_per_square_meter(), 2.0);
+    assert_eq!(irradiance * area, Power::from_watts(12.0));
+    assert_eq!(
+        velocity.to_string(),
+        format!("{} m/s", velocity.meters_per_second())
+    );
+
+    let positions = Length::from_meters(1.0) * Vector::new(2.0, 4.0, 6.0);
+    assert_eq!(
+        (positions / Time::from_seconds(2.0)).into_value(),
+        Vector::new(1.0, 2.0, 3.0)
+    );
+}
@@ -0,0 +1,147 @@
+use super::sphere;
+use tool::{Bvh, Mesh, Vector, Vectors};
+
+fn triangle() -> [Vector<f64>; 3] {
+    [Vector::zeros(), Vector::x(), Vector::y()]
+}
+
+/// Closest intersection by testing all the faces.
+fn brute_force(
+    mesh: &Mesh<f64>,
+    origin: &Vector<f64>,
+    direction: &Vector<f64>,
+) -> Option<(f64, usize)> {
+    let mut first: Option<(f64, usize)> = None;
+    for (index, face) in mesh.faces().iter().enumerate() {
+        let vertices = [
+            mesh.vertices().column(face[0]).into_owned(),
+            mesh.vertices().column(face[1]).into_owned(),
+            mesh.vertices().column(face[2]).into_owned(),
+        ];
+        if let Some(distance) = tool::ray_triangle_intersection(origin, direction, &vertices) {
+            match first {
+                Some((first_distance, _)) if first_distance <= distance => (),
+                _ => first = Some((distance, index)),
+            }
+        }
+    }
+    first
+}
+
+#[test]
+fn ray_triangle_intersection() {
+    let distance = tool::ray_triangle_intersection(
+        &Vector::new(0.2, 0.2, 1.0),
+        &Vector::new(0.0, 0.0, -2.0),
+        &triangle(),
+    );
+
+    assert!(relative_eq!(distance.unwrap(), 0.5, epsilon = f64::EPSILON));
+}
+
+#[test]
+fn ray_triangle_no_intersection() {
+    let outside = tool::ray_triangle_intersection(
+        &Vector::new(0.8, 0.8, 1.0),
+        &Vector::new(0.0, 0.0, -1.0),
+        &triangle(),
+    );
+    let parallel = tool::ray_triangle_intersection(
+        &Vector::new(0.2, 0.2, 1.0),
+        &Vector::new(1.0, 0.0, 0.0),
+        &triangle(),
+    );
+    let behind = tool::ray_triangle_intersection(
+        &Vector::new(0.2, 0.2, 1.0),
+        &Vector::new(0.0, 0.0, 1.0),
+        &triangle(),
+    );
+
+    assert_eq!(outside, None);
+    assert_eq!(parallel, None);
+    assert_eq!(behind, None);
+}
+
+#[test]
+fn bvh_all_hits() {
+    let mesh = sphere(12, 24);
+    let bvh = Bvh::from_mesh(&mesh);
+    let origin = Vector::new(0.01, 0.02, -5.0);
+
+    let hits = bvh.all_hits(&origin, &Vector::z(), None);
+
+    assert_eq!(bvh.number_faces(), mesh.number_faces());
+    assert_eq!(hits.len(), 2);
+    assert!(hits[0].distance < hits[1].distance);
+    assert!(relative_eq!(hits[0].distance, 4.0, epsilon = 0.05));
+    assert!(relative_eq!(hits[1].distance, 6.0, epsilon = 0.05));
+}
+
+#[test]
+fn bvh_first_hits_brute_force() {
+    let mesh = sphere(12, 24);
+    let bvh = Bvh::from_mesh(&mesh);
+    let size = 200;
+    let mut origins = Vectors::zeros(size);
+    let mut directions = Vectors::zeros(size);
+    for index in 0..size {
+        let angle = index as f64 * 0.37;
+        origins.column_mut(index).copy_from(&Vector::new(
+            3.0 * angle.cos(),
+            3.0 * angle.sin(),
+            0.1 * angle.cos(),
+        ));
+        directions.column_mut(index).copy_from(&Vector::new(
+            -angle.cos() + 0.3 * (1.3 * angle).sin(),
+            -angle.sin() + 0.3 * (0.7 * angle).cos(),
+            0.2 * (2.1 * angle).sin(),
+        ));
+    }
+
+    let hits = bvh.first_hits(&origins, &directions, None);
+    let any_hits = bvh.any_hits(&origins, &directions, None);
+
+    for index in 0..size {
+        let expected_hit = brute_force(
+            &mesh,
+            &origins.column(index).into_owned(),
+            &directions.column(index).into_owned(),
+        );
+        match (hits[index], expected_hit) {
+            (Some(hit), Some((distance, face))) => {
+                assert_eq!(hit.face, face);
+                assert!(relative_eq!(hit.distance, distance, epsilon = f64::EPSILON));
+            }
+            (None, None) => (),
+            _ => panic!("ray {} differs from the brute force", index),
+        }
+        assert_eq!(any_hits[index], hits[index].is_some());
+    }
+}
+
+#[test]
+fn bvh_any_hit_max_distance() {
+    let mesh = sphere(8, 16);
+    let bvh = Bvh::from_mesh(&mesh);
+    let origin = Vector::new(0.0, 0.0, -5.0);
+
+    assert!(bvh.any_hit(&origin, &Vector::z(), None));
+    assert!(!bvh.any_hit(&origin, &Vector::z(), Some(3.5)));
+    assert!(!bvh.any_hit(&origin, &-Vector::z(), None));
+}
+
+#[test]
+fn bvh_no_self_intersection() {
+    let mesh = sphere(8, 16);
+    let bvh = Bvh::from_mesh(&mesh);
+    let centroids = mesh.face_centroids();
+    let normals = mesh.face_normals();
+
+    let outward = bvh.first_hits(&centroids, &normals, None);
+    let inward = bvh.first_hits(&centroids, &-&normals, None);
+
+    assert!(outward.iter().all(|hit| hit.is_none()));
+    for (face, hit) in inward.iter().enumerate() {
+        assert_ne!(hit.unwrap().face, face);
+    }
+}
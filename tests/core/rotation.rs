@@ -0,0 +1,139 @@
+use itertools::multizip;
+use na::UnitQuaternion;
+use tool::{EulerSequence, Rotation, Vector, Vectors, DEG2RAD};
+
+const SEQUENCES: [EulerSequence; 12] = [
+    EulerSequence::XYX,
+    EulerSequence::XZX,
+    EulerSequence::YXY,
+    EulerSequence::YZY,
+    EulerSequence::ZXZ,
+    EulerSequence::ZYZ,
+    EulerSequence::XYZ,
+    EulerSequence::XZY,
+    EulerSequence::YXZ,
+    EulerSequence::YZX,
+    EulerSequence::ZXY,
+    EulerSequence::ZYX,
+];
+
+#[test]
+fn elementary_rotation() {
+    let rotation = tool::elementary_rotation(2, 90.0 * DEG2RAD);
+
+    assert!(relative_eq!(
+        rotation * Vector::x(),
+        Vector::y(),
+        epsilon = f64::EPSILON
+    ));
+}
+
+#[test]
+fn rotation_from_euler_yaw_pitch_roll() {
+    let (yaw, pitch, roll) = (0.3, -0.7, 1.2);
+
+    let rotation = tool::rotation_from_euler(EulerSequence::ZYX, &Vector::new(yaw, pitch, roll));
+
+    assert!(relative_eq!(
+        rotation,
+        Rotation::from_euler_angles(roll, pitch, yaw),
+        epsilon = 1e-15
+    ));
+}
+
+#[test]
+fn rotation_from_euler_sequences() {
+    let angles = Vector::new(0.3, -0.7, 1.2);
+
+    for sequence in SEQUENCES.iter() {
+        let rotation = tool::rotation_from_euler(*sequence, &angles);
+        let [first, second, third] = sequence.axes();
+        let expected_rotation = tool::elementary_rotation(first, angles[0])
+            * tool::elementary_rotation(second, angles[1])
+            * tool::elementary_rotation(third, angles[2]);
+
+        assert!(relative_eq!(rotation, expected_rotation, epsilon = 1e-15));
+        assert!(relative_eq!(
+            rotation.matrix().determinant(),
+            1.0,
+            epsilon = 1e-15
+        ));
+    }
+}
+
+#[test]
+fn rotation_from_euler_extrinsic() {
+    let angles = Vector::new(0.3, -0.7, 1.2);
+    let reversed_angles = Vector::new(angles[2], angles[1], angles[0]);
+    let [first, second, third] = EulerSequence::XYZ.axes();
+    let expected_rotation = tool::elementary_rotation(third, angles[2])
+        * tool::elementary_rotation(second, angles[1])
+        * tool::elementary_rotation(first, angles[0]);
+
+    let rotation = tool::rotation_from_euler(EulerSequence::XYZ.reversed(), &reversed_angles);
+
+    assert!(relative_eq!(rotation, expected_rotation, epsilon = 1e-15));
+}
+
+#[test]
+fn rotation_from_axis_angle() {
+    let rotation = tool::rotation_from_axis_angle(&Vector::new(1.0, 1.0, 1.0), 120.0 * DEG2RAD);
+
+    assert!(relative_eq!(
+        rotation * Vector::x(),
+        Vector::y(),
+        epsilon = 1e-15
+    ));
+}
+
+#[test]
+fn rotate() {
+    let vectors = Vectors::from_column_slice(&[1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 2.0, 3.0]);
+    let expected_vectors =
+        Vectors::from_column_slice(&[0.0, 1.0, 0.0, -1.0, 0.0, 0.0, -2.0, 1.0, 3.0]);
+    let rotation = tool::elementary_rotation(2, 90.0 * DEG2RAD);
+
+    let rotated = tool::rotate(&vectors, &rotation);
+
+    for (component, expected_component) in multizip((rotated.iter(), expected_vectors.iter())) {
+        assert!(relative_eq!(component, expected_component, epsilon = 1e-15));
+    }
+}
+
+#[test]
+fn rotate_inverse() {
+    let vectors = Vectors::from_column_slice(&[1.0, 2.0, 3.0, -1.0, 0.5, -2.0]);
+    let rotation = tool::rotation_from_euler(EulerSequence::ZXZ, &Vector::new(0.3, -0.7, 1.2));
+
+    let round_trip = tool::rotate_inverse(&tool::rotate(&vectors, &rotation), &rotation);
+
+    for (component, expected_component) in multizip((round_trip.iter(), vectors.iter())) {
+        assert!(relative_eq!(component, expected_component, epsilon = 1e-14));
+    }
+}
+
+#[test]
+fn rotate_quaternion() {
+    let vectors = Vectors::from_column_slice(&[1.0, 2.0, 3.0, -1.0, 0.5, -2.0]);
+    let quaternion = UnitQuaternion::from_scaled_axis(Vector::new(0.1, -0.4, 0.8));
+    let rotation = tool::rotation_from_quaternion(&quaternion);
+
+    let rotated = tool::rotate_quaternion(&vectors.columns(0, 2), &quaternion);
+    let round_trip = tool::rotate_quaternion_inverse(&rotated, &quaternion);
+
+    for (vector, rotated_vector) in multizip((vectors.column_iter(), rotated.column_iter())) {
+        assert!(relative_eq!(
+            rotation * vector,
+            rotated_vector.into_owned(),
+            epsilon = 1e-14
+        ));
+        assert!(relative_eq!(
+            quaternion * vector.into_owned(),
+            rotated_vector.into_owned(),
+            epsilon = 1e-14
+        ));
+    }
+    for (component, expected_component) in multizip((round_trip.iter(), vectors.iter())) {
+        assert!(relative_eq!(component, expected_component, epsilon = 1e-14));
+    }
+}
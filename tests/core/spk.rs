@@ -0,0 +1,273 @@
+use super::temp_path;
+use tool::{Epoch, Spk, SpkError, TimeScale, Vector, OBLIQUITY_J2000};
+
+/// Segment of a test kernel: target, center, frame, type, start, end and data.
+type Segment = (i32, i32, i32, i32, f64, f64, Vec<f64>);
+
+/// Bytes of a DAF/SPK file with a single summary record followed by its name record and the data
+/// of the segments.
+fn kernel(segments: &[Segment], big_endian: bool) -> Vec<u8> {
+    let double = |value: f64| {
+        if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        }
+    };
+    let integer = |value: i32| {
+        if big_endian {
+            value.to_be_bytes()
+        } else {
+            value.to_le_bytes()
+        }
+    };
+    let mut data = vec![0; 3 * 1024];
+    let mut address = 3 * 128 + 1;
+
+    // File record.
+    data[0..8].copy_from_slice(b"DAF/SPK ");
+    data[8..12].copy_from_slice(&integer(2));
+    data[12..16].copy_from_slice(&integer(6));
+    data[16..76].copy_from_slice(&[b' '; 60]);
+    data[76..80].copy_from_slice(&integer(2));
+    data[80..84].copy_from_slice(&integer(2));
+    data[88..96].copy_from_slice(if big_endian { b"BIG-IEEE" } else { b"LTL-IEEE" });
+
+    // Summary and name records.
+    data[1024 + 16..1024 + 24].copy_from_slice(&double(segments.len() as f64));
+    for (index, (target, center, frame, data_type, start, end, values)) in
+        segments.iter().enumerate()
+    {
+        let summary = 1024 + 24 + index * 40;
+        data[summary..summary + 8].copy_from_slice(&double(*start));
+        data[summary + 8..summary + 16].copy_from_slice(&double(*end));
+        let end_address = address + values.len() - 1;
+        for (component, value) in [
+            *target,
+            *center,
+            *frame,
+            *data_type,
+            address as i32,
+            end_address as i32,
+        ]
+        .iter()
+        .enumerate()
+        {
+            let offset = summary + 16 + component * 4;
+            data[offset..offset + 4].copy_from_slice(&integer(*value));
+        }
+        let name = format!("{:<40}", format!("SEGMENT {}", index));
+        data[2048 + index * 40..2048 + (index + 1) * 40].copy_from_slice(name.as_bytes());
+        for value in values {
+            data.extend_from_slice(&double(*value));
+        }
+        address = end_address + 1;
+    }
+    data.resize(data.len().div_ceil(1024) * 1024, 0);
+    data
+}
+
+/// Data of a segment of type 2 with records of 100 s from 0 s, whose coefficients are the same
+/// for the three components up to a factor.
+fn chebyshev(records: &[[f64; 3]]) -> Vec<f64> {
+    let mut data = vec![];
+    for (index, coefficients) in records.iter().enumerate() {
+        data.extend_from_slice(&[100.0 * index as f64 + 50.0, 50.0]);
+        for factor in &[1.0, 2.0, -3.0] {
+            data.extend(coefficients.iter().map(|coefficient| coefficient * factor));
+        }
+    }
+    data.extend_from_slice(&[0.0, 100.0, 11.0, records.len() as f64]);
+    data
+}
+
+/// Data of a segment of type 2 with a constant position, in km.
+fn constant(position: [f64; 3]) -> Vec<f64> {
+    let mut data = vec![0.0, 1e10];
+    data.extend_from_slice(&position);
+    data.extend_from_slice(&[-1e10, 2e10, 5.0, 1.0]);
+    data
+}
+
+fn epoch(seconds: f64) -> Epoch {
+    Epoch::from_seconds_past_j2000(seconds, TimeScale::TDB)
+}
+
+#[test]
+fn chebyshev_type_2() {
+    let data = chebyshev(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+    let spk = Spk::from_bytes(kernel(&[(399, 3, 1, 2, 0.0, 200.0, data)], false)).unwrap();
+    assert_eq!(spk.segments().len(), 1);
+    assert_eq!(spk.segments()[0].name, "SEGMENT 0");
+    assert_eq!(spk.segments()[0].end, 200.0);
+
+    // Second record, centered on 150 s with a radius of 50 s.
+    let s: f64 = (130.0 - 150.0) / 50.0;
+    let x = 4.0 + 5.0 * s + 6.0 * (2.0 * s * s - 1.0);
+    let dx = (5.0 + 24.0 * s) / 50.0;
+    let (position, velocity) = spk.state(399, 3, &epoch(130.0)).unwrap();
+    assert!(relative_eq!(
+        position,
+        Vector::new(x, 2.0 * x, -3.0 * x) * 1e3,
+        epsilon = 1e-9
+    ));
+    assert!(relative_eq!(
+        velocity,
+        Vector::new(dx, 2.0 * dx, -3.0 * dx) * 1e3,
+        epsilon = 1e-9
+    ));
+
+    // The end of the coverage belongs to the last record.
+    let (position, _) = spk.state(399, 3, &epoch(200.0)).unwrap();
+    assert!(relative_eq!(position[0], 15e3, epsilon = 1e-9));
+}
+
+#[test]
+fn chebyshev_type_3() {
+    let data = vec![
+        50.0, 50.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0, 11.0, 12.0, 0.0, 100.0,
+        14.0, 1.0,
+    ];
+    let spk = Spk::from_bytes(kernel(&[(-10, 10, 1, 3, 0.0, 100.0, data)], false)).unwrap();
+    let (position, velocity) = spk.state(-10, 10, &epoch(75.0)).unwrap();
+    assert!(relative_eq!(
+        position,
+        Vector::new(2.0, 5.0, 8.0) * 1e3,
+        epsilon = 1e-9
+    ));
+    assert!(relative_eq!(
+        velocity,
+        Vector::new(11.0, 14.0, 17.0) * 1e3,
+        epsilon = 1e-9
+    ));
+}
+
+#[test]
+fn hermite_type_13() {
+    // Cubic motion sampled at unequal steps, which the Hermite polynomials of a window of four
+    // states reproduce exactly.
+    let position = |t: f64| Vector::new(1.0 + 2.0 * t, t * t - 3.0, 0.5 * t.powi(3) - t);
+    let velocity = |t: f64| Vector::new(2.0, 2.0 * t, 1.5 * t * t - 1.0);
+    let epochs = [0.0, 0.7, 1.5, 3.0, 3.2, 5.0];
+    let mut data = vec![];
+    for &t in &epochs {
+        data.extend(position(t).iter().chain(velocity(t).iter()));
+    }
+    data.extend_from_slice(&epochs);
+    data.extend_from_slice(&[3.0, epochs.len() as f64]);
+    let spk = Spk::from_bytes(kernel(&[(2000001, 10, 1, 13, 0.0, 5.0, data)], false)).unwrap();
+
+    for &t in &[0.0, 0.3, 1.5, 2.2, 3.1, 4.9, 5.0] {
+        let state = spk.state(2000001, 10, &epoch(t)).unwrap();
+        assert!(relative_eq!(state.0, position(t) * 1e3, epsilon = 1e-9));
+        assert!(relative_eq!(state.1, velocity(t) * 1e3, epsilon = 1e-9));
+    }
+}
+
+#[test]
+fn chain() {
+    let segments = [
+        (3, 0, 1, 2, -1e9, 1e9, constant([100.0, 0.0, 0.0])),
+        (10, 0, 1, 2, -1e9, 1e9, constant([-1.0, 0.0, 0.0])),
+        (399, 3, 1, 2, -1e9, 1e9, constant([-1.0, 0.0, 0.0])),
+        (301, 3, 1, 2, -1e9, 1e9, constant([80.0, 0.5, 0.0])),
+    ];
+    let spk = Spk::from_bytes(kernel(&segments, false)).unwrap();
+    let epoch = epoch(0.0);
+
+    let (moon, _) = spk.state(301, 399, &epoch).unwrap();
+    assert!(relative_eq!(
+        moon,
+        Vector::new(81e3, 500.0, 0.0),
+        epsilon = 1e-6
+    ));
+    let (earth, _) = spk.state(399, 10, &epoch).unwrap();
+    assert!(relative_eq!(
+        earth,
+        Vector::new(100e3, 0.0, 0.0),
+        epsilon = 1e-6
+    ));
+    let (sun, _) = spk.state(10, 399, &epoch).unwrap();
+    assert!(relative_eq!(sun, -earth, epsilon = 1e-6));
+    let (barycenter, _) = spk.state(0, 301, &epoch).unwrap();
+    assert!(relative_eq!(
+        barycenter,
+        Vector::new(-180e3, -500.0, 0.0),
+        epsilon = 1e-6
+    ));
+    assert_eq!(spk.state(399, 399, &epoch).unwrap().0, Vector::zeros());
+}
+
+#[test]
+fn precedence_and_frames() {
+    let segments = [
+        (399, 3, 1, 2, -1e9, 1e9, constant([1.0, 0.0, 0.0])),
+        (399, 3, 17, 2, 0.0, 1e3, constant([0.0, 1.0, 0.0])),
+    ];
+    let spk = Spk::from_bytes(kernel(&segments, false)).unwrap();
+
+    // The last segment takes precedence within its coverage, and is rotated from the ecliptic.
+    let (position, _) = spk.state(399, 3, &epoch(500.0)).unwrap();
+    assert!(relative_eq!(
+        position,
+        Vector::new(0.0, OBLIQUITY_J2000.cos(), OBLIQUITY_J2000.sin()) * 1e3,
+        epsilon = 1e-9
+    ));
+    let (position, _) = spk.state(399, 3, &epoch(-500.0)).unwrap();
+    assert!(relative_eq!(
+        position,
+        Vector::new(1e3, 0.0, 0.0),
+        epsilon = 1e-9
+    ));
+}
+
+#[test]
+fn big_endian_file() {
+    let path = temp_path("big_endian.bsp");
+    let data = chebyshev(&[[1.0, 2.0, 3.0]]);
+    std::fs::write(&path, kernel(&[(5, 0, 1, 2, 0.0, 100.0, data)], true)).unwrap();
+    let spk = Spk::open(&path).unwrap();
+    let (position, _) = spk.state(5, 0, &epoch(50.0)).unwrap();
+    assert!(relative_eq!(position[0], -2e3, epsilon = 1e-9));
+}
+
+#[test]
+fn errors() {
+    let segments = [
+        (399, 3, 1, 2, 0.0, 200.0, chebyshev(&[[1.0, 2.0, 3.0]; 2])),
+        (499, 4, 1, 5, 0.0, 200.0, vec![0.0; 8]),
+        (599, 5, 10, 2, 0.0, 200.0, chebyshev(&[[1.0, 2.0, 3.0]; 2])),
+    ];
+    let spk = Spk::from_bytes(kernel(&segments, false)).unwrap();
+    assert!(matches!(
+        spk.state(399, 3, &epoch(300.0)),
+        Err(SpkError::NoCoverage { body: 399, .. })
+    ));
+    assert!(matches!(
+        spk.state(399, 4, &epoch(100.0)),
+        Err(SpkError::NoPath {
+            target: 399,
+            center: 4
+        })
+    ));
+    assert!(matches!(
+        spk.state(499, 4, &epoch(100.0)),
+        Err(SpkError::UnsupportedType(5))
+    ));
+    assert!(matches!(
+        spk.state(599, 5, &epoch(100.0)),
+        Err(SpkError::UnsupportedFrame(10))
+    ));
+
+    let mut data = kernel(&segments, false);
+    data[0..8].copy_from_slice(b"DAF/CK  ");
+    assert!(matches!(Spk::from_bytes(data), Err(SpkError::Format(_))));
+    assert!(matches!(
+        Spk::from_bytes(vec![0; 100]),
+        Err(SpkError::Format(_))
+    ));
+    assert!(matches!(
+        Spk::open(temp_path("missing.bsp")),
+        Err(SpkError::Io(_))
+    ));
+}
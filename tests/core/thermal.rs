@@ -0,0 +1,124 @@
+use tool::{
+    BottomBoundary, ConductionScheme, List, ThermalError, ThermalModel, ThermalProperties,
+    STEFAN_BOLTZMANN,
+};
+
+/// Material of diffusivity 1e-6 m²/s and thermal inertia 1000 in SI units.
+fn properties() -> ThermalProperties {
+    ThermalProperties {
+        conductivity: 1.0,
+        density: 1000.0,
+        heat_capacity: 1000.0,
+        emissivity: 0.9,
+    }
+}
+
+fn model(number_facets: usize, scheme: ConductionScheme) -> ThermalModel {
+    let mut model = ThermalModel::new(properties(), number_facets, 20, 0.01, 250.0).unwrap();
+    model.scheme = scheme;
+    model
+}
+
+#[test]
+fn properties_values() {
+    let properties = properties();
+    assert!(relative_eq!(properties.diffusivity(), 1e-6));
+    assert!(relative_eq!(properties.thermal_inertia(), 1000.0));
+    assert!(relative_eq!(
+        properties.skin_depth(std::f64::consts::PI * 1e4),
+        0.1,
+        epsilon = 1e-12
+    ));
+}
+
+#[test]
+fn radiative_equilibrium() {
+    let flux = 500.0;
+    let expected = (flux / (0.9 * STEFAN_BOLTZMANN)).powf(0.25);
+    for &(scheme, time_step) in &[
+        (ConductionScheme::Explicit, 50.0),
+        (ConductionScheme::CrankNicolson, 1000.0),
+    ] {
+        let mut model = model(2, scheme);
+        let fluxes = List::from_row_slice(&[flux, flux]);
+        for _ in 0..(5e5 / time_step) as usize {
+            model.step(&fluxes, time_step).unwrap();
+        }
+        for temperature in model.temperatures().iter() {
+            assert!(relative_eq!(*temperature, expected, epsilon = 1e-2));
+        }
+    }
+}
+
+#[test]
+fn bottom_gradient() {
+    // An internal heat flux gives a linear profile, and the surface radiates it in addition to
+    // the absorbed flux.
+    let gradient = 2.0;
+    let mut model = model(1, ConductionScheme::CrankNicolson);
+    model.bottom = BottomBoundary::Gradient(gradient);
+    let fluxes = List::from_row_slice(&[100.0]);
+    for _ in 0..3000 {
+        model.step(&fluxes, 1000.0).unwrap();
+    }
+    let surface = ((100.0 + gradient) / (0.9 * STEFAN_BOLTZMANN)).powf(0.25);
+    for (layer, temperature) in model.temperatures().column(0).iter().enumerate() {
+        let expected = surface + gradient * layer as f64 * model.depth_step();
+        assert!(relative_eq!(*temperature, expected, epsilon = 1e-2));
+    }
+}
+
+#[test]
+fn schemes_agree() {
+    // A lit facet heats up and a dark facet cools down, the same way with both schemes.
+    let fluxes = List::from_row_slice(&[800.0, 0.0]);
+    let mut explicit = model(2, ConductionScheme::Explicit);
+    let mut crank_nicolson = model(2, ConductionScheme::CrankNicolson);
+    for _ in 0..400 {
+        explicit.step(&fluxes, 10.0).unwrap();
+        crank_nicolson.step(&fluxes, 10.0).unwrap();
+    }
+    let surface = crank_nicolson.surface_temperatures();
+    assert!(surface[0] > 280.0);
+    assert!(surface[1] < 240.0);
+    assert!(relative_eq!(
+        explicit.temperatures(),
+        crank_nicolson.temperatures(),
+        epsilon = 0.5
+    ));
+}
+
+#[test]
+fn errors() {
+    let mut model = model(2, ConductionScheme::Explicit);
+    assert!(relative_eq!(model.maximum_explicit_time_step(), 50.0));
+    let fluxes = List::from_row_slice(&[100.0, 100.0]);
+    let temperatures = model.temperatures().clone();
+    assert_eq!(
+        model.step(&fluxes, 60.0),
+        Err(ThermalError::Unstable {
+            time_step: 60.0,
+            maximum_time_step: model.maximum_explicit_time_step(),
+        })
+    );
+    assert_eq!(model.temperatures(), &temperatures);
+
+    // The Crank-Nicolson scheme is unconditionally stable.
+    model.scheme = ConductionScheme::CrankNicolson;
+    assert!(model.step(&fluxes, 60.0).is_ok());
+
+    for (fluxes, time_step) in [
+        (List::from_row_slice(&[100.0]), 10.0),
+        (List::from_row_slice(&[100.0, -1.0]), 10.0),
+        (fluxes, 0.0),
+    ] {
+        assert!(matches!(
+            model.step(&fluxes, time_step),
+            Err(ThermalError::InvalidParameter(_))
+        ));
+    }
+    assert!(ThermalModel::new(properties(), 1, 2, 0.01, 250.0).is_err());
+    let mut invalid = properties();
+    invalid.emissivity = 1.5;
+    assert!(ThermalModel::new(invalid, 1, 20, 0.01, 250.0).is_err());
+}
@@ -0,0 +1,223 @@
+use tool::{OrbitalElements, TwoBodyError, Vector, DEG2RAD, TAU};
+
+const GM_EARTH_KM: f64 = 398_600.0;
+const GM_SUN: f64 = 1.32712440018e20;
+
+#[test]
+fn stumpff_continuity() {
+    for &z in &[-1.0, 1.0] {
+        let below = z * (1.0 - 1e-9);
+        let above = z * (1.0 + 1e-9);
+        assert!(relative_eq!(
+            tool::stumpff_c(below),
+            tool::stumpff_c(above),
+            epsilon = 1e-9
+        ));
+        assert!(relative_eq!(
+            tool::stumpff_s(below),
+            tool::stumpff_s(above),
+            epsilon = 1e-9
+        ));
+    }
+    assert_eq!(tool::stumpff_c(0.0), 0.5);
+    assert!(relative_eq!(
+        tool::stumpff_s(0.0),
+        1.0 / 6.0,
+        epsilon = 1e-16
+    ));
+}
+
+#[test]
+fn propagate_curtis() {
+    // Curtis, Orbital Mechanics for Engineering Students, example 3.7.
+    let position = Vector::new(7000.0, -12124.0, 0.0);
+    let velocity = Vector::new(2.6679, 4.6210, 0.0);
+
+    let (position, velocity) = tool::propagate(&position, &velocity, 3600.0, GM_EARTH_KM).unwrap();
+
+    assert!(relative_eq!(
+        position,
+        Vector::new(-3297.8, 7413.4, 0.0),
+        epsilon = 0.5
+    ));
+    assert!(relative_eq!(
+        velocity,
+        Vector::new(-8.2977, -0.96309, 0.0),
+        epsilon = 1e-3
+    ));
+}
+
+#[test]
+fn propagate_circular() {
+    let radius = 7000.0;
+    let speed = (GM_EARTH_KM / radius).sqrt();
+    let period = TAU * radius / speed;
+    let position = Vector::new(radius, 0.0, 0.0);
+    let velocity = Vector::new(0.0, speed, 0.0);
+
+    let (quarter_position, quarter_velocity) =
+        tool::propagate(&position, &velocity, period / 4.0, GM_EARTH_KM).unwrap();
+    let (full_position, full_velocity) =
+        tool::propagate(&position, &velocity, period * 3.0, GM_EARTH_KM).unwrap();
+
+    assert!(relative_eq!(
+        quarter_position,
+        Vector::new(0.0, radius, 0.0),
+        epsilon = 1e-7
+    ));
+    assert!(relative_eq!(
+        quarter_velocity,
+        Vector::new(-speed, 0.0, 0.0),
+        epsilon = 1e-10
+    ));
+    assert!(relative_eq!(full_position, position, epsilon = 1e-6));
+    assert!(relative_eq!(full_velocity, velocity, epsilon = 1e-10));
+}
+
+#[test]
+fn propagate_matches_elements() {
+    for &(semi_major_axis, eccentricity) in &[(1.5e11, 0.3), (1.5e11, 0.97), (-5e10, 1.8)] {
+        let elements = OrbitalElements::from_true_anomaly(
+            semi_major_axis,
+            eccentricity,
+            30.0 * DEG2RAD,
+            40.0 * DEG2RAD,
+            50.0 * DEG2RAD,
+            10.0 * DEG2RAD,
+        )
+        .unwrap();
+        let time = 2e6;
+        let (position, velocity) = elements.to_state(GM_SUN).unwrap();
+        let mut expected = elements;
+        expected.mean_anomaly += elements.mean_motion(GM_SUN) * time;
+        let (expected_position, expected_velocity) = expected.to_state(GM_SUN).unwrap();
+
+        let (position, velocity) = tool::propagate(&position, &velocity, time, GM_SUN).unwrap();
+
+        assert!(relative_eq!(
+            position,
+            expected_position,
+            max_relative = 1e-9
+        ));
+        assert!(relative_eq!(
+            velocity,
+            expected_velocity,
+            max_relative = 1e-9
+        ));
+    }
+}
+
+#[test]
+fn propagate_round_trip() {
+    // Nearly parabolic and hyperbolic escapes.
+    let position = Vector::new(7000.0, 1000.0, -500.0);
+    for &speed in &[(2.0 * GM_EARTH_KM / 7000.0_f64).sqrt(), 15.0] {
+        let velocity = Vector::new(0.5, speed, 1.0).normalize() * speed;
+
+        let (forward_position, forward_velocity) =
+            tool::propagate(&position, &velocity, 86400.0, GM_EARTH_KM).unwrap();
+        let (back_position, back_velocity) =
+            tool::propagate(&forward_position, &forward_velocity, -86400.0, GM_EARTH_KM).unwrap();
+
+        assert!(relative_eq!(back_position, position, max_relative = 1e-9));
+        assert!(relative_eq!(back_velocity, velocity, max_relative = 1e-9));
+    }
+}
+
+#[test]
+fn propagate_underflow() {
+    // The starting guess of the universal anomaly underflows to zero, where nothing is bracketed.
+    let position = Vector::new(1e150, 0.0, 0.0);
+
+    assert_eq!(
+        tool::propagate(&position, &Vector::zeros(), 1e-200, 1.0),
+        Err(TwoBodyError::NotConverged { last_value: 0.0 })
+    );
+}
+
+#[test]
+fn lambert_curtis() {
+    // Curtis, Orbital Mechanics for Engineering Students, example 5.2.
+    let departure = Vector::new(5000.0, 10000.0, 2100.0);
+    let arrival = Vector::new(-14600.0, 2500.0, 7000.0);
+
+    let solutions = tool::lambert(&departure, &arrival, 3600.0, GM_EARTH_KM, true, 0).unwrap();
+
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0].revolutions, 0);
+    assert!(relative_eq!(
+        solutions[0].velocity_departure,
+        Vector::new(-5.9925, 1.9254, 3.2456),
+        epsilon = 1e-3
+    ));
+    assert!(relative_eq!(
+        solutions[0].velocity_arrival,
+        Vector::new(-3.3125, -4.1966, -0.38529),
+        epsilon = 1e-3
+    ));
+}
+
+#[test]
+fn lambert_multi_revolution() {
+    let departure = Vector::new(7000.0, 0.0, 0.0);
+    let arrival = Vector::new(-5000.0, 9000.0, 1000.0);
+    let time_of_flight = 60000.0;
+
+    for &prograde in &[true, false] {
+        let solutions = tool::lambert(
+            &departure,
+            &arrival,
+            time_of_flight,
+            GM_EARTH_KM,
+            prograde,
+            10,
+        )
+        .unwrap();
+
+        assert!(solutions.len() >= 3);
+        assert_eq!(solutions.len() % 2, 1);
+        for (index, solution) in solutions.iter().enumerate() {
+            assert_eq!(solution.revolutions, index.div_ceil(2));
+            let (position, velocity) = tool::propagate(
+                &departure,
+                &solution.velocity_departure,
+                time_of_flight,
+                GM_EARTH_KM,
+            )
+            .unwrap();
+            assert!(relative_eq!(position, arrival, max_relative = 1e-8));
+            assert!(relative_eq!(
+                velocity,
+                solution.velocity_arrival,
+                max_relative = 1e-8
+            ));
+            let angular_momentum = departure.cross(&solution.velocity_departure);
+            assert_eq!(angular_momentum[2] > 0.0, prograde);
+        }
+    }
+}
+
+#[test]
+fn lambert_errors() {
+    let departure = Vector::new(7000.0, 0.0, 0.0);
+
+    assert_eq!(
+        tool::lambert(
+            &departure,
+            &(departure * -2.0),
+            3600.0,
+            GM_EARTH_KM,
+            true,
+            0
+        ),
+        Err(TwoBodyError::CollinearPositions)
+    );
+    assert_eq!(
+        tool::lambert(&departure, &Vector::y(), -1.0, GM_EARTH_KM, true, 0),
+        Err(TwoBodyError::InvalidTimeOfFlight(-1.0))
+    );
+    assert_eq!(
+        tool::propagate(&departure, &Vector::y(), 1.0, 0.0),
+        Err(TwoBodyError::InvalidGravitationalParameter(0.0))
+    );
+}
@@ -0,0 +1,102 @@
+use super::sphere;
+use tool::{Mesh, Vector, Vectors, ViewFactorOptions, TAU};
+
+/// Two parallel squares of side 1 facing each other at a distance, with an optional square
+/// blocking the view halfway.
+fn parallel_squares(distance: f64, blocker: bool) -> (Vectors<f64>, Vec<[usize; 3]>) {
+    let mut vectors = vec![];
+    let mut faces = vec![];
+    let mut square = |height: f64, up: bool| {
+        let start = vectors.len();
+        for (x, y) in [(-0.5, -0.5), (0.5, -0.5), (0.5, 0.5), (-0.5, 0.5)].iter() {
+            vectors.push(Vector::new(*x, *y, height));
+        }
+        if up {
+            faces.push([start, start + 1, start + 2]);
+            faces.push([start, start + 2, start + 3]);
+        } else {
+            faces.push([start, start + 2, start + 1]);
+            faces.push([start, start + 3, start + 2]);
+        }
+    };
+    square(0.0, true);
+    square(distance, false);
+    if blocker {
+        square(distance / 2.0, true);
+    }
+    (Vectors::from_columns(&vectors), faces)
+}
+
+#[test]
+fn view_factors_distant_squares() {
+    let distance = 20.0;
+    let (vertices, faces) = parallel_squares(distance, false);
+
+    let factors = tool::view_factors(&vertices, &faces, &ViewFactorOptions::default());
+
+    let expected_factor = 1.0 / (TAU / 2.0 * distance.powi(2));
+    let factor = factors[(0, 2)] + factors[(0, 3)] + factors[(1, 2)] + factors[(1, 3)];
+    assert!(relative_eq!(
+        factor / 2.0,
+        expected_factor,
+        max_relative = 1e-2
+    ));
+    assert_eq!(factors[(0, 1)], 0.0);
+}
+
+#[test]
+fn view_factors_close_squares() {
+    // The view factor between two parallel squares of side equal to their distance is 0.1998.
+    let (vertices, faces) = parallel_squares(1.0, false);
+    let options = ViewFactorOptions {
+        subdivisions: 16,
+        ..ViewFactorOptions::default()
+    };
+
+    let factors = tool::view_factors(&vertices, &faces, &options);
+
+    let factor = factors[(0, 2)] + factors[(0, 3)] + factors[(1, 2)] + factors[(1, 3)];
+    assert!(relative_eq!(factor / 2.0, 0.1998, max_relative = 2e-2));
+}
+
+#[test]
+fn view_factors_occlusion() {
+    let (vertices, faces) = parallel_squares(2.0, true);
+    let options = ViewFactorOptions {
+        occlusion: false,
+        ..ViewFactorOptions::default()
+    };
+
+    let factors = tool::view_factors(&vertices, &faces, &ViewFactorOptions::default());
+    let factors_without_occlusion = tool::view_factors(&vertices, &faces, &options);
+
+    assert_eq!(factors[(0, 2)], 0.0);
+    assert!(factors_without_occlusion[(0, 2)] > 0.0);
+    assert!(factors[(0, 4)] == 0.0 && factors[(4, 2)] > 0.0);
+}
+
+#[test]
+fn view_factors_sphere_enclosure() {
+    // Inside a sphere, the view factor to a facet is its fraction of the total area.
+    let sphere = sphere(6, 12);
+    let faces = sphere
+        .faces()
+        .iter()
+        .map(|face| [face[0], face[2], face[1]])
+        .collect();
+    let mesh = Mesh::new(sphere.vertices().clone(), faces);
+    let areas = mesh.face_areas();
+
+    let factors = tool::view_factors(mesh.vertices(), mesh.faces(), &ViewFactorOptions::default());
+
+    for i in 0..mesh.number_faces() {
+        assert!(relative_eq!(factors.row(i).sum(), 1.0, epsilon = 0.05));
+        for j in 0..mesh.number_faces() {
+            assert!(relative_eq!(
+                areas[i] * factors[(i, j)],
+                areas[j] * factors[(j, i)],
+                max_relative = 1e-12
+            ));
+        }
+    }
+}